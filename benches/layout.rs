@@ -0,0 +1,65 @@
+//!Benchmark for the memory layout of dmx addresses. Run with `cargo bench --bench layout`
+//!
+//!`DMXAddress` stores universe, address and absolute dmx address, so no calculation is needed to access them. `CompactDMXAddress` only stores the absolute dmx address and calculates universe and address on access. This compares the size of both and the cost of reading universe and address from a large collection
+use std::convert::TryFrom;
+use std::hint::black_box;
+use std::mem::size_of;
+use std::time::Instant;
+
+use dmx_struct::{CompactDMXAddress, DMXAddress};
+
+///Number of addresses in the collection
+const ADDRESSES: u32 = 1_000_000;
+
+///Number of passes over the collection
+const PASSES: u32 = 20;
+
+fn main() {
+    println!(
+        "size DMXAddress {} bytes, Option<DMXAddress> {} bytes",
+        size_of::<DMXAddress>(),
+        size_of::<Option<DMXAddress>>()
+    );
+    println!(
+        "size CompactDMXAddress {} bytes, Option<CompactDMXAddress> {} bytes",
+        size_of::<CompactDMXAddress>(),
+        size_of::<Option<CompactDMXAddress>>()
+    );
+
+    let addresses: Vec<DMXAddress> = (1..=ADDRESSES)
+        .map(|absolute| DMXAddress::try_from(absolute.to_string().as_str()).unwrap())
+        .collect();
+    let compact: Vec<CompactDMXAddress> = addresses
+        .iter()
+        .copied()
+        .map(CompactDMXAddress::from)
+        .collect();
+
+    let start = Instant::now();
+    let mut sum = 0_u64;
+    for _ in 0..PASSES {
+        for address in black_box(&addresses) {
+            sum += u64::from(address.universe) + u64::from(address.address);
+        }
+    }
+    report("DMXAddress", start.elapsed().as_nanos());
+
+    let start = Instant::now();
+    let mut compact_sum = 0_u64;
+    for _ in 0..PASSES {
+        for address in black_box(&compact) {
+            compact_sum += u64::from(address.universe()) + u64::from(address.address());
+        }
+    }
+    report("CompactDMXAddress", start.elapsed().as_nanos());
+    assert_eq!(black_box(sum), black_box(compact_sum));
+}
+
+///Prints the time that was needed to read universe and address of one address
+fn report(name: &str, nanos: u128) {
+    println!(
+        "access {:<18} {:>6.2} ns/iter",
+        name,
+        nanos as f64 / f64::from(ADDRESSES * PASSES)
+    );
+}
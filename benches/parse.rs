@@ -0,0 +1,60 @@
+//!Benchmark for parsing dmx addresses. Run with `cargo bench`
+//!
+//!Every allocation is counted by a global allocator to prove that parsing never allocates
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::convert::TryFrom;
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+use dmx_struct::DMXAddress;
+
+///Number of parsed addresses per input
+const ITERATIONS: u32 = 1_000_000;
+
+///Allocator that counts every allocation and forwards it to the system allocator
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn main() {
+    for input in [
+        "1.511",
+        "4.465",
+        "63999.512",
+        "1024",
+        "32767488",
+        "2.513",
+        "1.2.3",
+        "abc",
+    ] {
+        let allocations = ALLOCATIONS.load(Ordering::SeqCst);
+        let start = Instant::now();
+        for _ in 0..ITERATIONS {
+            let _ = black_box(DMXAddress::try_from(black_box(input)));
+        }
+        let elapsed = start.elapsed();
+        let allocations = ALLOCATIONS.load(Ordering::SeqCst) - allocations;
+        println!(
+            "parse {:<12} {:>8.2} ns/iter {:>3} allocations",
+            format!("{:?}", input),
+            elapsed.as_nanos() as f64 / f64::from(ITERATIONS),
+            allocations
+        );
+        assert_eq!(0, allocations, "parsing {:?} allocated", input);
+    }
+}
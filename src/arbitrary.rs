@@ -0,0 +1,71 @@
+//!Implements `arbitrary::Arbitrary` for `DMXAddress` to generate valid dmx addresses from fuzz input. Only available with the feature `arbitrary`
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::{DMXAddress, Protocol};
+
+///Generates only valid dmx addresses, from 1.001 to 63999.512, from 4 bytes of the fuzz input
+impl<'a> Arbitrary<'a> for DMXAddress {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let absolute = u.int_in_range(1..=Protocol::Sacn.max_absolute())?;
+        //The absolute address is always in range, so this can not fail
+        Ok(DMXAddress::from_absolute(absolute).unwrap())
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <u32 as Arbitrary<'a>>::size_hint(depth)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::arbitrary::{Arbitrary, Unstructured};
+
+    use crate::DMXAddress;
+
+    #[test]
+    fn test_arbitrary() {
+        let data = [0_u8; 4];
+        let address = DMXAddress::arbitrary(&mut Unstructured::new(&data)).unwrap();
+        assert_eq!(
+            DMXAddress {
+                universe: 1,
+                address: 1,
+                absolute: 1
+            },
+            address
+        );
+    }
+
+    #[test]
+    fn test_arbitrary_2() {
+        let data = [0xFF_u8; 4];
+        let address = DMXAddress::arbitrary(&mut Unstructured::new(&data)).unwrap();
+        assert!(address.universe >= 1 && address.universe <= 63_999);
+        assert!(address.address >= 1 && address.address <= 512);
+    }
+
+    #[test]
+    fn test_arbitrary_always_valid() {
+        //Every window of the pseudo random input has to produce a valid address
+        let data: Vec<u8> = (0..1024_u32)
+            .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+            .collect();
+        let mut u = Unstructured::new(&data);
+        while !u.is_empty() {
+            let address = DMXAddress::arbitrary(&mut u).unwrap();
+            assert!(address.universe >= 1 && address.universe <= 63_999);
+            assert!(address.address >= 1 && address.address <= 512);
+            assert_eq!(
+                address.absolute,
+                u32::from(address.universe - 1) * 512 + u32::from(address.address)
+            );
+        }
+    }
+
+    #[test]
+    fn test_arbitrary_empty() {
+        //Empty input still produces the first address
+        let address = DMXAddress::arbitrary(&mut Unstructured::new(&[])).unwrap();
+        assert_eq!(1, address.absolute);
+    }
+}
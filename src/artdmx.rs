@@ -0,0 +1,304 @@
+//!Builds and parses Art-Net ArtDmx packets, which carry the levels of one universe. Only available with the feature `artnet`
+//!
+//!An ArtDmx packet starts with the id 'Art-Net', the OpCode 0x5000 and the protocol version 14, followed by sequence, physical port, the port address in the bytes `SubUni` and `Net`, the number of slots and the levels. Port address 0 is universe 1 of `DMXAddress`
+//!
+//!```rust
+//! use dmx_struct::{ArtDmx, ArtNetPortAddress, DMXUniverseBuffer};
+//!
+//! let mut buffer = DMXUniverseBuffer::new(1).unwrap();
+//! buffer.as_mut_slice()[0] = 255;
+//! let artdmx = ArtDmx::new(ArtNetPortAddress::from_u16(0).unwrap());
+//! let packet = artdmx.build(&buffer).unwrap();
+//! assert_eq!(530, packet.len());
+//! let (parsed, levels) = ArtDmx::parse(&packet).unwrap();
+//! assert_eq!(artdmx, parsed);
+//! assert_eq!(buffer, levels);
+//! ```
+use core::fmt::{Display, Formatter};
+
+use crate::{ArtNetPortAddress, DMXAddress, DMXParseError, DMXUniverseBuffer};
+
+///Id at the start of every Art-Net packet
+const ID: [u8; 8] = *b"Art-Net\0";
+///OpCode of ArtDmx, which is sent little endian
+const OP_DMX: u16 = 0x5000;
+///The lowest protocol version that is accepted and the one that is sent
+pub(crate) const PROTOCOL_VERSION: u16 = 14;
+///Number of bytes before the levels
+const HEADER_LENGTH: usize = 18;
+///Number of slots of a universe, which is the highest length of the levels
+const SLOTS: u16 = DMXAddress::ADDRESSES_PER_UNIVERSE;
+
+///Error returned if an ArtDmx packet can not be built or parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ArtDmxError {
+    ///The packet is too short for the header of ArtDmx. Holds the length in bytes
+    TooShort(usize),
+    ///The packet does not start with the id 'Art-Net'
+    InvalidId,
+    ///The packet is not an ArtDmx packet. Holds the OpCode
+    InvalidOpCode(u16),
+    ///The protocol version is older than 14
+    InvalidProtocolVersion(u16),
+    ///The number of slots is not between 1 and 512 or the packet does not hold all of them. Holds the number of slots
+    InvalidLength(u16),
+    ///The port address of the packet or the universe of the buffer is not valid
+    InvalidAddress(DMXParseError),
+    ///The buffer does not hold the universe of the port address
+    WrongUniverse {
+        ///The universe of the port address
+        expected: u16,
+        ///The universe of the buffer
+        actual: u16,
+    },
+}
+
+impl Display for ArtDmxError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ArtDmxError::TooShort(length) => write!(
+                f,
+                "packet of {} bytes is too short for an ArtDmx packet",
+                length
+            ),
+            ArtDmxError::InvalidId => write!(f, "the packet does not start with 'Art-Net'"),
+            ArtDmxError::InvalidOpCode(op_code) => {
+                write!(f, "OpCode {:#06x} is not ArtDmx", op_code)
+            }
+            ArtDmxError::InvalidProtocolVersion(version) => write!(
+                f,
+                "protocol version {} is not supported, it must be at least 14",
+                version
+            ),
+            ArtDmxError::InvalidLength(length) => write!(
+                f,
+                "length {} is not valid, it must be between 1 and {} and not exceed the packet",
+                length, SLOTS
+            ),
+            ArtDmxError::InvalidAddress(error) => write!(f, "invalid port address: {}", error),
+            ArtDmxError::WrongUniverse { expected, actual } => write!(
+                f,
+                "the buffer holds universe {}, but the port address is mapped to universe {}",
+                actual, expected
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ArtDmxError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ArtDmxError::InvalidAddress(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+///Header fields of an ArtDmx packet. The levels are taken from and returned as `DMXUniverseBuffer`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ArtDmx {
+    ///The port address of the universe
+    pub port_address: ArtNetPortAddress,
+    ///Sequence number to reorder packets (1-255). 0 disables reordering
+    pub sequence: u8,
+    ///The physical input port the data was received on. Only informative
+    pub physical: u8,
+}
+
+impl ArtDmx {
+    ///The number of bytes of a packet with 512 slots
+    pub const PACKET_LENGTH: usize = HEADER_LENGTH + SLOTS as usize;
+
+    ///Creates the header for the port address with sequence and physical 0
+    pub fn new(port_address: ArtNetPortAddress) -> Self {
+        ArtDmx {
+            port_address,
+            sequence: 0,
+            physical: 0,
+        }
+    }
+
+    ///Creates the header for the port address the universe of the buffer is mapped to. Universe 1 is port address 0. Returns an error if the universe is higher than 32768
+    pub fn for_buffer(buffer: &DMXUniverseBuffer) -> Result<Self, ArtDmxError> {
+        ArtNetPortAddress::from_dmx_universe(buffer.universe())
+            .map(ArtDmx::new)
+            .map_err(ArtDmxError::InvalidAddress)
+    }
+
+    ///Builds a packet with all 512 slots of the buffer. Returns an error if the buffer does not hold the universe of the port address
+    pub fn build(
+        &self,
+        buffer: &DMXUniverseBuffer,
+    ) -> Result<[u8; Self::PACKET_LENGTH], ArtDmxError> {
+        let universe = self.port_address.dmx_universe();
+        if buffer.universe() != universe {
+            return Err(ArtDmxError::WrongUniverse {
+                expected: universe,
+                actual: buffer.universe(),
+            });
+        }
+        let port_address = self.port_address.value().to_le_bytes();
+        let mut packet = [0; Self::PACKET_LENGTH];
+        packet[0..8].copy_from_slice(&ID);
+        packet[8..10].copy_from_slice(&OP_DMX.to_le_bytes());
+        packet[10..12].copy_from_slice(&PROTOCOL_VERSION.to_be_bytes());
+        packet[12] = self.sequence;
+        packet[13] = self.physical;
+        packet[14] = port_address[0];
+        packet[15] = port_address[1];
+        packet[16..18].copy_from_slice(&SLOTS.to_be_bytes());
+        packet[HEADER_LENGTH..].copy_from_slice(buffer.as_slice());
+        Ok(packet)
+    }
+
+    ///Parses an ArtDmx packet. Returns the header and the levels in the buffer of the universe the port address is mapped to. Senders may send less than 512 slots, the missing slots are at level 0. Bytes after the levels are ignored
+    pub fn parse(packet: &[u8]) -> Result<(Self, DMXUniverseBuffer), ArtDmxError> {
+        if packet.len() < HEADER_LENGTH {
+            return Err(ArtDmxError::TooShort(packet.len()));
+        }
+        if packet[0..8] != ID {
+            return Err(ArtDmxError::InvalidId);
+        }
+        let op_code = u16::from_le_bytes([packet[8], packet[9]]);
+        if op_code != OP_DMX {
+            return Err(ArtDmxError::InvalidOpCode(op_code));
+        }
+        let version = u16::from_be_bytes([packet[10], packet[11]]);
+        if version < PROTOCOL_VERSION {
+            return Err(ArtDmxError::InvalidProtocolVersion(version));
+        }
+        let length = u16::from_be_bytes([packet[16], packet[17]]);
+        let end = HEADER_LENGTH + usize::from(length);
+        if length == 0 || length > SLOTS || packet.len() < end {
+            return Err(ArtDmxError::InvalidLength(length));
+        }
+        let port_address =
+            ArtNetPortAddress::from_u16(u16::from_le_bytes([packet[14], packet[15]]))
+                .map_err(ArtDmxError::InvalidAddress)?;
+        let mut buffer = DMXUniverseBuffer::new(port_address.dmx_universe())
+            .map_err(ArtDmxError::InvalidAddress)?;
+        buffer.as_mut_slice()[..usize::from(length)].copy_from_slice(&packet[HEADER_LENGTH..end]);
+        let artdmx = ArtDmx {
+            port_address,
+            sequence: packet[12],
+            physical: packet[13],
+        };
+        Ok((artdmx, buffer))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ArtDmx, ArtDmxError, ArtNetPortAddress, DMXParseError, DMXUniverseBuffer};
+
+    fn packet() -> [u8; ArtDmx::PACKET_LENGTH] {
+        let mut buffer = DMXUniverseBuffer::new(0x1235).unwrap();
+        buffer.as_mut_slice()[0] = 1;
+        buffer.as_mut_slice()[511] = 2;
+        let mut artdmx = ArtDmx::for_buffer(&buffer).unwrap();
+        artdmx.sequence = 7;
+        artdmx.physical = 3;
+        artdmx.build(&buffer).unwrap()
+    }
+
+    #[test]
+    fn test_build() {
+        let packet = packet();
+        assert_eq!(b"Art-Net\0", &packet[0..8]);
+        assert_eq!(
+            &[0x00, 0x50, 0x00, 0x0E, 7, 3, 0x34, 0x12, 0x02, 0x00],
+            &packet[8..18]
+        );
+        assert_eq!(1, packet[18]);
+        assert_eq!(2, packet[529]);
+    }
+
+    #[test]
+    fn test_build_invalid() {
+        let buffer = DMXUniverseBuffer::new(2).unwrap();
+        assert!(matches!(
+            ArtDmx::new(ArtNetPortAddress::from_u16(0).unwrap()).build(&buffer),
+            Err(ArtDmxError::WrongUniverse {
+                expected: 1,
+                actual: 2
+            })
+        ));
+        assert!(matches!(
+            ArtDmx::for_buffer(&DMXUniverseBuffer::new(32_769).unwrap()),
+            Err(ArtDmxError::InvalidAddress(
+                DMXParseError::InvalidPortAddress(32_768)
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_parse() {
+        let (artdmx, buffer) = ArtDmx::parse(&packet()).unwrap();
+        assert_eq!(0x1234, artdmx.port_address.value());
+        assert_eq!(7, artdmx.sequence);
+        assert_eq!(3, artdmx.physical);
+        assert_eq!(0x1235, buffer.universe());
+        assert_eq!(1, buffer.as_slice()[0]);
+        assert_eq!(2, buffer.as_slice()[511]);
+    }
+
+    #[test]
+    fn test_parse_short() {
+        let mut packet = packet()[..20].to_vec();
+        packet[16..18].copy_from_slice(&2u16.to_be_bytes());
+        let (_, buffer) = ArtDmx::parse(&packet).unwrap();
+        assert_eq!(&[1, 0, 0], &buffer.as_slice()[0..3]);
+        packet[16..18].copy_from_slice(&3u16.to_be_bytes());
+        assert!(matches!(
+            ArtDmx::parse(&packet),
+            Err(ArtDmxError::InvalidLength(3))
+        ));
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(matches!(
+            ArtDmx::parse(&packet()[..17]),
+            Err(ArtDmxError::TooShort(17))
+        ));
+        let mut packet = packet();
+        packet[0] = b'a';
+        assert!(matches!(
+            ArtDmx::parse(&packet),
+            Err(ArtDmxError::InvalidId)
+        ));
+        let mut packet = self::packet();
+        packet[9] = 0x20;
+        assert!(matches!(
+            ArtDmx::parse(&packet),
+            Err(ArtDmxError::InvalidOpCode(0x2000))
+        ));
+        let mut packet = self::packet();
+        packet[11] = 13;
+        assert!(matches!(
+            ArtDmx::parse(&packet),
+            Err(ArtDmxError::InvalidProtocolVersion(13))
+        ));
+        let mut packet = self::packet();
+        packet[16..18].copy_from_slice(&0u16.to_be_bytes());
+        assert!(matches!(
+            ArtDmx::parse(&packet),
+            Err(ArtDmxError::InvalidLength(0))
+        ));
+        packet[16..18].copy_from_slice(&513u16.to_be_bytes());
+        assert!(matches!(
+            ArtDmx::parse(&packet),
+            Err(ArtDmxError::InvalidLength(513))
+        ));
+        let mut packet = self::packet();
+        packet[15] = 0x80;
+        assert!(matches!(
+            ArtDmx::parse(&packet),
+            Err(ArtDmxError::InvalidAddress(
+                DMXParseError::InvalidPortAddress(0x8034)
+            ))
+        ));
+    }
+}
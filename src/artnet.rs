@@ -0,0 +1,581 @@
+//!Holds the Art-Net notation of dmx addresses. Art-Net addresses a universe with a 15 bit port address that is written as net-subnet-universe, eg. 0-1-15
+//!
+//!Art-Net counts universes from 0 while `DMXAddress` counts from 1, so port address 0 (0-0-0) is universe 1
+use core::convert::{TryFrom, TryInto};
+use core::fmt::{Display, Formatter};
+
+use crate::parse::{parse_number, DEFAULT_SEPARATORS};
+use crate::{DMXAddress, DMXParseError};
+
+///15 bit Art-Net port address made of net (0-127), sub-net (0-15) and universe (0-15)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ArtNetPortAddress(u16);
+
+impl ArtNetPortAddress {
+    ///The highest port address (127-15-15)
+    pub const MAX: u16 = 0x7FFF;
+
+    ///Creates a port address from net (0-127), sub-net (0-15) and universe (0-15)
+    pub fn new(net: u8, sub_net: u8, universe: u8) -> Result<Self, DMXParseError> {
+        if net > 127 {
+            return Err(DMXParseError::InvalidNet(net.into()));
+        }
+        if sub_net > 15 {
+            return Err(DMXParseError::InvalidSubNet(sub_net.into()));
+        }
+        if universe > 15 {
+            return Err(DMXParseError::InvalidArtNetUniverse(universe.into()));
+        }
+        Ok(ArtNetPortAddress(
+            (u16::from(net) << 8) | (u16::from(sub_net) << 4) | u16::from(universe),
+        ))
+    }
+
+    ///Creates a port address from its 15 bit value (0-32767)
+    pub fn from_u16(port_address: u16) -> Result<Self, DMXParseError> {
+        if port_address > Self::MAX {
+            return Err(DMXParseError::InvalidPortAddress(port_address.into()));
+        }
+        Ok(ArtNetPortAddress(port_address))
+    }
+
+    ///Creates a port address from the lower 15 bits of the value, so it never fails
+    #[cfg(feature = "artnet_protocol")]
+    pub(crate) const fn from_u15(port_address: u16) -> Self {
+        ArtNetPortAddress(port_address & Self::MAX)
+    }
+
+    ///Returns the 15 bit value of the port address
+    pub fn value(&self) -> u16 {
+        self.0
+    }
+
+    ///Returns the net (0-127)
+    pub fn net(&self) -> u8 {
+        (self.0 >> 8) as u8
+    }
+
+    ///Returns the sub-net (0-15)
+    pub fn sub_net(&self) -> u8 {
+        ((self.0 >> 4) & 0x0F) as u8
+    }
+
+    ///Returns the universe inside the sub-net (0-15)
+    pub fn universe(&self) -> u8 {
+        (self.0 & 0x0F) as u8
+    }
+
+    ///Returns the universe of `DMXAddress` that this port address is mapped to. Port address 0 is universe 1
+    pub fn dmx_universe(&self) -> u16 {
+        self.0 + 1
+    }
+
+    ///Returns the port address that the universe of `DMXAddress` is mapped to. Universe 1 is port address 0
+    pub fn from_dmx_universe(universe: u16) -> Result<Self, DMXParseError> {
+        match universe.checked_sub(1) {
+            Some(port_address) => Self::from_u16(port_address),
+            None => Err(DMXParseError::InvalidUniverse(0)),
+        }
+    }
+}
+
+///Parses a port address of format net-subnet-universe, eg. 0-1-15
+impl TryFrom<&str> for ArtNetPortAddress {
+    type Error = DMXParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        parse_port_address(value.as_bytes(), 0)
+    }
+}
+
+///Port addresses can be used in format with {}. It will return the format 'net-subnet-universe'
+impl Display for ArtNetPortAddress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}-{}-{}", self.net(), self.sub_net(), self.universe())
+    }
+}
+
+///Dmx address in Art-Net notation made of the port address and the address in the universe (1-512), eg. 0-1-15.234
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ArtNetAddress {
+    ///The Art-Net port address of the universe
+    pub port_address: ArtNetPortAddress,
+    ///The address in the dmx universe (1-512)
+    pub address: u16,
+}
+
+///Parses a dmx address of format 'net-subnet-universe.address', eg. 0-1-15.234
+impl TryFrom<&str> for ArtNetAddress {
+    type Error = DMXParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let value = value.as_bytes();
+        if value.is_empty() {
+            return Err(DMXParseError::Empty);
+        }
+        //Without separator the address is missing. It is parsed as empty number at the end of the input
+        let separator = value
+            .iter()
+            .position(|byte| DEFAULT_SEPARATORS.contains(byte))
+            .unwrap_or(value.len());
+        let address_start = (separator + 1).min(value.len());
+        let address = &value[address_start..];
+        if let Some(second) = address
+            .iter()
+            .position(|byte| DEFAULT_SEPARATORS.contains(byte))
+        {
+            return Err(DMXParseError::TooManyDots {
+                offset: address_start + second,
+            });
+        }
+        let port_address = parse_port_address(&value[..separator], 0)?;
+        let address = parse_number(address, address_start)?;
+        if address == 0 || address > u32::from(DMXAddress::ADDRESSES_PER_UNIVERSE) {
+            return Err(DMXParseError::InvalidAddress(address));
+        }
+        Ok(ArtNetAddress {
+            port_address,
+            address: address.try_into().unwrap(),
+        })
+    }
+}
+
+///Art-Net addresses can be used in format with {}. It will return the format 'net-subnet-universe.address'
+impl Display for ArtNetAddress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}.{:03}", self.port_address, self.address)
+    }
+}
+
+///Every Art-Net address can be represented as `DMXAddress`
+impl From<ArtNetAddress> for DMXAddress {
+    fn from(value: ArtNetAddress) -> Self {
+        DMXAddress::from_parts(
+            value.port_address.dmx_universe().into(),
+            value.address.into(),
+        )
+        .unwrap()
+    }
+}
+
+///A `DMXAddress` can only be represented in Art-Net notation if its universe is not higher than 32768
+impl TryFrom<DMXAddress> for ArtNetAddress {
+    type Error = DMXParseError;
+
+    fn try_from(value: DMXAddress) -> Result<Self, Self::Error> {
+        Ok(ArtNetAddress {
+            port_address: ArtNetPortAddress::from_dmx_universe(value.universe)?,
+            address: value.address,
+        })
+    }
+}
+
+impl DMXAddress {
+    ///Converts the address to Art-Net numbering. sACN universe 1 is Art-Net port address 0 (0-0-0). Returns `InvalidPortAddress` if the universe is higher than 32768
+    ///
+    ///```rust
+    /// use std::convert::TryFrom;
+    ///
+    /// use dmx_struct::DMXAddress;
+    ///
+    /// let address = DMXAddress::try_from("1.234").unwrap();
+    /// let artnet = address.to_artnet().unwrap();
+    /// assert_eq!(0, artnet.port_address.value());
+    /// assert_eq!(address, DMXAddress::from_artnet(artnet));
+    /// ```
+    pub fn to_artnet(&self) -> Result<ArtNetAddress, DMXParseError> {
+        ArtNetAddress::try_from(*self)
+    }
+
+    ///Converts an address in Art-Net numbering to sACN numbering. Art-Net port address 0 (0-0-0) is sACN universe 1
+    pub fn from_artnet(address: ArtNetAddress) -> DMXAddress {
+        address.into()
+    }
+
+    ///Returns the bytes `Net` and `SubUni` of an ArtDmx packet that carries the address and the index of its slot in the data of the packet. `SubUni` holds sub-net and universe, `Net` the 7 bits above. Universe 1 is port address 0 and address 1 is slot 0. Returns `InvalidPortAddress` if the universe is higher than 32768
+    ///
+    ///```rust
+    /// use std::convert::TryFrom;
+    ///
+    /// use dmx_struct::DMXAddress;
+    ///
+    /// let address = DMXAddress::try_from("258.10").unwrap();
+    /// assert_eq!((1, 1, 9), address.to_artnet_wire().unwrap());
+    /// assert_eq!(address, DMXAddress::from_artnet_wire(1, 1, 9).unwrap());
+    /// ```
+    pub fn to_artnet_wire(&self) -> Result<(u8, u8, u16), DMXParseError> {
+        let port_address = ArtNetPortAddress::from_dmx_universe(self.universe)?;
+        let value = port_address.value();
+        Ok(((value >> 8) as u8, (value & 0xFF) as u8, self.address - 1))
+    }
+
+    ///Creates the address from the bytes `Net` and `SubUni` of an ArtDmx packet and the index of the slot in its data. Returns `InvalidNet` if net is higher than 127 and `InvalidAddress` with the 1-based address if the slot is higher than 511
+    pub fn from_artnet_wire(net: u8, sub_uni: u8, slot: u16) -> Result<DMXAddress, DMXParseError> {
+        if net > 127 {
+            return Err(DMXParseError::InvalidNet(net.into()));
+        }
+        if slot >= DMXAddress::ADDRESSES_PER_UNIVERSE {
+            return Err(DMXParseError::InvalidAddress(u32::from(slot) + 1));
+        }
+        let port_address = (u16::from(net) << 8) | u16::from(sub_uni);
+        DMXAddress::from_parts(u32::from(port_address) + 1, u32::from(slot) + 1)
+    }
+}
+
+///Parses a port address of format net-subnet-universe. The offset is the position of the port address in the whole input and is returned in the error
+fn parse_port_address(value: &[u8], offset: usize) -> Result<ArtNetPortAddress, DMXParseError> {
+    if value.is_empty() {
+        return Err(DMXParseError::Empty);
+    }
+    let mut parts = [0_u32; 3];
+    let mut start = 0;
+    for (i, part) in parts.iter_mut().enumerate() {
+        //The last part holds the rest of the input, so additional - make it invalid
+        let end = if i < 2 {
+            value[start..]
+                .iter()
+                .position(|byte| *byte == b'-')
+                .map_or(value.len(), |end| start + end)
+        } else {
+            value.len()
+        };
+        *part = parse_number(&value[start..end], offset + start)?;
+        start = (end + 1).min(value.len());
+    }
+    let [net, sub_net, universe] = parts;
+    if net > 127 {
+        return Err(DMXParseError::InvalidNet(net));
+    }
+    if sub_net > 15 {
+        return Err(DMXParseError::InvalidSubNet(sub_net));
+    }
+    if universe > 15 {
+        return Err(DMXParseError::InvalidArtNetUniverse(universe));
+    }
+    ArtNetPortAddress::new(net as u8, sub_net as u8, universe as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use crate::{ArtNetAddress, ArtNetPortAddress, DMXAddress, DMXParseError};
+
+    #[test]
+    fn test_port_address() {
+        let port_address = ArtNetPortAddress::new(0, 1, 15).unwrap();
+        assert_eq!(31, port_address.value());
+        assert_eq!(0, port_address.net());
+        assert_eq!(1, port_address.sub_net());
+        assert_eq!(15, port_address.universe());
+        assert_eq!(32, port_address.dmx_universe());
+    }
+
+    #[test]
+    fn test_port_address_2() {
+        let port_address = ArtNetPortAddress::from_u16(0x7FFF).unwrap();
+        assert_eq!(127, port_address.net());
+        assert_eq!(15, port_address.sub_net());
+        assert_eq!(15, port_address.universe());
+        assert_eq!(32_768, port_address.dmx_universe());
+    }
+
+    #[test]
+    fn test_port_address_invalid() {
+        assert!(matches!(
+            ArtNetPortAddress::new(128, 0, 0),
+            Err(DMXParseError::InvalidNet(128))
+        ));
+        assert!(matches!(
+            ArtNetPortAddress::new(0, 16, 0),
+            Err(DMXParseError::InvalidSubNet(16))
+        ));
+        assert!(matches!(
+            ArtNetPortAddress::new(0, 0, 16),
+            Err(DMXParseError::InvalidArtNetUniverse(16))
+        ));
+        assert!(matches!(
+            ArtNetPortAddress::from_u16(0x8000),
+            Err(DMXParseError::InvalidPortAddress(0x8000))
+        ));
+    }
+
+    #[test]
+    fn test_from_dmx_universe() {
+        assert_eq!(0, ArtNetPortAddress::from_dmx_universe(1).unwrap().value());
+        assert_eq!(
+            0x7FFF,
+            ArtNetPortAddress::from_dmx_universe(32_768)
+                .unwrap()
+                .value()
+        );
+        assert!(matches!(
+            ArtNetPortAddress::from_dmx_universe(0),
+            Err(DMXParseError::InvalidUniverse(0))
+        ));
+        assert!(matches!(
+            ArtNetPortAddress::from_dmx_universe(32_769),
+            Err(DMXParseError::InvalidPortAddress(32_768))
+        ));
+    }
+
+    #[test]
+    fn test_port_address_parse() {
+        assert_eq!(
+            ArtNetPortAddress::new(0, 1, 15).unwrap(),
+            ArtNetPortAddress::try_from("0-1-15").unwrap()
+        );
+        assert_eq!(
+            ArtNetPortAddress::new(127, 15, 15).unwrap(),
+            ArtNetPortAddress::try_from("127-15-15").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_port_address_parse_invalid() {
+        assert!(matches!(
+            ArtNetPortAddress::try_from(""),
+            Err(DMXParseError::Empty)
+        ));
+        assert!(matches!(
+            ArtNetPortAddress::try_from("0-1"),
+            Err(DMXParseError::NotANumber { offset: 3, .. })
+        ));
+        assert!(matches!(
+            ArtNetPortAddress::try_from("0-1-2-3"),
+            Err(DMXParseError::NotANumber { offset: 4, .. })
+        ));
+        assert!(matches!(
+            ArtNetPortAddress::try_from("0-x-2"),
+            Err(DMXParseError::NotANumber { offset: 2, .. })
+        ));
+        assert!(matches!(
+            ArtNetPortAddress::try_from("128-0-0"),
+            Err(DMXParseError::InvalidNet(128))
+        ));
+        assert!(matches!(
+            ArtNetPortAddress::try_from("0-16-0"),
+            Err(DMXParseError::InvalidSubNet(16))
+        ));
+        assert!(matches!(
+            ArtNetPortAddress::try_from("0-0-300"),
+            Err(DMXParseError::InvalidArtNetUniverse(300))
+        ));
+    }
+
+    #[test]
+    fn test_port_address_display() {
+        assert_eq!(
+            "0-1-15",
+            format!("{}", ArtNetPortAddress::new(0, 1, 15).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            ArtNetAddress {
+                port_address: ArtNetPortAddress::new(0, 1, 15).unwrap(),
+                address: 234
+            },
+            ArtNetAddress::try_from("0-1-15.234").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(matches!(
+            ArtNetAddress::try_from(""),
+            Err(DMXParseError::Empty)
+        ));
+        assert!(matches!(
+            ArtNetAddress::try_from("0-1-15"),
+            Err(DMXParseError::NotANumber { offset: 6, .. })
+        ));
+        assert!(matches!(
+            ArtNetAddress::try_from("0-1-15.2.3"),
+            Err(DMXParseError::TooManyDots { offset: 8 })
+        ));
+        assert!(matches!(
+            ArtNetAddress::try_from("0-1-15.513"),
+            Err(DMXParseError::InvalidAddress(513))
+        ));
+        assert!(matches!(
+            ArtNetAddress::try_from("0-1-15.0"),
+            Err(DMXParseError::InvalidAddress(0))
+        ));
+        assert!(matches!(
+            ArtNetAddress::try_from("0-1.1"),
+            Err(DMXParseError::NotANumber { offset: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            "0-1-15.234",
+            format!("{}", ArtNetAddress::try_from("0-1-15.234").unwrap())
+        );
+        assert_eq!(
+            "0-0-0.001",
+            format!("{}", ArtNetAddress::try_from("0-0-0.1").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_to_dmx_address() {
+        assert_eq!(
+            DMXAddress::try_from("32.234").unwrap(),
+            DMXAddress::from(ArtNetAddress::try_from("0-1-15.234").unwrap())
+        );
+        assert_eq!(
+            DMXAddress::try_from("1.1").unwrap(),
+            DMXAddress::parse_artnet("0-0-0.1").unwrap()
+        );
+        assert_eq!(
+            DMXAddress::try_from("32768.512").unwrap(),
+            DMXAddress::parse_artnet("127-15-15.512").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_from_dmx_address() {
+        assert_eq!(
+            "0-1-15.234",
+            format!(
+                "{}",
+                ArtNetAddress::try_from(DMXAddress::try_from("32.234").unwrap()).unwrap()
+            )
+        );
+        assert!(matches!(
+            ArtNetAddress::try_from(DMXAddress::try_from("32769.1").unwrap()),
+            Err(DMXParseError::InvalidPortAddress(32_768))
+        ));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for value in &["0-0-0.001", "0-1-15.234", "3-2-1.512", "127-15-15.512"] {
+            let address = DMXAddress::parse_artnet(value).unwrap();
+            assert_eq!(
+                *value,
+                ArtNetAddress::try_from(address).unwrap().to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn test_to_artnet() {
+        let artnet = DMXAddress::try_from("1.1").unwrap().to_artnet().unwrap();
+        assert_eq!(0, artnet.port_address.value());
+        assert_eq!(1, artnet.address);
+        let artnet = DMXAddress::try_from("32768.512")
+            .unwrap()
+            .to_artnet()
+            .unwrap();
+        assert_eq!(0x7FFF, artnet.port_address.value());
+        assert_eq!(512, artnet.address);
+        assert!(matches!(
+            DMXAddress::try_from("32769.1").unwrap().to_artnet(),
+            Err(DMXParseError::InvalidPortAddress(32_768))
+        ));
+    }
+
+    #[test]
+    fn test_from_artnet() {
+        assert_eq!(
+            DMXAddress {
+                universe: 1,
+                address: 1,
+                absolute: 1
+            },
+            DMXAddress::from_artnet(ArtNetAddress {
+                port_address: ArtNetPortAddress::from_u16(0).unwrap(),
+                address: 1
+            })
+        );
+        assert_eq!(
+            DMXAddress {
+                universe: 17,
+                address: 5,
+                absolute: 8197
+            },
+            DMXAddress::from_artnet(ArtNetAddress {
+                port_address: ArtNetPortAddress::new(0, 1, 0).unwrap(),
+                address: 5
+            })
+        );
+    }
+
+    #[test]
+    fn test_artnet_round_trip() {
+        for value in &["1.1", "2.234", "16.512", "17.1", "32768.512"] {
+            let address = DMXAddress::try_from(*value).unwrap();
+            assert_eq!(
+                address,
+                DMXAddress::from_artnet(address.to_artnet().unwrap())
+            );
+        }
+    }
+
+    #[test]
+    fn test_artnet_wire() {
+        assert_eq!(
+            (0, 0, 0),
+            DMXAddress::try_from("1.1")
+                .unwrap()
+                .to_artnet_wire()
+                .unwrap()
+        );
+        assert_eq!(
+            (0, 0x1F, 233),
+            DMXAddress::try_from("32.234")
+                .unwrap()
+                .to_artnet_wire()
+                .unwrap()
+        );
+        assert_eq!(
+            (127, 255, 511),
+            DMXAddress::try_from("32768.512")
+                .unwrap()
+                .to_artnet_wire()
+                .unwrap()
+        );
+        assert!(matches!(
+            DMXAddress::try_from("32769.1").unwrap().to_artnet_wire(),
+            Err(DMXParseError::InvalidPortAddress(32_768))
+        ));
+    }
+
+    #[test]
+    fn test_from_artnet_wire() {
+        assert_eq!(
+            DMXAddress::try_from("1.1").unwrap(),
+            DMXAddress::from_artnet_wire(0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            DMXAddress::try_from("32768.512").unwrap(),
+            DMXAddress::from_artnet_wire(127, 255, 511).unwrap()
+        );
+        assert!(matches!(
+            DMXAddress::from_artnet_wire(128, 0, 0),
+            Err(DMXParseError::InvalidNet(128))
+        ));
+        assert!(matches!(
+            DMXAddress::from_artnet_wire(0, 0, 512),
+            Err(DMXParseError::InvalidAddress(513))
+        ));
+    }
+
+    #[test]
+    fn test_artnet_wire_round_trip() {
+        for value in &["1.1", "2.100", "17.1", "257.512", "32768.256"] {
+            let address = DMXAddress::try_from(*value).unwrap();
+            let (net, sub_uni, slot) = address.to_artnet_wire().unwrap();
+            assert_eq!(
+                address,
+                DMXAddress::from_artnet_wire(net, sub_uni, slot).unwrap()
+            );
+        }
+    }
+}
@@ -0,0 +1,190 @@
+//!Converts port addresses and ArtDmx packets from and to the types of the `artnet_protocol` crate, so it can be used for transport. Only available with the feature `artnet_protocol`
+//!
+//!`PortAddress` converts from and to `ArtNetPortAddress` and `Output` from and to `ArtDmx` and the levels in a `DMXUniverseBuffer`. Port address 0 is universe 1 of `DMXAddress`
+//!
+//!```rust
+//! use std::convert::TryFrom;
+//!
+//! use artnet_protocol::Output;
+//! use dmx_struct::DMXUniverseBuffer;
+//!
+//! let mut buffer = DMXUniverseBuffer::new(1).unwrap();
+//! buffer.as_mut_slice()[0] = 255;
+//! let output = Output::try_from(&buffer).unwrap();
+//! assert_eq!(0, u16::from(output.port_address));
+//! assert_eq!(buffer, DMXUniverseBuffer::try_from(&output).unwrap());
+//! ```
+use std::convert::TryFrom;
+
+use ::artnet_protocol::{Output, PortAddress};
+
+use crate::artdmx::PROTOCOL_VERSION;
+use crate::{ArtDmx, ArtDmxError, ArtNetPortAddress, DMXAddress, DMXUniverseBuffer};
+
+///Both hold 15 bit port addresses (0-32767), so every `PortAddress` is a valid `ArtNetPortAddress`
+impl From<PortAddress> for ArtNetPortAddress {
+    fn from(port_address: PortAddress) -> Self {
+        ArtNetPortAddress::from_u15(port_address.into())
+    }
+}
+
+///Both hold 15 bit port addresses (0-32767), so every `ArtNetPortAddress` is a valid `PortAddress`
+impl From<ArtNetPortAddress> for PortAddress {
+    fn from(port_address: ArtNetPortAddress) -> Self {
+        match PortAddress::try_from(port_address.value()) {
+            Ok(port_address) => port_address,
+            //ArtNetPortAddress never exceeds ArtNetPortAddress::MAX, which is the limit of PortAddress
+            Err(_) => unreachable!("port address {} exceeds 15 bits", port_address.value()),
+        }
+    }
+}
+
+impl ArtDmx {
+    ///Creates an `Output` of the `artnet_protocol` crate with the header and all 512 slots of the buffer. Returns an error if the buffer does not hold the universe of the port address
+    pub fn to_output(&self, buffer: &DMXUniverseBuffer) -> Result<Output, ArtDmxError> {
+        let universe = self.port_address.dmx_universe();
+        if buffer.universe() != universe {
+            return Err(ArtDmxError::WrongUniverse {
+                expected: universe,
+                actual: buffer.universe(),
+            });
+        }
+        Ok(Output {
+            sequence: self.sequence,
+            physical: self.physical,
+            port_address: self.port_address.into(),
+            data: buffer.as_slice().to_vec().into(),
+            ..Output::default()
+        })
+    }
+
+    ///Reads an `Output` of the `artnet_protocol` crate like `ArtDmx::parse`. Returns the header and the levels in the buffer of the universe the port address is mapped to. Senders may send less than 512 slots, the missing slots are at level 0
+    pub fn from_output(output: &Output) -> Result<(Self, DMXUniverseBuffer), ArtDmxError> {
+        let version = u16::from_be_bytes(output.version);
+        if version < PROTOCOL_VERSION {
+            return Err(ArtDmxError::InvalidProtocolVersion(version));
+        }
+        let data = output.data.as_ref();
+        let length = u16::try_from(data.len()).unwrap_or(u16::MAX);
+        if length == 0 || length > DMXAddress::ADDRESSES_PER_UNIVERSE {
+            return Err(ArtDmxError::InvalidLength(length));
+        }
+        let port_address = ArtNetPortAddress::from(output.port_address);
+        let mut buffer = DMXUniverseBuffer::new(port_address.dmx_universe())
+            .map_err(ArtDmxError::InvalidAddress)?;
+        buffer.as_mut_slice()[..data.len()].copy_from_slice(data);
+        let artdmx = ArtDmx {
+            port_address,
+            sequence: output.sequence,
+            physical: output.physical,
+        };
+        Ok((artdmx, buffer))
+    }
+}
+
+///Creates an `Output` for the port address the universe of the buffer is mapped to with sequence and physical 0, like `ArtDmx::for_buffer`. Returns an error if the universe is higher than 32768
+impl TryFrom<&DMXUniverseBuffer> for Output {
+    type Error = ArtDmxError;
+
+    fn try_from(buffer: &DMXUniverseBuffer) -> Result<Self, Self::Error> {
+        ArtDmx::for_buffer(buffer)?.to_output(buffer)
+    }
+}
+
+///Returns the levels of an `Output` like `ArtDmx::from_output` without the header
+impl TryFrom<&Output> for DMXUniverseBuffer {
+    type Error = ArtDmxError;
+
+    fn try_from(output: &Output) -> Result<Self, Self::Error> {
+        ArtDmx::from_output(output).map(|(_, buffer)| buffer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use ::artnet_protocol::{Output, PortAddress};
+
+    use crate::{ArtDmx, ArtDmxError, ArtNetPortAddress, DMXUniverseBuffer};
+
+    #[test]
+    fn test_port_address() {
+        let port_address = ArtNetPortAddress::new(1, 2, 3).unwrap();
+        let converted = PortAddress::from(port_address);
+        assert_eq!(0x0123, u16::from(converted));
+        assert_eq!(port_address, ArtNetPortAddress::from(converted));
+        let max = ArtNetPortAddress::from_u16(ArtNetPortAddress::MAX).unwrap();
+        assert_eq!(32767, u16::from(PortAddress::from(max)));
+        assert_eq!(max, ArtNetPortAddress::from(PortAddress::from(max)));
+    }
+
+    #[test]
+    fn test_to_output() {
+        let mut buffer = DMXUniverseBuffer::new(0x1235).unwrap();
+        buffer.as_mut_slice()[0] = 1;
+        buffer.as_mut_slice()[511] = 2;
+        let mut artdmx = ArtDmx::for_buffer(&buffer).unwrap();
+        artdmx.sequence = 7;
+        artdmx.physical = 3;
+        let output = artdmx.to_output(&buffer).unwrap();
+        assert_eq!(7, output.sequence);
+        assert_eq!(3, output.physical);
+        assert_eq!(0x1234, u16::from(output.port_address));
+        assert_eq!(buffer.as_slice(), &output.data.as_ref()[..]);
+        assert_eq!((artdmx, buffer), ArtDmx::from_output(&output).unwrap());
+    }
+
+    #[test]
+    fn test_to_output_wrong_universe() {
+        let artdmx = ArtDmx::new(ArtNetPortAddress::from_u16(0).unwrap());
+        assert!(matches!(
+            artdmx.to_output(&DMXUniverseBuffer::new(2).unwrap()),
+            Err(ArtDmxError::WrongUniverse {
+                expected: 1,
+                actual: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn test_from_output_short() {
+        let output = Output {
+            port_address: PortAddress::from(4),
+            data: vec![255, 128].into(),
+            ..Output::default()
+        };
+        let buffer = DMXUniverseBuffer::try_from(&output).unwrap();
+        assert_eq!(5, buffer.universe());
+        assert_eq!(&[255, 128, 0], &buffer.as_slice()[0..3]);
+    }
+
+    #[test]
+    fn test_from_output_invalid() {
+        let output = Output {
+            data: Vec::new().into(),
+            ..Output::default()
+        };
+        assert_eq!(
+            Err(ArtDmxError::InvalidLength(0)),
+            DMXUniverseBuffer::try_from(&output)
+        );
+        let output = Output {
+            data: vec![0; 513].into(),
+            ..Output::default()
+        };
+        assert_eq!(
+            Err(ArtDmxError::InvalidLength(513)),
+            DMXUniverseBuffer::try_from(&output)
+        );
+        let output = Output {
+            version: [0, 13],
+            data: vec![0; 512].into(),
+            ..Output::default()
+        };
+        assert_eq!(
+            Err(ArtDmxError::InvalidProtocolVersion(13)),
+            DMXUniverseBuffer::try_from(&output)
+        );
+    }
+}
@@ -0,0 +1,1327 @@
+//!Holds buffers for the levels of dmx universes that are addressed by `DMXAddress`
+use core::fmt::{Display, Formatter};
+use core::ops::{Index, IndexMut};
+#[cfg(feature = "std")]
+use std::collections::{BTreeMap, BTreeSet};
+#[cfg(feature = "std")]
+use std::sync::Arc;
+
+use crate::{DMXAddress, DMXAddressRange, DMXParseError};
+#[cfg(feature = "std")]
+use crate::{DMXAddressSet, DimmerCurve};
+
+///Number of slots in a universe as usize for the levels and stamps
+const SLOTS: usize = DMXAddress::ADDRESSES_PER_UNIVERSE as usize;
+
+///Error returned if the property values of a sACN (E1.31) DMP layer can not be read into a `DMXUniverseBuffer`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DmpPayloadError {
+    ///The universe is out of range (1-63999)
+    InvalidUniverse(u16),
+    ///The start code is not the null start code 0 that holds levels
+    InvalidStartCode(u8),
+    ///The payload does not hold the start code or more than 512 slots. Holds the length in bytes
+    InvalidLength(usize),
+}
+
+impl Display for DmpPayloadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DmpPayloadError::InvalidUniverse(universe) => write!(
+                f,
+                "universe {} is out of range, it must be between 1 and {}",
+                universe,
+                DMXAddress::MAX_UNIVERSE
+            ),
+            DmpPayloadError::InvalidStartCode(start_code) => write!(
+                f,
+                "start code {} is not supported, only the null start code 0 holds levels",
+                start_code
+            ),
+            DmpPayloadError::InvalidLength(length) => write!(
+                f,
+                "dmp property values of {} bytes are not valid, they must hold the start code and at most {} slots",
+                length, SLOTS
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DmpPayloadError {}
+
+///Error returned if an address or another buffer can not be used with a buffer
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DMXBufferError {
+    ///The universe of the address is out of range (1-63999)
+    InvalidUniverse(u16),
+    ///The address in the universe is out of range (1-512)
+    InvalidAddress(u16),
+    ///The address or the other buffer is not in the universe of the buffer
+    WrongUniverse {
+        ///The universe of the buffer
+        expected: u16,
+        ///The universe of the address or the other buffer
+        actual: u16,
+    },
+}
+
+impl Display for DMXBufferError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DMXBufferError::InvalidUniverse(universe) => write!(
+                f,
+                "universe {} is out of range, it must be between 1 and {}",
+                universe,
+                DMXAddress::MAX_UNIVERSE
+            ),
+            DMXBufferError::InvalidAddress(address) => write!(
+                f,
+                "address {} is out of range, it must be between 1 and {}",
+                address, SLOTS
+            ),
+            DMXBufferError::WrongUniverse { expected, actual } => write!(
+                f,
+                "the address is in universe {}, but the buffer holds universe {}",
+                actual, expected
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DMXBufferError {}
+
+///Levels of the 512 slots of one universe. The buffer only accepts addresses of its universe
+///
+///Each slot remembers when it was written last to merge buffers Latest-Takes-Precedence. Every write advances the clock of the buffer and stamps the slot with it. The stamps are not compared by ==
+///
+///```rust
+/// use std::convert::TryFrom;
+///
+/// use dmx_struct::{DMXAddress, DMXUniverseBuffer};
+///
+/// let mut buffer = DMXUniverseBuffer::new(2).unwrap();
+/// buffer.set(&DMXAddress::try_from("2.1").unwrap(), 255).unwrap();
+/// assert_eq!(Some(255), buffer.get(&DMXAddress::try_from("2.1").unwrap()));
+/// assert!(buffer.set(&DMXAddress::try_from("1.1").unwrap(), 255).is_err());
+/// assert_eq!(255, buffer.as_slice()[0]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DMXUniverseBuffer {
+    universe: u16,
+    levels: [u8; SLOTS],
+    //Clock of the last write of each slot. 0 if the slot was never written
+    stamps: [u32; SLOTS],
+    clock: u32,
+}
+
+impl DMXUniverseBuffer {
+    ///Creates a buffer for the universe with all levels at 0. Returns an error if the universe is out of range (1-63999)
+    pub fn new(universe: u16) -> Result<DMXUniverseBuffer, DMXParseError> {
+        DMXAddress::from_parts(universe.into(), 1)?;
+        Ok(DMXUniverseBuffer {
+            universe,
+            levels: [0; SLOTS],
+            stamps: [0; SLOTS],
+            clock: 0,
+        })
+    }
+
+    ///Returns the universe of the buffer
+    pub fn universe(&self) -> u16 {
+        self.universe
+    }
+
+    ///Returns the level at the address. Returns None if the address is not in the universe of the buffer
+    pub fn get(&self, address: &DMXAddress) -> Option<u8> {
+        self.slot(address).ok().map(|index| self.levels[index])
+    }
+
+    ///Sets the level at the address. Returns an error if the address is not in the universe of the buffer
+    pub fn set(&mut self, address: &DMXAddress, level: u8) -> Result<(), DMXBufferError> {
+        let index = self.slot(address)?;
+        self.levels[index] = level;
+        self.stamps[index] = self.tick();
+        Ok(())
+    }
+
+    ///Sets all slots to the level
+    pub fn fill(&mut self, level: u8) {
+        self.levels = [level; SLOTS];
+        self.stamps = [self.tick(); SLOTS];
+    }
+
+    ///Sets all slots to 0
+    pub fn clear(&mut self) {
+        self.fill(0);
+    }
+
+    ///Returns the levels of all slots. The level of address 1 is at index 0
+    pub fn as_slice(&self) -> &[u8] {
+        &self.levels
+    }
+
+    ///Returns the levels of all slots to change them. The level of address 1 is at index 0. Changes through the slice are not stamped
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        &mut self.levels
+    }
+
+    ///Merges the levels of other into this buffer Highest-Takes-Precedence, so each slot holds the higher level of both buffers. Returns an error if the buffers hold different universes
+    ///
+    ///```rust
+    /// use dmx_struct::DMXUniverseBuffer;
+    ///
+    /// let mut a = DMXUniverseBuffer::new(1).unwrap();
+    /// let mut b = DMXUniverseBuffer::new(1).unwrap();
+    /// a.as_mut_slice()[0..2].copy_from_slice(&[100, 10]);
+    /// b.as_mut_slice()[0..2].copy_from_slice(&[50, 20]);
+    /// a.merge_htp(&b).unwrap();
+    /// assert_eq!(&[100, 20], &a.as_slice()[0..2]);
+    /// ```
+    pub fn merge_htp(&mut self, other: &DMXUniverseBuffer) -> Result<(), DMXBufferError> {
+        if other.universe != self.universe {
+            return Err(DMXBufferError::WrongUniverse {
+                expected: self.universe,
+                actual: other.universe,
+            });
+        }
+        for (level, other) in self.levels.iter_mut().zip(other.levels.iter()) {
+            *level = (*level).max(*other);
+        }
+        Ok(())
+    }
+
+    ///Returns the clock of the last write. 0 if the buffer was never written
+    pub fn clock(&self) -> u32 {
+        self.clock
+    }
+
+    ///Sets the clock, eg. to a timestamp in milliseconds that is shared by several sources. The next write is stamped with the following value. The clock never goes back, so a lower value is ignored
+    pub fn set_clock(&mut self, clock: u32) {
+        self.clock = self.clock.max(clock);
+    }
+
+    ///Returns the clock of the last write of the address. Returns None if the slot was never written or the address is not in the universe of the buffer
+    pub fn changed_at(&self, address: &DMXAddress) -> Option<u32> {
+        let index = self.slot(address).ok()?;
+        Some(self.stamps[index]).filter(|stamp| *stamp > 0)
+    }
+
+    ///Merges the levels of other into this buffer Latest-Takes-Precedence, so each slot holds the level of the buffer that wrote it last. Slots that were written at the same clock keep the level of this buffer. Returns an error if the buffers hold different universes
+    ///
+    ///```rust
+    /// use std::convert::TryFrom;
+    ///
+    /// use dmx_struct::{DMXAddress, DMXUniverseBuffer};
+    ///
+    /// let address = DMXAddress::try_from("1.1").unwrap();
+    /// let mut a = DMXUniverseBuffer::new(1).unwrap();
+    /// let mut b = DMXUniverseBuffer::new(1).unwrap();
+    /// a.set_clock(100);
+    /// a.set(&address, 255).unwrap();
+    /// b.set_clock(200);
+    /// b.set(&address, 10).unwrap();
+    /// a.merge_ltp(&b).unwrap();
+    /// assert_eq!(Some(10), a.get(&address));
+    /// ```
+    pub fn merge_ltp(&mut self, other: &DMXUniverseBuffer) -> Result<(), DMXBufferError> {
+        if other.universe != self.universe {
+            return Err(DMXBufferError::WrongUniverse {
+                expected: self.universe,
+                actual: other.universe,
+            });
+        }
+        for index in 0..SLOTS {
+            if other.stamps[index] > self.stamps[index] {
+                self.levels[index] = other.levels[index];
+                self.stamps[index] = other.stamps[index];
+            }
+        }
+        self.set_clock(other.clock);
+        Ok(())
+    }
+
+    ///Returns the ranges of consecutive slots whose level differs from previous in ascending order, eg. to only send the changed slots. Returns an error if the buffers hold different universes
+    ///
+    ///```rust
+    /// use std::convert::TryFrom;
+    ///
+    /// use dmx_struct::{DMXAddress, DMXUniverseBuffer};
+    ///
+    /// let previous = DMXUniverseBuffer::new(1).unwrap();
+    /// let mut current = previous.clone();
+    /// current.set(&DMXAddress::try_from("1.10").unwrap(), 255).unwrap();
+    /// current.set(&DMXAddress::try_from("1.11").unwrap(), 255).unwrap();
+    /// let changes: Vec<String> = current.diff(&previous).unwrap().map(|range| range.to_string()).collect();
+    /// assert_eq!(vec!["1.010-1.011"], changes);
+    /// ```
+    pub fn diff<'a>(
+        &'a self,
+        previous: &'a DMXUniverseBuffer,
+    ) -> Result<impl Iterator<Item = DMXAddressRange> + 'a, DMXBufferError> {
+        if previous.universe != self.universe {
+            return Err(DMXBufferError::WrongUniverse {
+                expected: self.universe,
+                actual: previous.universe,
+            });
+        }
+        let universe = u32::from(self.universe);
+        let changed = move |index: &usize| self.levels[*index] != previous.levels[*index];
+        //Index of the first slot that was not compared yet
+        let mut next = 0;
+        Ok(core::iter::from_fn(move || {
+            let start = (next..SLOTS).find(changed)?;
+            let end = (start..SLOTS)
+                .find(|index| !changed(index))
+                .unwrap_or(SLOTS);
+            next = end;
+            //The universe was validated when the buffer was created, so the addresses are valid
+            Some(DMXAddressRange {
+                start: DMXAddress::from_parts(universe, start as u32 + 1).ok()?,
+                end: DMXAddress::from_parts(universe, end as u32).ok()?,
+            })
+        }))
+    }
+
+    ///Returns the property values of a sACN (E1.31) DMP layer, which are the null start code followed by the levels of the 512 slots
+    ///
+    ///```rust
+    /// use dmx_struct::DMXUniverseBuffer;
+    ///
+    /// let mut buffer = DMXUniverseBuffer::new(1).unwrap();
+    /// buffer.as_mut_slice()[0] = 255;
+    /// let payload = buffer.to_dmp_payload();
+    /// assert_eq!(&[0, 255, 0], &payload[0..3]);
+    /// assert_eq!(buffer, DMXUniverseBuffer::from_dmp_payload(1, &payload).unwrap());
+    /// ```
+    pub fn to_dmp_payload(&self) -> [u8; SLOTS + 1] {
+        let mut payload = [0; SLOTS + 1];
+        payload[1..].copy_from_slice(&self.levels);
+        payload
+    }
+
+    ///Creates a buffer for the universe from the property values of a sACN (E1.31) DMP layer. Senders may send less than 512 slots, the missing slots are at level 0. All slots are stamped as written. Returns an error if the universe is out of range, the payload is empty or longer than 513 bytes or the start code is not 0
+    pub fn from_dmp_payload(universe: u16, payload: &[u8]) -> Result<Self, DmpPayloadError> {
+        let mut buffer = DMXUniverseBuffer::new(universe)
+            .map_err(|_| DmpPayloadError::InvalidUniverse(universe))?;
+        if payload.is_empty() || payload.len() > SLOTS + 1 {
+            return Err(DmpPayloadError::InvalidLength(payload.len()));
+        }
+        if payload[0] != 0 {
+            return Err(DmpPayloadError::InvalidStartCode(payload[0]));
+        }
+        buffer.levels[..payload.len() - 1].copy_from_slice(&payload[1..]);
+        buffer.stamps = [buffer.tick(); SLOTS];
+        Ok(buffer)
+    }
+
+    ///Scales all levels proportionally by the master, so 255 keeps the levels and 0 is a blackout. The result is rounded to the nearest level
+    ///
+    ///```rust
+    /// use dmx_struct::DMXUniverseBuffer;
+    ///
+    /// let mut buffer = DMXUniverseBuffer::new(1).unwrap();
+    /// buffer.as_mut_slice()[0..3].copy_from_slice(&[255, 100, 1]);
+    /// buffer.scale(128);
+    /// assert_eq!(&[128, 50, 1], &buffer.as_slice()[0..3]);
+    /// ```
+    pub fn scale(&mut self, master: u8) {
+        self.scale_where(master, |_| true);
+    }
+
+    ///Scales the levels of the slots whose index matches by the master. All scaled slots are stamped with one write
+    fn scale_where(&mut self, master: u8, matches: impl Fn(usize) -> bool) {
+        let stamp = self.tick();
+        for index in (0..SLOTS).filter(|index| matches(*index)) {
+            //level * master / 255 rounded half up, the product never exceeds u32
+            let product = u32::from(self.levels[index]) * u32::from(master);
+            self.levels[index] = ((product * 2 + 255) / 510) as u8;
+            self.stamps[index] = stamp;
+        }
+    }
+
+    ///Advances the clock for a write and returns it
+    fn tick(&mut self) -> u32 {
+        self.clock = self.clock.saturating_add(1);
+        self.clock
+    }
+
+    ///Returns the index of the slot of the address in the levels
+    fn slot(&self, address: &DMXAddress) -> Result<usize, DMXBufferError> {
+        if address.universe != self.universe {
+            return Err(DMXBufferError::WrongUniverse {
+                expected: self.universe,
+                actual: address.universe,
+            });
+        }
+        if address.address == 0 || address.address > DMXAddress::ADDRESSES_PER_UNIVERSE {
+            return Err(DMXBufferError::InvalidAddress(address.address));
+        }
+        Ok(usize::from(address.address - 1))
+    }
+}
+
+///Returns the level at the address like `get`, eg. `buffer[address]`
+///
+///Panics if the address is not in the universe of the buffer
+impl Index<DMXAddress> for DMXUniverseBuffer {
+    type Output = u8;
+
+    fn index(&self, address: DMXAddress) -> &u8 {
+        match self.slot(&address) {
+            Ok(index) => &self.levels[index],
+            Err(e) => panic!("{}", e),
+        }
+    }
+}
+
+///Returns the level at the address to change it like `set`, eg. `buffer[address] = 255`. The slot is stamped as written
+///
+///Panics if the address is not in the universe of the buffer
+impl IndexMut<DMXAddress> for DMXUniverseBuffer {
+    fn index_mut(&mut self, address: DMXAddress) -> &mut u8 {
+        match self.slot(&address) {
+            Ok(index) => {
+                self.stamps[index] = self.tick();
+                &mut self.levels[index]
+            }
+            Err(e) => panic!("{}", e),
+        }
+    }
+}
+
+///Buffers are equal if they hold the same universe and levels. When the slots were written is not compared
+impl PartialEq for DMXUniverseBuffer {
+    fn eq(&self, other: &Self) -> bool {
+        self.universe == other.universe && self.levels[..] == other.levels[..]
+    }
+}
+
+impl Eq for DMXUniverseBuffer {}
+
+///Levels of any number of universes. A universe is only allocated when a level other than 0 is set in it, so untouched universes do not use memory. Only available with the feature `std`
+///
+///```rust
+/// use std::convert::TryFrom;
+///
+/// use dmx_struct::{DMXAddress, DMXBuffer};
+///
+/// let mut buffer = DMXBuffer::new();
+/// buffer.set(&DMXAddress::try_from("40.1").unwrap(), 255).unwrap();
+/// assert_eq!(Some(255), buffer.get(&DMXAddress::try_from("40.1").unwrap()));
+/// assert_eq!(Some(0), buffer.get(&DMXAddress::try_from("1.1").unwrap()));
+/// assert_eq!(1, buffer.universes().count());
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Default)]
+pub struct DMXBuffer {
+    //The buffers are boxed, so the nodes of the map stay small
+    universes: BTreeMap<u16, Box<DMXUniverseBuffer>>,
+    //Clock that is passed on to the universes, so universes that are allocated later are stamped after the earlier writes
+    clock: u32,
+}
+
+#[cfg(feature = "std")]
+impl DMXBuffer {
+    ///Creates a buffer without any allocated universe
+    pub fn new() -> Self {
+        DMXBuffer {
+            universes: BTreeMap::new(),
+            clock: 0,
+        }
+    }
+
+    ///Returns the level at the address. Untouched universes are at level 0. Returns None if the address is not valid
+    pub fn get(&self, address: &DMXAddress) -> Option<u8> {
+        match self.universes.get(&address.universe) {
+            Some(universe) => universe.get(address),
+            None => DMXAddress::from_parts(address.universe.into(), address.address.into())
+                .ok()
+                .map(|_| 0),
+        }
+    }
+
+    ///Sets the level at the address. Allocates the universe of the address if the level is not 0. Returns an error if the address is not valid
+    pub fn set(&mut self, address: &DMXAddress, level: u8) -> Result<(), DMXBufferError> {
+        let result = match self.universes.get_mut(&address.universe) {
+            Some(universe) => {
+                universe.set_clock(self.clock);
+                universe.set(address, level).map(|_| universe.clock())
+            }
+            None => {
+                let mut universe = DMXUniverseBuffer::new(address.universe)
+                    .map_err(|_| DMXBufferError::InvalidUniverse(address.universe))?;
+                universe.set_clock(self.clock);
+                universe.set(address, level)?;
+                let clock = universe.clock();
+                if level != 0 {
+                    self.universes.insert(address.universe, Box::new(universe));
+                }
+                Ok(clock)
+            }
+        };
+        self.clock = result?;
+        Ok(())
+    }
+
+    ///Returns the buffer of the universe. Returns None if the universe is not allocated
+    pub fn universe(&self, universe: u16) -> Option<&DMXUniverseBuffer> {
+        self.universes.get(&universe).map(|universe| &**universe)
+    }
+
+    ///Returns the buffers of all allocated universes in ascending order of the universes
+    pub fn universes(&self) -> impl Iterator<Item = &DMXUniverseBuffer> {
+        self.universes.values().map(|universe| &**universe)
+    }
+
+    ///Frees the memory of the universe. Its levels are 0 afterwards. Returns true if the universe was allocated
+    pub fn release(&mut self, universe: u16) -> bool {
+        self.universes.remove(&universe).is_some()
+    }
+
+    ///Frees the memory of all universes, so all levels are 0
+    pub fn clear(&mut self) {
+        self.universes.clear();
+    }
+
+    ///Merges the levels of other into this buffer Highest-Takes-Precedence, so each slot holds the higher level of both buffers. Universes that are only allocated in other are copied
+    pub fn merge_htp(&mut self, other: &DMXBuffer) {
+        for (universe, buffer) in &other.universes {
+            match self.universes.get_mut(universe) {
+                //Both buffers hold the same universe, so merging never fails
+                Some(own) => {
+                    let _ = own.merge_htp(buffer);
+                }
+                None => {
+                    self.universes.insert(*universe, buffer.clone());
+                }
+            }
+        }
+    }
+
+    ///Returns the clock of the last write. 0 if the buffer was never written
+    pub fn clock(&self) -> u32 {
+        self.clock
+    }
+
+    ///Sets the clock, eg. to a timestamp in milliseconds that is shared by several sources. The next write is stamped with the following value. The clock never goes back, so a lower value is ignored
+    pub fn set_clock(&mut self, clock: u32) {
+        self.clock = self.clock.max(clock);
+    }
+
+    ///Merges the levels of other into this buffer Latest-Takes-Precedence, so each slot holds the level of the buffer that wrote it last. Universes that are only allocated in other are copied. Writes of level 0 to universes that are not allocated are not remembered
+    pub fn merge_ltp(&mut self, other: &DMXBuffer) {
+        for (universe, buffer) in &other.universes {
+            match self.universes.get_mut(universe) {
+                //Both buffers hold the same universe, so merging never fails
+                Some(own) => {
+                    let _ = own.merge_ltp(buffer);
+                }
+                None => {
+                    self.universes.insert(*universe, buffer.clone());
+                }
+            }
+        }
+        self.set_clock(other.clock);
+    }
+
+    ///Returns the ranges of consecutive slots whose level differs from previous by universe. Only universes with changes are returned. Universes that are not allocated are at level 0
+    ///
+    ///```rust
+    /// use std::convert::TryFrom;
+    ///
+    /// use dmx_struct::{DMXAddress, DMXBuffer};
+    ///
+    /// let previous = DMXBuffer::new();
+    /// let mut current = previous.clone();
+    /// current.set(&DMXAddress::try_from("3.1").unwrap(), 255).unwrap();
+    /// let diff = current.diff(&previous);
+    /// assert_eq!(vec![3], diff.keys().copied().collect::<Vec<u16>>());
+    /// assert_eq!("3.001-3.001", diff[&3][0].to_string());
+    /// ```
+    pub fn diff(&self, previous: &DMXBuffer) -> BTreeMap<u16, Vec<DMXAddressRange>> {
+        let universes: BTreeSet<u16> = self
+            .universes
+            .keys()
+            .chain(previous.universes.keys())
+            .copied()
+            .collect();
+        universes
+            .into_iter()
+            .filter_map(|universe| {
+                //The universes of both buffers are valid, so a missing one can always be created empty
+                let empty = DMXUniverseBuffer::new(universe).ok()?;
+                let current = self.universes.get(&universe).map_or(&empty, |b| &**b);
+                let previous = previous.universes.get(&universe).map_or(&empty, |b| &**b);
+                let ranges: Vec<DMXAddressRange> = current.diff(previous).ok()?.collect();
+                if ranges.is_empty() {
+                    None
+                } else {
+                    Some((universe, ranges))
+                }
+            })
+            .collect()
+    }
+
+    ///Captures the levels of all universes, eg. to park the output and put it back later with `restore`. The snapshot is immutable and shares its levels with all its clones
+    ///
+    ///```rust
+    /// use std::convert::TryFrom;
+    ///
+    /// use dmx_struct::{DMXAddress, DMXBuffer};
+    ///
+    /// let address = DMXAddress::try_from("1.1").unwrap();
+    /// let mut buffer = DMXBuffer::new();
+    /// buffer.set(&address, 255).unwrap();
+    /// let snapshot = buffer.snapshot();
+    /// buffer.set(&address, 0).unwrap();
+    /// buffer.restore(&snapshot);
+    /// assert_eq!(Some(255), buffer.get(&address));
+    /// ```
+    pub fn snapshot(&self) -> DMXBufferSnapshot {
+        DMXBufferSnapshot {
+            universes: Arc::new(self.universes.clone()),
+        }
+    }
+
+    ///Sets the levels of all universes to the ones of the snapshot. Universes that are not in the snapshot are released. The restored slots count as written now for Latest-Takes-Precedence merges
+    pub fn restore(&mut self, snapshot: &DMXBufferSnapshot) {
+        self.universes = (*snapshot.universes).clone();
+        let clock = self.clock;
+        for universe in self.universes.values_mut() {
+            universe.set_clock(clock);
+            universe.stamps = [universe.tick(); SLOTS];
+            self.clock = self.clock.max(universe.clock);
+        }
+    }
+
+    ///Scales all levels proportionally by the master, like a grand master. 255 keeps the levels and 0 is a blackout. The result is rounded to the nearest level
+    ///
+    ///```rust
+    /// use std::convert::TryFrom;
+    ///
+    /// use dmx_struct::{DMXAddress, DMXBuffer};
+    ///
+    /// let address = DMXAddress::try_from("2.1").unwrap();
+    /// let mut buffer = DMXBuffer::new();
+    /// buffer.set(&address, 200).unwrap();
+    /// buffer.scale(64);
+    /// assert_eq!(Some(50), buffer.get(&address));
+    /// ```
+    pub fn scale(&mut self, master: u8) {
+        self.scale_where(master, |_| false);
+    }
+
+    ///Scales all levels by the master except the ones of the excluded addresses, eg. the non-dim channels of moving lights
+    ///
+    ///```rust
+    /// use std::convert::TryFrom;
+    ///
+    /// use dmx_struct::{DMXAddress, DMXAddressSet, DMXBuffer};
+    ///
+    /// let pan = DMXAddress::try_from("1.1").unwrap();
+    /// let dimmer = DMXAddress::try_from("1.2").unwrap();
+    /// let mut buffer = DMXBuffer::new();
+    /// buffer.set(&pan, 128).unwrap();
+    /// buffer.set(&dimmer, 255).unwrap();
+    /// let non_dim: DMXAddressSet = vec![pan].into_iter().collect();
+    /// buffer.scale_except(0, &non_dim);
+    /// assert_eq!(Some(128), buffer.get(&pan));
+    /// assert_eq!(Some(0), buffer.get(&dimmer));
+    /// ```
+    pub fn scale_except(&mut self, master: u8, excluded: &DMXAddressSet) {
+        self.scale_where(master, |address| excluded.contains(address));
+    }
+
+    ///Sets all levels to 0. The universes stay allocated, so the blackout is stamped for Latest-Takes-Precedence merges
+    pub fn blackout(&mut self) {
+        self.scale(0);
+    }
+
+    ///Sets all levels to 0 except the ones of the excluded addresses
+    pub fn blackout_except(&mut self, excluded: &DMXAddressSet) {
+        self.scale_except(0, excluded);
+    }
+
+    ///Maps the levels of the addresses with the dimmer curve, eg. the addresses of a `DMXAddressRange` or a `DMXAddressSet`. Invalid addresses are ignored
+    ///
+    ///```rust
+    /// use std::convert::TryFrom;
+    ///
+    /// use dmx_struct::{DMXAddress, DMXAddressRange, DMXBuffer, DimmerCurve};
+    ///
+    /// let address = DMXAddress::try_from("1.1").unwrap();
+    /// let mut buffer = DMXBuffer::new();
+    /// buffer.set(&address, 128).unwrap();
+    /// let range = DMXAddressRange::try_from("1.1-1.10").unwrap();
+    /// buffer.apply_curve(range.addresses(), &DimmerCurve::Square);
+    /// assert_eq!(Some(64), buffer.get(&address));
+    /// ```
+    pub fn apply_curve<I: IntoIterator<Item = DMXAddress>>(
+        &mut self,
+        addresses: I,
+        curve: &DimmerCurve,
+    ) {
+        for address in addresses {
+            if let Some(level) = self.get(&address) {
+                let curved = curve.apply(level);
+                if curved != level {
+                    //The level could be read, so the address is valid
+                    let _ = self.set(&address, curved);
+                }
+            }
+        }
+    }
+
+    ///Scales the levels of all allocated universes by the master except the ones of the addresses that are excluded
+    fn scale_where(&mut self, master: u8, excluded: impl Fn(&DMXAddress) -> bool) {
+        for (universe, buffer) in self.universes.iter_mut() {
+            let universe = u32::from(*universe);
+            buffer.set_clock(self.clock);
+            buffer.scale_where(master, |index| {
+                //The universe is allocated, so its addresses are valid
+                DMXAddress::from_parts(universe, index as u32 + 1)
+                    .map_or(false, |address| !excluded(&address))
+            });
+            self.clock = self.clock.max(buffer.clock);
+        }
+    }
+
+    ///Returns the number of bytes the levels of the allocated universes use. The memory of the map that holds them is not included
+    pub fn memory_usage(&self) -> usize {
+        self.universes.len() * core::mem::size_of::<DMXUniverseBuffer>()
+    }
+}
+
+///Returns the level at the address like `get`, eg. `buffer[address]`. Untouched universes are at level 0
+///
+///Panics if the address is not valid
+#[cfg(feature = "std")]
+impl Index<DMXAddress> for DMXBuffer {
+    type Output = u8;
+
+    fn index(&self, address: DMXAddress) -> &u8 {
+        match self.universes.get(&address.universe) {
+            Some(universe) => &universe[address],
+            None => match DMXAddress::from_parts(address.universe.into(), address.address.into()) {
+                Ok(_) => &0,
+                Err(e) => panic!("{}", e),
+            },
+        }
+    }
+}
+
+///Returns the level at the address to change it like `set`, eg. `buffer[address] = 255`. The slot is stamped as written. Unlike `set` the universe is allocated even if the level stays 0
+///
+///Panics if the address is not valid
+#[cfg(feature = "std")]
+impl IndexMut<DMXAddress> for DMXBuffer {
+    fn index_mut(&mut self, address: DMXAddress) -> &mut u8 {
+        if let Err(e) = DMXAddress::from_parts(address.universe.into(), address.address.into()) {
+            panic!("{}", e);
+        }
+        let clock = self.clock;
+        //The universe was validated with the address, so creating its buffer never fails
+        let universe = self
+            .universes
+            .entry(address.universe)
+            .or_insert_with(|| Box::new(DMXUniverseBuffer::new(address.universe).unwrap()));
+        universe.set_clock(clock);
+        let index = usize::from(address.address - 1);
+        universe.stamps[index] = universe.tick();
+        self.clock = universe.clock;
+        &mut universe.levels[index]
+    }
+}
+
+///Buffers are equal if they hold the same universes and levels. When the slots were written is not compared
+#[cfg(feature = "std")]
+impl PartialEq for DMXBuffer {
+    fn eq(&self, other: &Self) -> bool {
+        self.universes == other.universes
+    }
+}
+
+#[cfg(feature = "std")]
+impl Eq for DMXBuffer {}
+
+///Immutable copy of the levels of a `DMXBuffer` created with `DMXBuffer::snapshot`. Clones share the levels, so cloning is cheap. Only available with the feature `std`
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DMXBufferSnapshot {
+    universes: Arc<BTreeMap<u16, Box<DMXUniverseBuffer>>>,
+}
+
+#[cfg(feature = "std")]
+impl DMXBufferSnapshot {
+    ///Returns the level at the address. Universes that were not allocated are at level 0. Returns None if the address is not valid
+    pub fn get(&self, address: &DMXAddress) -> Option<u8> {
+        match self.universes.get(&address.universe) {
+            Some(universe) => universe.get(address),
+            None => DMXAddress::from_parts(address.universe.into(), address.address.into())
+                .ok()
+                .map(|_| 0),
+        }
+    }
+
+    ///Returns the buffers of all universes that were allocated in ascending order of the universes
+    pub fn universes(&self) -> impl Iterator<Item = &DMXUniverseBuffer> {
+        self.universes.values().map(|universe| &**universe)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use crate::test_helpers::address;
+    use crate::{
+        DMXAddress, DMXAddressRange, DMXBufferError, DMXParseError, DMXUniverseBuffer,
+        DmpPayloadError,
+    };
+    #[cfg(feature = "std")]
+    use crate::{DMXAddressSet, DMXBuffer, DMXBufferSnapshot, DimmerCurve};
+
+    #[test]
+    fn test_new() {
+        let buffer = DMXUniverseBuffer::new(1).unwrap();
+        assert_eq!(1, buffer.universe());
+        assert_eq!(&[0; 512][..], buffer.as_slice());
+        assert!(DMXUniverseBuffer::new(63_999).is_ok());
+        assert!(matches!(
+            DMXUniverseBuffer::new(0),
+            Err(DMXParseError::InvalidUniverse(0))
+        ));
+        assert!(matches!(
+            DMXUniverseBuffer::new(64_000),
+            Err(DMXParseError::InvalidUniverse(64_000))
+        ));
+    }
+
+    #[test]
+    fn test_get_set() {
+        let mut buffer = DMXUniverseBuffer::new(3).unwrap();
+        buffer.set(&address("3.1"), 1).unwrap();
+        buffer.set(&address("3.512"), 2).unwrap();
+        assert_eq!(Some(1), buffer.get(&address("3.1")));
+        assert_eq!(Some(2), buffer.get(&address("3.512")));
+        assert_eq!(Some(0), buffer.get(&address("3.2")));
+        assert_eq!(2, buffer.as_slice()[511]);
+        assert_eq!(None, buffer.get(&address("2.1")));
+    }
+
+    #[test]
+    fn test_set_invalid() {
+        let mut buffer = DMXUniverseBuffer::new(3).unwrap();
+        assert!(matches!(
+            buffer.set(&address("4.1"), 1),
+            Err(DMXBufferError::WrongUniverse {
+                expected: 3,
+                actual: 4
+            })
+        ));
+        assert!(matches!(
+            buffer.set(
+                &DMXAddress {
+                    universe: 3,
+                    address: 513,
+                    absolute: 1537
+                },
+                1
+            ),
+            Err(DMXBufferError::InvalidAddress(513))
+        ));
+        assert_eq!(DMXUniverseBuffer::new(3).unwrap(), buffer);
+    }
+
+    #[test]
+    fn test_fill_clear() {
+        let mut buffer = DMXUniverseBuffer::new(1).unwrap();
+        buffer.fill(128);
+        assert!(buffer.as_slice().iter().all(|level| *level == 128));
+        buffer.as_mut_slice()[9] = 5;
+        assert_eq!(Some(5), buffer.get(&address("1.10")));
+        buffer.clear();
+        assert_eq!(DMXUniverseBuffer::new(1).unwrap(), buffer);
+    }
+
+    #[test]
+    fn test_merge_htp() {
+        let mut a = DMXUniverseBuffer::new(2).unwrap();
+        let mut b = DMXUniverseBuffer::new(2).unwrap();
+        a.set(&address("2.1"), 200).unwrap();
+        a.set(&address("2.2"), 10).unwrap();
+        b.set(&address("2.2"), 20).unwrap();
+        b.set(&address("2.512"), 255).unwrap();
+        a.merge_htp(&b).unwrap();
+        assert_eq!(Some(200), a.get(&address("2.1")));
+        assert_eq!(Some(20), a.get(&address("2.2")));
+        assert_eq!(Some(255), a.get(&address("2.512")));
+        assert_eq!(Some(0), a.get(&address("2.3")));
+        assert!(matches!(
+            a.merge_htp(&DMXUniverseBuffer::new(3).unwrap()),
+            Err(DMXBufferError::WrongUniverse {
+                expected: 2,
+                actual: 3
+            })
+        ));
+    }
+
+    #[test]
+    fn test_changed_at() {
+        let mut buffer = DMXUniverseBuffer::new(1).unwrap();
+        assert_eq!(0, buffer.clock());
+        assert_eq!(None, buffer.changed_at(&address("1.1")));
+        buffer.set(&address("1.1"), 10).unwrap();
+        buffer.set(&address("1.2"), 10).unwrap();
+        assert_eq!(Some(1), buffer.changed_at(&address("1.1")));
+        assert_eq!(Some(2), buffer.changed_at(&address("1.2")));
+        buffer.set_clock(1000);
+        buffer.set_clock(10);
+        assert_eq!(1000, buffer.clock());
+        buffer.set(&address("1.1"), 0).unwrap();
+        assert_eq!(Some(1001), buffer.changed_at(&address("1.1")));
+        buffer.fill(5);
+        assert_eq!(Some(1002), buffer.changed_at(&address("1.512")));
+        assert_eq!(None, buffer.changed_at(&address("2.1")));
+    }
+
+    #[test]
+    fn test_merge_ltp() {
+        let mut a = DMXUniverseBuffer::new(1).unwrap();
+        let mut b = DMXUniverseBuffer::new(1).unwrap();
+        a.set(&address("1.1"), 255).unwrap();
+        a.set(&address("1.2"), 255).unwrap();
+        b.set_clock(1);
+        b.set(&address("1.2"), 10).unwrap();
+        b.set(&address("1.3"), 20).unwrap();
+        a.set_clock(10);
+        a.set(&address("1.3"), 30).unwrap();
+        a.merge_ltp(&b).unwrap();
+        assert_eq!(Some(255), a.get(&address("1.1")));
+        //Both buffers wrote 1.2 at clock 2, so a keeps its level
+        assert_eq!(Some(255), a.get(&address("1.2")));
+        assert_eq!(Some(30), a.get(&address("1.3")));
+        b.set_clock(100);
+        b.set(&address("1.1"), 0).unwrap();
+        a.merge_ltp(&b).unwrap();
+        assert_eq!(Some(0), a.get(&address("1.1")));
+        assert_eq!(Some(101), a.changed_at(&address("1.1")));
+        assert_eq!(101, a.clock());
+        assert!(matches!(
+            a.merge_ltp(&DMXUniverseBuffer::new(2).unwrap()),
+            Err(DMXBufferError::WrongUniverse {
+                expected: 1,
+                actual: 2
+            })
+        ));
+    }
+
+    fn range(value: &str) -> DMXAddressRange {
+        DMXAddressRange::try_from(value).unwrap()
+    }
+
+    #[test]
+    fn test_diff() {
+        let previous = DMXUniverseBuffer::new(2).unwrap();
+        let mut current = previous.clone();
+        assert_eq!(0, current.diff(&previous).unwrap().count());
+        current.set(&address("2.1"), 1).unwrap();
+        current.set(&address("2.5"), 1).unwrap();
+        current.set(&address("2.6"), 1).unwrap();
+        current.set(&address("2.511"), 1).unwrap();
+        current.set(&address("2.512"), 1).unwrap();
+        assert_eq!(
+            vec![range("2.1-2.1"), range("2.5-2.6"), range("2.511-2.512")],
+            current
+                .diff(&previous)
+                .unwrap()
+                .collect::<Vec<DMXAddressRange>>()
+        );
+        current.fill(7);
+        assert_eq!(
+            vec![range("2.1-2.512")],
+            current
+                .diff(&previous)
+                .unwrap()
+                .collect::<Vec<DMXAddressRange>>()
+        );
+        assert!(matches!(
+            current.diff(&DMXUniverseBuffer::new(1).unwrap()),
+            Err(DMXBufferError::WrongUniverse {
+                expected: 2,
+                actual: 1
+            })
+        ));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_buffer() {
+        let mut buffer = DMXBuffer::new();
+        assert_eq!(Some(0), buffer.get(&address("12.100")));
+        buffer.set(&address("12.100"), 0).unwrap();
+        assert_eq!(0, buffer.universes().count());
+        assert_eq!(0, buffer.memory_usage());
+        buffer.set(&address("12.100"), 50).unwrap();
+        buffer.set(&address("3.1"), 10).unwrap();
+        buffer.set(&address("12.100"), 60).unwrap();
+        assert_eq!(Some(60), buffer.get(&address("12.100")));
+        assert_eq!(Some(10), buffer.get(&address("3.1")));
+        assert_eq!(Some(0), buffer.get(&address("12.101")));
+        assert_eq!(
+            vec![3, 12],
+            buffer
+                .universes()
+                .map(|universe| universe.universe())
+                .collect::<Vec<u16>>()
+        );
+        assert_eq!(Some(60), buffer.universe(12).map(|u| u.as_slice()[99]));
+        assert_eq!(None, buffer.universe(1));
+        assert_eq!(
+            2 * std::mem::size_of::<DMXUniverseBuffer>(),
+            buffer.memory_usage()
+        );
+        assert!(buffer.release(3));
+        assert!(!buffer.release(3));
+        assert_eq!(Some(0), buffer.get(&address("3.1")));
+        buffer.clear();
+        assert_eq!(DMXBuffer::default(), buffer);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_buffer_invalid() {
+        let mut buffer = DMXBuffer::new();
+        let invalid = DMXAddress {
+            universe: 1,
+            address: 513,
+            absolute: 513,
+        };
+        assert_eq!(None, buffer.get(&invalid));
+        assert!(matches!(
+            buffer.set(&invalid, 1),
+            Err(DMXBufferError::InvalidAddress(513))
+        ));
+        let invalid = DMXAddress {
+            universe: 64_000,
+            address: 1,
+            absolute: 32_768_001,
+        };
+        assert_eq!(None, buffer.get(&invalid));
+        assert!(matches!(
+            buffer.set(&invalid, 1),
+            Err(DMXBufferError::InvalidUniverse(64_000))
+        ));
+        assert!(buffer.universes().next().is_none());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_buffer_merge_htp() {
+        let mut a = DMXBuffer::new();
+        let mut b = DMXBuffer::new();
+        a.set(&address("1.1"), 100).unwrap();
+        a.set(&address("2.1"), 100).unwrap();
+        b.set(&address("2.1"), 150).unwrap();
+        b.set(&address("2.2"), 5).unwrap();
+        b.set(&address("3.1"), 50).unwrap();
+        a.merge_htp(&b);
+        assert_eq!(Some(100), a.get(&address("1.1")));
+        assert_eq!(Some(150), a.get(&address("2.1")));
+        assert_eq!(Some(5), a.get(&address("2.2")));
+        assert_eq!(Some(50), a.get(&address("3.1")));
+        assert_eq!(3, a.universes().count());
+        assert_eq!(Some(50), b.get(&address("3.1")));
+        let before = a.clone();
+        a.merge_htp(&DMXBuffer::new());
+        assert_eq!(before, a);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_buffer_merge_ltp() {
+        let mut a = DMXBuffer::new();
+        let mut b = DMXBuffer::new();
+        a.set(&address("1.1"), 100).unwrap();
+        b.set_clock(50);
+        b.set(&address("1.1"), 10).unwrap();
+        b.set(&address("2.1"), 20).unwrap();
+        a.set_clock(60);
+        a.set(&address("2.2"), 30).unwrap();
+        a.merge_ltp(&b);
+        assert_eq!(Some(10), a.get(&address("1.1")));
+        assert_eq!(Some(20), a.get(&address("2.1")));
+        assert_eq!(Some(30), a.get(&address("2.2")));
+        assert_eq!(61, a.clock());
+        a.set(&address("2.1"), 5).unwrap();
+        a.merge_ltp(&b);
+        assert_eq!(Some(5), a.get(&address("2.1")));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_buffer_diff() {
+        let mut previous = DMXBuffer::new();
+        previous.set(&address("1.1"), 10).unwrap();
+        previous.set(&address("2.1"), 10).unwrap();
+        let mut current = previous.clone();
+        assert!(current.diff(&previous).is_empty());
+        current.set(&address("1.1"), 20).unwrap();
+        current.set(&address("1.2"), 20).unwrap();
+        current.release(2);
+        current.set(&address("3.100"), 20).unwrap();
+        let diff = current.diff(&previous);
+        assert_eq!(vec![1, 2, 3], diff.keys().copied().collect::<Vec<u16>>());
+        assert_eq!(vec![range("1.1-1.2")], diff[&1]);
+        assert_eq!(vec![range("2.1-2.1")], diff[&2]);
+        assert_eq!(vec![range("3.100-3.100")], diff[&3]);
+        let mut unchanged = current.clone();
+        unchanged.set(&address("4.1"), 0).unwrap();
+        assert!(unchanged.diff(&current).is_empty());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_snapshot() {
+        let mut buffer = DMXBuffer::new();
+        buffer.set(&address("1.1"), 100).unwrap();
+        buffer.set(&address("2.1"), 50).unwrap();
+        let snapshot = buffer.snapshot();
+        let clone: DMXBufferSnapshot = snapshot.clone();
+        assert_eq!(snapshot, clone);
+        assert_eq!(snapshot, buffer.snapshot());
+        buffer.set(&address("1.1"), 0).unwrap();
+        buffer.set(&address("3.1"), 10).unwrap();
+        assert_ne!(snapshot, buffer.snapshot());
+        assert_eq!(Some(100), snapshot.get(&address("1.1")));
+        assert_eq!(Some(0), snapshot.get(&address("3.1")));
+        assert_eq!(2, snapshot.universes().count());
+        let clock = buffer.clock();
+        buffer.restore(&clone);
+        assert_eq!(Some(100), buffer.get(&address("1.1")));
+        assert_eq!(Some(50), buffer.get(&address("2.1")));
+        assert_eq!(None, buffer.universe(3));
+        assert_eq!(snapshot, buffer.snapshot());
+        assert_eq!(
+            Some(clock + 1),
+            buffer.universe(2).unwrap().changed_at(&address("2.2"))
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_restore_ltp() {
+        let mut buffer = DMXBuffer::new();
+        buffer.set(&address("1.1"), 100).unwrap();
+        let snapshot = buffer.snapshot();
+        let mut other = DMXBuffer::new();
+        other.set_clock(10);
+        other.set(&address("1.1"), 1).unwrap();
+        buffer.set_clock(20);
+        buffer.restore(&snapshot);
+        buffer.merge_ltp(&other);
+        assert_eq!(Some(100), buffer.get(&address("1.1")));
+    }
+
+    #[test]
+    fn test_scale() {
+        let mut buffer = DMXUniverseBuffer::new(1).unwrap();
+        buffer.as_mut_slice()[0..6].copy_from_slice(&[255, 254, 128, 127, 1, 0]);
+        buffer.scale(255);
+        assert_eq!(&[255, 254, 128, 127, 1, 0], &buffer.as_slice()[0..6]);
+        buffer.scale(127);
+        assert_eq!(&[127, 127, 64, 63, 0, 0], &buffer.as_slice()[0..6]);
+        assert_eq!(Some(2), buffer.changed_at(&address("1.512")));
+        buffer.scale(0);
+        assert_eq!(&[0; 512][..], buffer.as_slice());
+    }
+
+    #[test]
+    fn test_scale_rounding() {
+        for level in 0..=255u32 {
+            for master in 0..=255u32 {
+                let mut buffer = DMXUniverseBuffer::new(1).unwrap();
+                buffer.as_mut_slice()[0] = level as u8;
+                buffer.scale(master as u8);
+                let expected = (f64::from(level * master) / 255.0).round() as u8;
+                assert_eq!(expected, buffer.as_slice()[0]);
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_buffer_scale() {
+        let mut buffer = DMXBuffer::new();
+        buffer.set(&address("1.1"), 255).unwrap();
+        buffer.set(&address("2.1"), 100).unwrap();
+        buffer.scale(51);
+        assert_eq!(Some(51), buffer.get(&address("1.1")));
+        assert_eq!(Some(20), buffer.get(&address("2.1")));
+        assert_eq!(Some(0), buffer.get(&address("3.1")));
+        assert_eq!(4, buffer.clock());
+        buffer.blackout();
+        assert_eq!(Some(0), buffer.get(&address("1.1")));
+        assert_eq!(Some(0), buffer.get(&address("2.1")));
+        assert_eq!(2, buffer.universes().count());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_buffer_blackout_except() {
+        let mut buffer = DMXBuffer::new();
+        buffer.set(&address("1.1"), 255).unwrap();
+        buffer.set(&address("1.2"), 255).unwrap();
+        buffer.set(&address("2.512"), 255).unwrap();
+        let excluded: DMXAddressSet = vec![address("1.2"), address("2.512"), address("5.1")]
+            .into_iter()
+            .collect();
+        buffer.scale_except(128, &excluded);
+        assert_eq!(Some(128), buffer.get(&address("1.1")));
+        assert_eq!(Some(255), buffer.get(&address("1.2")));
+        buffer.blackout_except(&excluded);
+        assert_eq!(Some(0), buffer.get(&address("1.1")));
+        assert_eq!(Some(255), buffer.get(&address("1.2")));
+        assert_eq!(Some(255), buffer.get(&address("2.512")));
+        assert_eq!(Some(0), buffer.get(&address("5.1")));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_buffer_apply_curve() {
+        let mut buffer = DMXBuffer::new();
+        buffer.set(&address("1.1"), 128).unwrap();
+        buffer.set(&address("1.2"), 128).unwrap();
+        buffer.set(&address("1.3"), 255).unwrap();
+        let set: DMXAddressSet = vec![address("1.1"), address("1.3"), address("2.1")]
+            .into_iter()
+            .collect();
+        buffer.apply_curve(set.iter(), &DimmerCurve::Square);
+        assert_eq!(Some(64), buffer.get(&address("1.1")));
+        assert_eq!(Some(128), buffer.get(&address("1.2")));
+        assert_eq!(Some(255), buffer.get(&address("1.3")));
+        assert_eq!(None, buffer.universe(2));
+        let clock = buffer.clock();
+        buffer.apply_curve(set.iter(), &DimmerCurve::Linear);
+        assert_eq!(clock, buffer.clock());
+        let mut table = [0; 256];
+        table[0] = 10;
+        let range = DMXAddressRange::try_from("2.1-2.2").unwrap();
+        buffer.apply_curve(range.addresses(), &DimmerCurve::Custom(table));
+        assert_eq!(Some(10), buffer.get(&address("2.2")));
+        assert_eq!(Some(0), buffer.get(&address("2.3")));
+    }
+
+    #[test]
+    fn test_dmp_payload() {
+        let mut buffer = DMXUniverseBuffer::new(7).unwrap();
+        buffer.set(&address("7.1"), 1).unwrap();
+        buffer.set(&address("7.512"), 2).unwrap();
+        let payload = buffer.to_dmp_payload();
+        assert_eq!(0, payload[0]);
+        assert_eq!(1, payload[1]);
+        assert_eq!(2, payload[512]);
+        let parsed = DMXUniverseBuffer::from_dmp_payload(7, &payload).unwrap();
+        assert_eq!(buffer, parsed);
+        assert_eq!(Some(1), parsed.changed_at(&address("7.2")));
+        let parsed = DMXUniverseBuffer::from_dmp_payload(7, &[0, 10, 20]).unwrap();
+        assert_eq!(&[10, 20, 0], &parsed.as_slice()[0..3]);
+        assert_eq!(
+            &[0; 512][..],
+            DMXUniverseBuffer::from_dmp_payload(7, &[0])
+                .unwrap()
+                .as_slice()
+        );
+    }
+
+    #[test]
+    fn test_dmp_payload_invalid() {
+        assert!(matches!(
+            DMXUniverseBuffer::from_dmp_payload(1, &[]),
+            Err(DmpPayloadError::InvalidLength(0))
+        ));
+        assert!(matches!(
+            DMXUniverseBuffer::from_dmp_payload(1, &[0; 514]),
+            Err(DmpPayloadError::InvalidLength(514))
+        ));
+        assert!(matches!(
+            DMXUniverseBuffer::from_dmp_payload(1, &[0xCC, 1]),
+            Err(DmpPayloadError::InvalidStartCode(0xCC))
+        ));
+        assert!(matches!(
+            DMXUniverseBuffer::from_dmp_payload(0, &[0]),
+            Err(DmpPayloadError::InvalidUniverse(0))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_dmp_payload_error_message() {
+        assert_eq!(
+            "dmp property values of 514 bytes are not valid, they must hold the start code and at most 512 slots",
+            DmpPayloadError::InvalidLength(514).to_string()
+        );
+    }
+
+    #[test]
+    fn test_index() {
+        let mut buffer = DMXUniverseBuffer::new(2).unwrap();
+        let address = DMXAddress::try_from("2.5").unwrap();
+        buffer[address] = 255;
+        assert_eq!(255, buffer[address]);
+        assert_eq!(Some(255), buffer.get(&address));
+        assert_eq!(Some(1), buffer.changed_at(&address));
+        buffer[address] -= 55;
+        assert_eq!(200, buffer[address]);
+        assert_eq!(Some(2), buffer.changed_at(&address));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_wrong_universe() {
+        let buffer = DMXUniverseBuffer::new(2).unwrap();
+        let _ = buffer[DMXAddress::try_from("1.1").unwrap()];
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_mut_wrong_universe() {
+        let mut buffer = DMXUniverseBuffer::new(2).unwrap();
+        buffer[DMXAddress::try_from("1.1").unwrap()] = 1;
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_buffer_index() {
+        let mut buffer = DMXBuffer::new();
+        let address = DMXAddress::try_from("40.512").unwrap();
+        assert_eq!(0, buffer[address]);
+        assert_eq!(0, buffer.universes().count());
+        buffer[address] = 255;
+        assert_eq!(255, buffer[address]);
+        assert_eq!(Some(255), buffer.get(&address));
+        assert_eq!(1, buffer.clock());
+        buffer
+            .set(&DMXAddress::try_from("1.1").unwrap(), 1)
+            .unwrap();
+        buffer[address] = 100;
+        assert_eq!(3, buffer.clock());
+        assert_eq!(Some(3), buffer.universe(40).unwrap().changed_at(&address));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    #[should_panic]
+    fn test_buffer_index_invalid() {
+        let buffer = DMXBuffer::new();
+        let _ = buffer[DMXAddress {
+            universe: 1,
+            address: 513,
+            absolute: 513,
+        }];
+    }
+}
@@ -0,0 +1,226 @@
+//!Holds the builder to create a dmx address from its components, eg. in generated code or from a configuration
+use core::fmt::{Display, Formatter};
+
+use crate::{DMXAddress, DMXParseError};
+
+///Error returned by `DMXAddressBuilder::build` if the components do not describe a valid dmx address
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DMXAddressBuilderError {
+    ///No component of the dmx address was set
+    Empty,
+    ///The builder has an address but no universe
+    MissingUniverse,
+    ///The builder has a universe but no address
+    MissingAddress,
+    ///The absolute dmx address does not match universe and address
+    AbsoluteMismatch {
+        ///The absolute dmx address that was set
+        absolute: u32,
+        ///The absolute dmx address of universe and address
+        expected: u32,
+    },
+    ///A component is out of range
+    InvalidAddress(DMXParseError),
+}
+
+impl Display for DMXAddressBuilderError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DMXAddressBuilderError::Empty => write!(f, "no component of the dmx address is set"),
+            DMXAddressBuilderError::MissingUniverse => {
+                write!(f, "the dmx address has an address but no universe")
+            }
+            DMXAddressBuilderError::MissingAddress => {
+                write!(f, "the dmx address has a universe but no address")
+            }
+            DMXAddressBuilderError::AbsoluteMismatch { absolute, expected } => write!(
+                f,
+                "the absolute dmx address {} does not match universe and address, which are absolute dmx address {}",
+                absolute, expected
+            ),
+            DMXAddressBuilderError::InvalidAddress(error) => {
+                write!(f, "invalid dmx address: {}", error)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DMXAddressBuilderError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DMXAddressBuilderError::InvalidAddress(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+///Builder for a `DMXAddress` that is returned by `DMXAddress::builder`. Either universe and address or the absolute dmx address have to be set. If all of them are set, they have to describe the same address
+///
+///```rust
+/// use dmx_struct::DMXAddress;
+///
+/// let address = DMXAddress::builder().universe(4).address(465).build().unwrap();
+/// assert_eq!(2001, address.absolute);
+/// assert!(DMXAddress::builder().universe(4).address(513).build().is_err());
+/// assert!(DMXAddress::builder().universe(4).address(465).absolute(2000).build().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DMXAddressBuilder {
+    universe: Option<u16>,
+    address: Option<u16>,
+    absolute: Option<u32>,
+}
+
+impl DMXAddressBuilder {
+    ///Returns a builder without any components
+    pub const fn new() -> Self {
+        DMXAddressBuilder {
+            universe: None,
+            address: None,
+            absolute: None,
+        }
+    }
+
+    ///Sets the universe (1-63999)
+    pub const fn universe(mut self, universe: u16) -> Self {
+        self.universe = Some(universe);
+        self
+    }
+
+    ///Sets the address in the universe (1-512)
+    pub const fn address(mut self, address: u16) -> Self {
+        self.address = Some(address);
+        self
+    }
+
+    ///Sets the absolute dmx address (1-32767488)
+    pub const fn absolute(mut self, absolute: u32) -> Self {
+        self.absolute = Some(absolute);
+        self
+    }
+
+    ///Validates the components and creates the dmx address. Returns `InvalidAddress` with the error of the component that is out of range, `MissingUniverse` or `MissingAddress` if only one of them is set without the absolute dmx address and `AbsoluteMismatch` if the absolute dmx address does not match universe and address
+    pub fn build(&self) -> Result<DMXAddress, DMXAddressBuilderError> {
+        match (self.universe, self.address, self.absolute) {
+            (None, None, None) => Err(DMXAddressBuilderError::Empty),
+            (None, None, Some(absolute)) => {
+                DMXAddress::from_absolute(absolute).map_err(DMXAddressBuilderError::InvalidAddress)
+            }
+            (Some(universe), Some(address), absolute) => {
+                let built = DMXAddress::from_parts(universe.into(), address.into())
+                    .map_err(DMXAddressBuilderError::InvalidAddress)?;
+                match absolute {
+                    Some(absolute) if absolute != built.absolute => {
+                        Err(DMXAddressBuilderError::AbsoluteMismatch {
+                            absolute,
+                            expected: built.absolute,
+                        })
+                    }
+                    _ => Ok(built),
+                }
+            }
+            (None, Some(_), _) => Err(DMXAddressBuilderError::MissingUniverse),
+            (Some(_), None, _) => Err(DMXAddressBuilderError::MissingAddress),
+        }
+    }
+}
+
+impl DMXAddress {
+    ///Returns a builder to create a dmx address from universe and address or from the absolute dmx address
+    pub const fn builder() -> DMXAddressBuilder {
+        DMXAddressBuilder::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use crate::{DMXAddress, DMXAddressBuilder, DMXAddressBuilderError, DMXParseError};
+
+    #[test]
+    fn test_build() {
+        assert_eq!(
+            DMXAddress::try_from("4.465").unwrap(),
+            DMXAddress::builder()
+                .universe(4)
+                .address(465)
+                .build()
+                .unwrap()
+        );
+        assert_eq!(
+            DMXAddress::try_from("4.465").unwrap(),
+            DMXAddress::builder().absolute(2001).build().unwrap()
+        );
+        assert_eq!(
+            DMXAddress::try_from("4.465").unwrap(),
+            DMXAddressBuilder::new()
+                .absolute(2001)
+                .address(465)
+                .universe(4)
+                .build()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_build_out_of_range() {
+        assert!(matches!(
+            DMXAddress::builder().universe(0).address(1).build(),
+            Err(DMXAddressBuilderError::InvalidAddress(
+                DMXParseError::InvalidUniverse(0)
+            ))
+        ));
+        assert!(matches!(
+            DMXAddress::builder().universe(64000).address(1).build(),
+            Err(DMXAddressBuilderError::InvalidAddress(
+                DMXParseError::InvalidUniverse(64000)
+            ))
+        ));
+        assert!(matches!(
+            DMXAddress::builder().universe(1).address(513).build(),
+            Err(DMXAddressBuilderError::InvalidAddress(
+                DMXParseError::InvalidAddress(513)
+            ))
+        ));
+        assert!(matches!(
+            DMXAddress::builder().absolute(0).build(),
+            Err(DMXAddressBuilderError::InvalidAddress(
+                DMXParseError::InvalidAbsolute(0)
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_build_incomplete() {
+        assert!(matches!(
+            DMXAddressBuilder::default().build(),
+            Err(DMXAddressBuilderError::Empty)
+        ));
+        assert!(matches!(
+            DMXAddress::builder().address(1).build(),
+            Err(DMXAddressBuilderError::MissingUniverse)
+        ));
+        assert!(matches!(
+            DMXAddress::builder().universe(1).absolute(1).build(),
+            Err(DMXAddressBuilderError::MissingAddress)
+        ));
+    }
+
+    #[test]
+    fn test_build_mismatch() {
+        assert!(matches!(
+            DMXAddress::builder()
+                .universe(4)
+                .address(465)
+                .absolute(2000)
+                .build(),
+            Err(DMXAddressBuilderError::AbsoluteMismatch {
+                absolute: 2000,
+                expected: 2001
+            })
+        ));
+    }
+}
@@ -0,0 +1,152 @@
+//!Holds the logical dmx channel that occupies one or more consecutive slots, eg. a 16 bit channel with coarse and fine byte
+use crate::{DMXAddress, DMXParseError, DMXValue};
+
+///Logical dmx channel made of the address of its first (most significant) byte and the number of bytes (1-4). The bytes are sent in consecutive slots and roll over to the next universe
+///
+///```rust
+/// use std::convert::TryFrom;
+///
+/// use dmx_struct::{DMXAddress, DMXChannel};
+///
+/// let channel = DMXChannel::new(DMXAddress::try_from("1.511").unwrap(), 2).unwrap();
+/// assert_eq!("1.512", channel.last_address().unwrap().to_string());
+/// assert!(channel.fits_in_universe());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DMXChannel {
+    ///The address of the first (most significant) byte
+    pub start: DMXAddress,
+    ///The number of bytes of the channel (1-4)
+    pub bytes: u8,
+}
+
+impl DMXChannel {
+    ///Creates a validated dmx channel. Returns an error if the number of bytes is not supported or the channel does not end before the last valid dmx address
+    pub fn new(start: DMXAddress, bytes: u8) -> Result<DMXChannel, DMXParseError> {
+        DMXValue::max_for(bytes)?;
+        let channel = DMXChannel { start, bytes };
+        match channel.last_address() {
+            Some(_) => Ok(channel),
+            None => Err(DMXParseError::InvalidAbsolute(
+                start.absolute + u32::from(bytes) - 1,
+            )),
+        }
+    }
+
+    ///Returns the address of the last (least significant) byte. Returns None if the channel has no bytes or does not end before the last valid dmx address
+    pub fn last_address(&self) -> Option<DMXAddress> {
+        self.start.byte_address(self.bytes.checked_sub(1)?)
+    }
+
+    ///Returns the addresses of all bytes of the channel starting with the most significant byte
+    pub fn addresses(&self) -> impl Iterator<Item = DMXAddress> {
+        let start = self.start;
+        (0..self.bytes).map_while(move |index| start.byte_address(index))
+    }
+
+    ///Returns true if all bytes of the channel are in the universe of its first byte
+    pub fn fits_in_universe(&self) -> bool {
+        u32::from(self.start.address) + u32::from(self.bytes) - 1
+            <= u32::from(DMXAddress::ADDRESSES_PER_UNIVERSE)
+    }
+
+    ///Returns true if one of the bytes of the channel is sent at the address
+    pub fn contains(&self, address: &DMXAddress) -> bool {
+        address.absolute >= self.start.absolute
+            && address.absolute < self.start.absolute + u32::from(self.bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_helpers::address;
+    use crate::{DMXAddress, DMXChannel, DMXParseError};
+
+    #[test]
+    fn test_new() {
+        assert_eq!(
+            DMXChannel {
+                start: address("1.1"),
+                bytes: 2
+            },
+            DMXChannel::new(address("1.1"), 2).unwrap()
+        );
+        assert!(DMXChannel::new(address("63999.509"), 4).is_ok());
+    }
+
+    #[test]
+    fn test_new_invalid() {
+        assert!(matches!(
+            DMXChannel::new(address("1.1"), 0),
+            Err(DMXParseError::InvalidByteCount(0))
+        ));
+        assert!(matches!(
+            DMXChannel::new(address("1.1"), 5),
+            Err(DMXParseError::InvalidByteCount(5))
+        ));
+        assert!(matches!(
+            DMXChannel::new(address("63999.512"), 2),
+            Err(DMXParseError::InvalidAbsolute(32_767_489))
+        ));
+    }
+
+    #[test]
+    fn test_last_address() {
+        assert_eq!(
+            address("1.1"),
+            DMXChannel::new(address("1.1"), 1)
+                .unwrap()
+                .last_address()
+                .unwrap()
+        );
+        assert_eq!(
+            address("2.002"),
+            DMXChannel::new(address("1.511"), 4)
+                .unwrap()
+                .last_address()
+                .unwrap()
+        );
+        assert!(DMXChannel {
+            start: address("1.1"),
+            bytes: 0
+        }
+        .last_address()
+        .is_none());
+    }
+
+    #[test]
+    fn test_addresses() {
+        let channel = DMXChannel::new(address("1.511"), 3).unwrap();
+        assert_eq!(
+            vec![address("1.511"), address("1.512"), address("2.1")],
+            channel.addresses().collect::<Vec<DMXAddress>>()
+        );
+        let channel = DMXChannel {
+            start: address("63999.512"),
+            bytes: 2,
+        };
+        assert_eq!(1, channel.addresses().count());
+    }
+
+    #[test]
+    fn test_fits_in_universe() {
+        assert!(DMXChannel::new(address("1.511"), 2)
+            .unwrap()
+            .fits_in_universe());
+        assert!(!DMXChannel::new(address("1.511"), 3)
+            .unwrap()
+            .fits_in_universe());
+        assert!(DMXChannel::new(address("2.1"), 4)
+            .unwrap()
+            .fits_in_universe());
+    }
+
+    #[test]
+    fn test_contains() {
+        let channel = DMXChannel::new(address("1.511"), 3).unwrap();
+        assert!(!channel.contains(&address("1.510")));
+        assert!(channel.contains(&address("1.511")));
+        assert!(channel.contains(&address("2.1")));
+        assert!(!channel.contains(&address("2.2")));
+    }
+}
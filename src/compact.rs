@@ -0,0 +1,156 @@
+//!Holds a dmx address that only stores the absolute dmx address to keep large collections of addresses small
+use core::convert::TryFrom;
+use core::fmt::{Display, Formatter, LowerHex, UpperHex};
+use core::num::NonZeroU32;
+
+use crate::{DMXAddress, DMXParseError};
+
+///Dmx address that only stores the absolute dmx address in 4 bytes. Absolute dmx address 0 is not valid, so `Option<CompactDMXAddress>` is 4 bytes as well. Converts to and from `DMXAddress` without validation, universe and address are calculated on access
+///
+///```rust
+/// use std::convert::TryFrom;
+/// use std::mem::size_of;
+///
+/// use dmx_struct::{CompactDMXAddress, DMXAddress};
+///
+/// let compact = CompactDMXAddress::try_from("2.1").unwrap();
+/// assert_eq!(513, compact.absolute());
+/// assert_eq!(DMXAddress::try_from("2.1").unwrap(), DMXAddress::from(compact));
+/// assert_eq!(4, size_of::<Option<CompactDMXAddress>>());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CompactDMXAddress(NonZeroU32);
+
+impl CompactDMXAddress {
+    ///Returns the absolute dmx address (1-32767488)
+    pub fn absolute(&self) -> u32 {
+        self.0.get()
+    }
+
+    ///Returns the universe of the dmx address starting from 1
+    pub fn universe(&self) -> u16 {
+        ((self.absolute() - 1) / u32::from(DMXAddress::ADDRESSES_PER_UNIVERSE) + 1) as u16
+    }
+
+    ///Returns the address in the dmx universe (1-512)
+    pub fn address(&self) -> u16 {
+        ((self.absolute() - 1) % u32::from(DMXAddress::ADDRESSES_PER_UNIVERSE) + 1) as u16
+    }
+}
+
+///Only keeps the absolute dmx address. A validated `DMXAddress` never holds the absolute dmx address 0, an address that was built by hand with absolute 0 is stored as 1.001 and panics in debug builds
+impl From<DMXAddress> for CompactDMXAddress {
+    fn from(address: DMXAddress) -> Self {
+        debug_assert!(address.absolute != 0, "absolute dmx address 0 is not valid");
+        CompactDMXAddress(
+            NonZeroU32::new(address.absolute.max(1)).expect("absolute dmx address is at least 1"),
+        )
+    }
+}
+
+impl From<CompactDMXAddress> for DMXAddress {
+    fn from(address: CompactDMXAddress) -> Self {
+        DMXAddress {
+            universe: address.universe(),
+            address: address.address(),
+            absolute: address.absolute(),
+        }
+    }
+}
+
+///Parses the same notations as `DMXAddress`, eg. 1.234 or 1024
+impl TryFrom<&str> for CompactDMXAddress {
+    type Error = DMXParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        DMXAddress::try_from(value).map(CompactDMXAddress::from)
+    }
+}
+
+///Formats the same way as `DMXAddress`, eg. 1.001
+impl Display for CompactDMXAddress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&DMXAddress::from(*self), f)
+    }
+}
+
+impl LowerHex for CompactDMXAddress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        LowerHex::fmt(&self.absolute(), f)
+    }
+}
+
+impl UpperHex for CompactDMXAddress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        UpperHex::fmt(&self.absolute(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+    use std::mem::size_of;
+
+    use crate::{CompactDMXAddress, DMXAddress, DMXParseError};
+
+    #[test]
+    fn test_size() {
+        assert_eq!(4, size_of::<CompactDMXAddress>());
+        assert_eq!(4, size_of::<Option<CompactDMXAddress>>());
+    }
+
+    #[test]
+    fn test_conversion() {
+        for value in &["1.1", "1.512", "2.1", "12.345", "63999.512"] {
+            let address = DMXAddress::try_from(*value).unwrap();
+            let compact = CompactDMXAddress::from(address);
+            assert_eq!(address.universe, compact.universe());
+            assert_eq!(address.address, compact.address());
+            assert_eq!(address.absolute, compact.absolute());
+            assert_eq!(address, DMXAddress::from(compact));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "absolute dmx address 0 is not valid")]
+    #[cfg(debug_assertions)]
+    fn test_from_absolute_0() {
+        let _ = CompactDMXAddress::from(DMXAddress {
+            universe: 1,
+            address: 1,
+            absolute: 0,
+        });
+    }
+
+    #[test]
+    fn test_try_from() {
+        assert_eq!(
+            CompactDMXAddress::from(DMXAddress::try_from("4.465").unwrap()),
+            CompactDMXAddress::try_from("2001").unwrap()
+        );
+        assert!(matches!(
+            CompactDMXAddress::try_from("0"),
+            Err(DMXParseError::InvalidAbsolute(0))
+        ));
+        assert!(matches!(
+            CompactDMXAddress::try_from(""),
+            Err(DMXParseError::Empty)
+        ));
+    }
+
+    #[test]
+    fn test_format() {
+        let compact = CompactDMXAddress::try_from("2.1").unwrap();
+        assert_eq!("2.001", compact.to_string());
+        assert_eq!("201", format!("{:x}", compact));
+        assert_eq!("0x201", format!("{:#X}", compact));
+    }
+
+    #[test]
+    fn test_ord() {
+        assert!(
+            CompactDMXAddress::try_from("1.512").unwrap()
+                < CompactDMXAddress::try_from("2.1").unwrap()
+        );
+    }
+}
@@ -0,0 +1,350 @@
+//!Implements CSV import and export of `DMXPatch` as used by paperwork tools like Lightwright. Only available with the feature `csv`
+//!
+//!The CSV needs a header with the columns fixture, address and footprint. The address is parsed like `TryFrom<&str>` (eg. 1.234 or 746). If there is a column universe, the column address only holds the address in the universe. Other columns are ignored
+//!
+//!```rust
+//! use dmx_struct::DMXPatch;
+//!
+//! let csv = "fixture,universe,address,footprint\n101,1,1,30\n102,1,31,16\n";
+//! let patch: DMXPatch<u32> = DMXPatch::read_csv(csv.as_bytes()).unwrap();
+//! let mut out = Vec::new();
+//! patch.write_csv(&mut out).unwrap();
+//! assert_eq!(csv, String::from_utf8(out).unwrap());
+//! ```
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+use std::io::{Read, Write};
+use std::str::FromStr;
+
+use crate::parse::parse_number;
+use crate::{DMXAddress, DMXFootprint, DMXFootprintError, DMXParseError, DMXPatch};
+
+///Error returned if a CSV can not be read into or written from a `DMXPatch`. Errors of a row hold the line number in the CSV starting at 1
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum PatchCsvError {
+    ///The CSV could not be read or written
+    Csv(::csv::Error),
+    ///The header does not contain a required column
+    MissingColumn(&'static str),
+    ///The fixture id of a row could not be parsed
+    InvalidFixture {
+        ///Line of the row in the CSV
+        line: u64,
+    },
+    ///A cell of a row holds an invalid universe, address or footprint
+    InvalidCell {
+        ///Line of the row in the CSV
+        line: u64,
+        ///Name of the column of the cell
+        column: &'static str,
+        ///The reason why the cell is invalid
+        source: DMXParseError,
+    },
+    ///The start address and the width of a row do not form a valid footprint
+    InvalidFootprint {
+        ///Line of the row in the CSV
+        line: u64,
+        ///The reason why the footprint is invalid
+        source: DMXFootprintError,
+    },
+    ///The fixture of a row was already patched in a previous row
+    DuplicateFixture {
+        ///Line of the row in the CSV
+        line: u64,
+    },
+    ///The footprint of a row overlaps the footprint of a previous row
+    Collision {
+        ///Line of the row in the CSV
+        line: u64,
+        ///The fixture of the previous row
+        fixture: String,
+    },
+}
+
+impl<K: Ord + Clone + FromStr + Display> DMXPatch<K> {
+    ///Reads a patch from CSV with the columns fixture, address and footprint and the optional column universe. Returns an error with the line number of the first invalid row
+    pub fn read_csv<R: Read>(reader: R) -> Result<DMXPatch<K>, PatchCsvError> {
+        let mut reader = ::csv::ReaderBuilder::new()
+            .trim(::csv::Trim::All)
+            .from_reader(reader);
+        let headers = reader.headers()?.clone();
+        let column = |name: &'static str| {
+            headers
+                .iter()
+                .position(|header| header.eq_ignore_ascii_case(name))
+                .ok_or(PatchCsvError::MissingColumn(name))
+        };
+        let fixture = column("fixture")?;
+        let address = column("address")?;
+        let footprint = column("footprint")?;
+        let universe = column("universe").ok();
+        let mut patch = DMXPatch::new();
+        for record in reader.records() {
+            let record = record?;
+            let line = record.position().map_or(0, |position| position.line());
+            let cell = |index: usize| record.get(index).unwrap_or("");
+            let invalid = |column: &'static str| {
+                move |source: DMXParseError| PatchCsvError::InvalidCell {
+                    line,
+                    column,
+                    source,
+                }
+            };
+            //An empty cell is reported as Empty like an empty address of TryFrom<&str>
+            let number = |index: usize, column: &'static str| match cell(index) {
+                "" => Err(invalid(column)(DMXParseError::Empty)),
+                value => parse_number(value.as_bytes(), 0).map_err(invalid(column)),
+            };
+            let id =
+                K::from_str(cell(fixture)).map_err(|_| PatchCsvError::InvalidFixture { line })?;
+            let start = match universe {
+                Some(universe) => {
+                    let universe = number(universe, "universe")?;
+                    let address = number(address, "address")?;
+                    DMXAddress::from_parts(universe, address).map_err(|source| match source {
+                        DMXParseError::InvalidUniverse(_) => invalid("universe")(source),
+                        _ => invalid("address")(source),
+                    })?
+                }
+                None => DMXAddress::try_from(cell(address)).map_err(invalid("address"))?,
+            };
+            let width = number(footprint, "footprint")?;
+            let invalid_footprint = |source| PatchCsvError::InvalidFootprint { line, source };
+            let width = u16::try_from(width)
+                .map_err(|_| invalid_footprint(DMXFootprintError::InvalidWidth(width)))?;
+            let footprint = DMXFootprint::new(start, width).map_err(invalid_footprint)?;
+            if patch.get(&id).is_some() {
+                return Err(PatchCsvError::DuplicateFixture { line });
+            }
+            patch
+                .insert(id, footprint)
+                .map_err(|collision| PatchCsvError::Collision {
+                    line,
+                    fixture: collision.id.to_string(),
+                })?;
+        }
+        Ok(patch)
+    }
+
+    ///Writes the patch as CSV with the columns fixture, universe, address and footprint in ascending order of the addresses
+    pub fn write_csv<W: Write>(&self, writer: W) -> Result<(), PatchCsvError> {
+        let mut writer = ::csv::Writer::from_writer(writer);
+        writer.write_record(["fixture", "universe", "address", "footprint"])?;
+        for (id, footprint) in self.iter() {
+            writer.write_record(&[
+                id.to_string(),
+                footprint.start.universe.to_string(),
+                footprint.start.address.to_string(),
+                footprint.width.to_string(),
+            ])?;
+        }
+        writer.flush().map_err(|e| PatchCsvError::Csv(e.into()))?;
+        Ok(())
+    }
+}
+
+impl From<::csv::Error> for PatchCsvError {
+    fn from(error: ::csv::Error) -> Self {
+        PatchCsvError::Csv(error)
+    }
+}
+
+impl Display for PatchCsvError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PatchCsvError::Csv(error) => write!(f, "invalid csv: {}", error),
+            PatchCsvError::MissingColumn(column) => {
+                write!(f, "the csv does not contain the column '{}'", column)
+            }
+            PatchCsvError::InvalidFixture { line } => {
+                write!(f, "line {}: the fixture is not valid", line)
+            }
+            PatchCsvError::InvalidCell {
+                line,
+                column,
+                source,
+            } => write!(f, "line {}: invalid {}: {}", line, column, source),
+            PatchCsvError::InvalidFootprint { line, source } => {
+                write!(f, "line {}: invalid footprint: {}", line, source)
+            }
+            PatchCsvError::DuplicateFixture { line } => {
+                write!(f, "line {}: the fixture is already patched", line)
+            }
+            PatchCsvError::Collision { line, fixture } => write!(
+                f,
+                "line {}: the footprint overlaps fixture {}",
+                line, fixture
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PatchCsvError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PatchCsvError::Csv(error) => Some(error),
+            PatchCsvError::InvalidCell { source, .. } => Some(source),
+            PatchCsvError::InvalidFootprint { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_helpers::footprint;
+    use crate::{DMXFootprintError, DMXParseError, DMXPatch, PatchCsvError};
+
+    fn read(csv: &str) -> Result<DMXPatch<String>, PatchCsvError> {
+        DMXPatch::read_csv(csv.as_bytes())
+    }
+
+    #[test]
+    fn test_read() {
+        let patch = read("Fixture,Universe,Address,Footprint,Type\nspot 1,1,1,30,Spot\nwash 1, 2 , 512 ,1,Wash\n").unwrap();
+        assert_eq!(2, patch.len());
+        assert_eq!(
+            Some(&footprint("1.1", 30)),
+            patch.get(&"spot 1".to_string())
+        );
+        assert_eq!(
+            Some(&footprint("2.512", 1)),
+            patch.get(&"wash 1".to_string())
+        );
+    }
+
+    #[test]
+    fn test_read_address_column() {
+        let patch = read("address,fixture,footprint\n1.234,a,10\n1024,b,1\n1/100,c,5\n").unwrap();
+        assert_eq!(Some(&footprint("1.234", 10)), patch.get(&"a".to_string()));
+        assert_eq!(Some(&footprint("2.512", 1)), patch.get(&"b".to_string()));
+        assert_eq!(Some(&footprint("1.100", 5)), patch.get(&"c".to_string()));
+    }
+
+    #[test]
+    fn test_read_missing_column() {
+        assert!(matches!(
+            read("fixture,universe,footprint\n1,1,1\n"),
+            Err(PatchCsvError::MissingColumn("address"))
+        ));
+    }
+
+    #[test]
+    fn test_read_invalid_cell() {
+        assert!(matches!(
+            read("fixture,address,footprint\na,1.1,1\nb,1.513,1\n"),
+            Err(PatchCsvError::InvalidCell {
+                line: 3,
+                column: "address",
+                source: DMXParseError::InvalidAddress(513)
+            })
+        ));
+        assert!(matches!(
+            read("fixture,universe,address,footprint\na,0,1,1\n"),
+            Err(PatchCsvError::InvalidCell {
+                line: 2,
+                column: "universe",
+                source: DMXParseError::InvalidUniverse(0)
+            })
+        ));
+        assert!(matches!(
+            read("fixture,universe,address,footprint\na,1,x,1\n"),
+            Err(PatchCsvError::InvalidCell {
+                line: 2,
+                column: "address",
+                source: DMXParseError::NotANumber { .. }
+            })
+        ));
+        assert!(matches!(
+            read("fixture,address,footprint\na,1.1,0\n"),
+            Err(PatchCsvError::InvalidFootprint {
+                line: 2,
+                source: DMXFootprintError::InvalidWidth(0)
+            })
+        ));
+        assert!(matches!(
+            read("fixture,address,footprint\na,1.1,70000\n"),
+            Err(PatchCsvError::InvalidFootprint {
+                line: 2,
+                source: DMXFootprintError::InvalidWidth(70_000)
+            })
+        ));
+        assert!(matches!(
+            read("fixture,address,footprint\na,,1\n"),
+            Err(PatchCsvError::InvalidCell {
+                line: 2,
+                column: "address",
+                source: DMXParseError::Empty
+            })
+        ));
+        assert!(matches!(
+            read("fixture,universe,address,footprint\na,1,,1\n"),
+            Err(PatchCsvError::InvalidCell {
+                line: 2,
+                column: "address",
+                source: DMXParseError::Empty
+            })
+        ));
+        assert!(matches!(
+            read("fixture,universe,address,footprint\na,1,513,1\n"),
+            Err(PatchCsvError::InvalidCell {
+                line: 2,
+                column: "address",
+                source: DMXParseError::InvalidAddress(513)
+            })
+        ));
+    }
+
+    #[test]
+    fn test_read_invalid_fixture() {
+        assert!(matches!(
+            DMXPatch::<u32>::read_csv("fixture,address,footprint\n1,1.1,1\nx,1.2,1\n".as_bytes()),
+            Err(PatchCsvError::InvalidFixture { line: 3 })
+        ));
+    }
+
+    #[test]
+    fn test_read_collision() {
+        let error = read("fixture,address,footprint\na,1.1,30\nb,1.2,1\n").unwrap_err();
+        assert!(matches!(
+            &error,
+            PatchCsvError::Collision { line: 3, fixture } if fixture == "a"
+        ));
+        assert_eq!(
+            "line 3: the footprint overlaps fixture a",
+            error.to_string()
+        );
+        assert!(matches!(
+            read("fixture,address,footprint\na,1.1,1\na,1.2,1\n"),
+            Err(PatchCsvError::DuplicateFixture { line: 3 })
+        ));
+    }
+
+    #[test]
+    fn test_write() {
+        let mut patch = DMXPatch::new();
+        patch.insert(2, footprint("2.1", 16)).unwrap();
+        patch.insert(1, footprint("1.483", 30)).unwrap();
+        let mut out = Vec::new();
+        patch.write_csv(&mut out).unwrap();
+        assert_eq!(
+            "fixture,universe,address,footprint\n1,1,483,30\n2,2,1,16\n",
+            String::from_utf8(out).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut patch = DMXPatch::new();
+        patch
+            .insert("spot, left".to_string(), footprint("1.1", 30))
+            .unwrap();
+        patch
+            .insert("wash".to_string(), footprint("3.100", 16))
+            .unwrap();
+        let mut out = Vec::new();
+        patch.write_csv(&mut out).unwrap();
+        assert_eq!(patch, DMXPatch::read_csv(out.as_slice()).unwrap());
+    }
+}
@@ -0,0 +1,91 @@
+//!Holds dimmer curves that map the levels of a buffer before output, eg. to correct the gamma of LED fixtures
+
+///Curve that maps a level to the level that is output. All curves keep 0 and 255 except custom ones
+///
+///```rust
+/// use dmx_struct::DimmerCurve;
+///
+/// assert_eq!(128, DimmerCurve::Linear.apply(128));
+/// assert_eq!(64, DimmerCurve::Square.apply(128));
+/// assert_eq!(255, DimmerCurve::SCurve.apply(255));
+/// ```
+//The table is stored inline, so curves can be used without allocation on embedded targets
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DimmerCurve {
+    ///Outputs the level unchanged
+    #[default]
+    Linear,
+    ///Squares the level, which is close to the gamma 2.2 of LEDs. Low levels are dimmed more finely
+    Square,
+    ///Smoothstep curve that is flat at both ends, like the preheat and top of incandescent dimmers
+    SCurve,
+    ///Lookup table that holds the output level at the index of each level
+    Custom([u8; 256]),
+}
+
+impl DimmerCurve {
+    ///Returns the output level of the level. The result is rounded to the nearest level
+    pub fn apply(&self, level: u8) -> u8 {
+        let x = u32::from(level);
+        match self {
+            DimmerCurve::Linear => level,
+            //x² / 255 rounded half up
+            DimmerCurve::Square => ((x * x * 2 + 255) / 510) as u8,
+            //(3x² - 2x³) with x in 0..1 is (3 * 255 * x² - 2 * x³) / 255² for levels, which never exceeds 255³
+            DimmerCurve::SCurve => {
+                let y = 3 * 255 * x * x - 2 * x * x * x;
+                ((y * 2 + 255 * 255) / (2 * 255 * 255)) as u8
+            }
+            DimmerCurve::Custom(table) => table[usize::from(level)],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::DimmerCurve;
+
+    #[test]
+    fn test_linear() {
+        for level in 0..=255 {
+            assert_eq!(level, DimmerCurve::Linear.apply(level));
+        }
+        assert_eq!(DimmerCurve::Linear, DimmerCurve::default());
+    }
+
+    #[test]
+    fn test_square() {
+        for level in 0..=255u8 {
+            let x = f64::from(level) / 255.0;
+            let expected = (x * x * 255.0).round() as u8;
+            assert_eq!(expected, DimmerCurve::Square.apply(level));
+        }
+        assert_eq!(0, DimmerCurve::Square.apply(0));
+        assert_eq!(255, DimmerCurve::Square.apply(255));
+    }
+
+    #[test]
+    fn test_s_curve() {
+        for level in 0..=255u8 {
+            let x = f64::from(level) / 255.0;
+            let expected = ((3.0 * x * x - 2.0 * x * x * x) * 255.0).round() as u8;
+            assert_eq!(expected, DimmerCurve::SCurve.apply(level));
+        }
+        assert_eq!(128, DimmerCurve::SCurve.apply(128));
+        assert!(DimmerCurve::SCurve.apply(32) < 32);
+        assert!(DimmerCurve::SCurve.apply(224) > 224);
+    }
+
+    #[test]
+    fn test_custom() {
+        let mut table = [0; 256];
+        for (index, level) in table.iter_mut().enumerate() {
+            *level = 255 - index as u8;
+        }
+        let curve = DimmerCurve::Custom(table);
+        assert_eq!(255, curve.apply(0));
+        assert_eq!(0, curve.apply(255));
+        assert_eq!(155, curve.apply(100));
+    }
+}
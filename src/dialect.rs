@@ -0,0 +1,182 @@
+//!Holds the address syntax of lighting consoles to parse dmx addresses that are copied from them and to format them for the export back to the console
+use crate::{
+    AddressFormat, DMXAddress, DMXParseError, FormatOptions, FormattedDMXAddress, ParseOptions,
+};
+
+///Syntax of a dmx address on a lighting console. Parsing with a dialect shares the validation with `TryFrom<&str>` but only accepts the notation of the console
+///
+///```rust
+/// use dmx_struct::{DMXAddress, Dialect};
+///
+/// let ma = DMXAddress::parse_dialect("2.1", Dialect::MA).unwrap();
+/// let eos = DMXAddress::parse_dialect("2/1", Dialect::Eos).unwrap();
+/// assert_eq!(ma, eos);
+/// assert!(DMXAddress::parse_dialect("2/1", Dialect::MA).is_err());
+/// assert_eq!("2.001", ma.format_dialect(Dialect::MA).to_string());
+/// assert_eq!("2/1", ma.format_dialect(Dialect::Eos).to_string());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dialect {
+    ///grandMA writes 'universe.address', eg. 2.001. Absolute dmx addresses are accepted as well, eg. 513
+    MA,
+    ///Hog writes 'universe/address', eg. 2/1. Absolute dmx addresses are not accepted
+    Hog,
+    ///ETC Eos writes 'port/offset' where the port is the universe and the offset the address in the universe, eg. 2/1. Absolute dmx addresses are accepted as well, eg. 513
+    Eos,
+}
+
+impl Dialect {
+    ///Returns the options that parse the notation of the console
+    pub const fn parse_options(&self) -> ParseOptions {
+        match self {
+            Dialect::MA => ParseOptions::new().separators(b"."),
+            Dialect::Hog => ParseOptions::new()
+                .separators(b"/")
+                .format(AddressFormat::Separated),
+            Dialect::Eos => ParseOptions::new().separators(b"/"),
+        }
+    }
+
+    ///Returns the options that format the canonical notation of the console, eg. 2.001 for grandMA and 2/1 for Hog and Eos
+    pub const fn format_options(&self) -> FormatOptions {
+        match self {
+            Dialect::MA => FormatOptions::new(),
+            Dialect::Hog | Dialect::Eos => FormatOptions::new().separator('/').address_width(0),
+        }
+    }
+}
+
+impl DMXAddress {
+    ///Parses a dmx address in the notation of a lighting console, eg. 1.234 for grandMA or 1/234 for Hog and Eos
+    pub fn parse_dialect(value: &str, dialect: Dialect) -> Result<DMXAddress, DMXParseError> {
+        Self::parse_with(value, &dialect.parse_options())
+    }
+
+    ///Returns the dmx address in the canonical notation of a lighting console to be used with {}, eg. 1.234 for grandMA or 1/234 for Hog and Eos. The result is always accepted by `DMXAddress::parse_dialect` with the same dialect
+    pub fn format_dialect(&self, dialect: Dialect) -> FormattedDMXAddress {
+        self.format_with(&dialect.format_options())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_helpers::address;
+    use crate::{AddressFormat, DMXAddress, DMXParseError, Dialect};
+
+    #[test]
+    fn test_ma() {
+        assert_eq!(
+            address("1.234"),
+            DMXAddress::parse_dialect("1.234", Dialect::MA).unwrap()
+        );
+        assert_eq!(
+            address("2.1"),
+            DMXAddress::parse_dialect("513", Dialect::MA).unwrap()
+        );
+        assert!(matches!(
+            DMXAddress::parse_dialect("1/234", Dialect::MA),
+            Err(DMXParseError::InvalidSeparator {
+                offset: 1,
+                separator: '/'
+            })
+        ));
+    }
+
+    #[test]
+    fn test_hog() {
+        assert_eq!(
+            address("1.234"),
+            DMXAddress::parse_dialect("1/234", Dialect::Hog).unwrap()
+        );
+        assert!(matches!(
+            DMXAddress::parse_dialect("513", Dialect::Hog),
+            Err(DMXParseError::UnsupportedFormat(AddressFormat::Absolute))
+        ));
+        assert_eq!(
+            Err(DMXParseError::InvalidSeparator {
+                offset: 1,
+                separator: '.'
+            }),
+            DMXAddress::parse_dialect("1.234", Dialect::Hog)
+        );
+        assert_eq!(
+            "the separator '.' at position 1 is not accepted between universe and address",
+            DMXAddress::parse_dialect("1.234", Dialect::Hog)
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_eos() {
+        assert_eq!(
+            address("2.1"),
+            DMXAddress::parse_dialect("2/1", Dialect::Eos).unwrap()
+        );
+        assert_eq!(
+            address("2.1"),
+            DMXAddress::parse_dialect("513", Dialect::Eos).unwrap()
+        );
+        assert!(matches!(
+            DMXAddress::parse_dialect("1:1", Dialect::Eos),
+            Err(DMXParseError::InvalidSeparator {
+                offset: 1,
+                separator: ':'
+            })
+        ));
+    }
+
+    #[test]
+    fn test_validation() {
+        for dialect in &[Dialect::MA, Dialect::Hog, Dialect::Eos] {
+            assert!(matches!(
+                DMXAddress::parse_dialect("", *dialect),
+                Err(DMXParseError::Empty)
+            ));
+        }
+        assert!(matches!(
+            DMXAddress::parse_dialect("1.513", Dialect::MA),
+            Err(DMXParseError::InvalidAddress(513))
+        ));
+        assert!(matches!(
+            DMXAddress::parse_dialect("64000/1", Dialect::Hog),
+            Err(DMXParseError::InvalidUniverse(64000))
+        ));
+        assert!(matches!(
+            DMXAddress::parse_dialect("1/1/1", Dialect::Eos),
+            Err(DMXParseError::TooManyDots { offset: 3 })
+        ));
+    }
+
+    #[test]
+    fn test_format_dialect() {
+        assert_eq!(
+            "1.001",
+            address("1.1").format_dialect(Dialect::MA).to_string()
+        );
+        assert_eq!(
+            "1/1",
+            address("1.1").format_dialect(Dialect::Hog).to_string()
+        );
+        assert_eq!(
+            "63999/512",
+            address("63999.512")
+                .format_dialect(Dialect::Eos)
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for dialect in &[Dialect::MA, Dialect::Hog, Dialect::Eos] {
+            for value in &["1.1", "1.512", "2.1", "12.345", "63999.512"] {
+                let address = address(value);
+                let formatted = address.format_dialect(*dialect).to_string();
+                assert_eq!(
+                    address,
+                    DMXAddress::parse_dialect(&formatted, *dialect).unwrap()
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,572 @@
+//!Builds sACN (E1.31) data packets from universe buffers and parses received ones. Only available with the feature `e131`
+//!
+//!A data packet consists of the root layer that identifies the source with its CID, the framing layer with source name, priority, sequence number and universe and the DMP layer that holds the start code and the levels of the 512 slots
+//!
+//!```rust
+//! use std::convert::TryFrom;
+//!
+//! use dmx_struct::{DMXAddress, DMXUniverseBuffer, SacnSource};
+//!
+//! let mut source = SacnSource::new([1; 16], "Console").unwrap();
+//! let mut buffer = DMXUniverseBuffer::new(2).unwrap();
+//! buffer.set(&DMXAddress::try_from("2.1").unwrap(), 255).unwrap();
+//! let packet = source.data_packet(&buffer);
+//! assert_eq!(638, packet.len());
+//! assert_eq!(255, packet[126]);
+//! ```
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+use crate::{DMXAddress, DMXUniverseBuffer, SacnUniverse};
+
+///Identifier of ACN packets at the start of the root layer
+const ACN_PACKET_IDENTIFIER: [u8; 12] = *b"ASC-E1.17\0\0\0";
+///Vector of the root layer of a data packet
+const VECTOR_ROOT_E131_DATA: u32 = 0x0000_0004;
+///Vector of the framing layer of a data packet
+const VECTOR_E131_DATA_PACKET: u32 = 0x0000_0002;
+///Vector of the DMP layer of a data packet
+const VECTOR_DMP_SET_PROPERTY: u8 = 0x02;
+///Address type and data type of the DMP layer
+const DMP_ADDRESS_TYPE: u8 = 0xA1;
+///Address of the first property value of the DMP layer
+const DMP_FIRST_PROPERTY_ADDRESS: u16 = 0x0000;
+///Increment between the addresses of the property values of the DMP layer
+const DMP_ADDRESS_INCREMENT: u16 = 0x0001;
+///Offset of the framing layer in a data packet
+const FRAMING_OFFSET: usize = 38;
+///Offset of the DMP layer in a data packet
+const DMP_OFFSET: usize = 115;
+///Offset of the property values in a data packet
+const PROPERTY_VALUES_OFFSET: usize = 125;
+///Number of property values, which are the start code and the slots of a universe
+const PROPERTY_VALUE_COUNT: u16 = DMXAddress::ADDRESSES_PER_UNIVERSE + 1;
+
+///Error returned if a sACN packet can not be built or parsed. Errors of a field of a received packet hold the byte offset of the field
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SacnPacketError {
+    ///The source name does not fit into the 64 bytes of the framing layer including its terminating null. Holds the length in bytes
+    SourceNameTooLong(usize),
+    ///The priority is out of range (0-200)
+    InvalidPriority(u8),
+    ///The packet is too short to hold the layers of a data packet and the start code. Holds the length in bytes
+    TooShort(usize),
+    ///The packet does not start with the preamble, postamble and ACN packet identifier of E1.17
+    InvalidIdentifier,
+    ///A vector or the address and data type of a layer is not the one of a data packet
+    InvalidVector {
+        ///Byte offset of the vector in the packet
+        offset: usize,
+        ///The vector that was received
+        vector: u32,
+    },
+    ///The length of a layer or the number of property values does not match the length of the packet
+    InvalidLength {
+        ///Byte offset of the field in the packet
+        offset: usize,
+        ///The length that was received
+        length: u16,
+    },
+    ///The first property address or the address increment of the DMP layer is not the one of a data packet
+    InvalidPropertyAddress {
+        ///Byte offset of the field in the packet
+        offset: usize,
+        ///The value that was received
+        value: u16,
+    },
+    ///The universe of the framing layer does not carry dmx data (1-63999)
+    InvalidUniverse(u16),
+    ///The start code is not the null start code 0 that holds levels
+    InvalidStartCode(u8),
+}
+
+impl Display for SacnPacketError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SacnPacketError::SourceNameTooLong(length) => write!(
+                f,
+                "source name of {} bytes is too long, it must not be longer than 63 bytes",
+                length
+            ),
+            SacnPacketError::InvalidPriority(priority) => write!(
+                f,
+                "priority {} is out of range, it must be between 0 and 200",
+                priority
+            ),
+            SacnPacketError::TooShort(length) => write!(
+                f,
+                "packet of {} bytes is too short for a sACN data packet",
+                length
+            ),
+            SacnPacketError::InvalidIdentifier => {
+                write!(
+                    f,
+                    "the packet does not start with the ACN packet identifier"
+                )
+            }
+            SacnPacketError::InvalidVector { offset, vector } => write!(
+                f,
+                "vector {:#x} at position {} is not valid for a sACN data packet",
+                vector, offset
+            ),
+            SacnPacketError::InvalidLength { offset, length } => write!(
+                f,
+                "length {} at position {} does not match the length of the packet",
+                length, offset
+            ),
+            SacnPacketError::InvalidPropertyAddress { offset, value } => write!(
+                f,
+                "property address field {:#x} at position {} is not valid for a sACN data packet",
+                value, offset
+            ),
+            SacnPacketError::InvalidUniverse(universe) => write!(
+                f,
+                "universe {} is out of range, it must be between 1 and {}",
+                universe,
+                DMXAddress::MAX_UNIVERSE
+            ),
+            SacnPacketError::InvalidStartCode(start_code) => write!(
+                f,
+                "start code {} is not supported, only the null start code 0 holds levels",
+                start_code
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SacnPacketError {}
+
+///Source of sACN data packets. The source is identified by its CID and name and sends at one priority. It keeps a separate sequence number for each universe it sends
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SacnSource {
+    cid: [u8; 16],
+    source_name: String,
+    priority: u8,
+    //Sequence number of the next packet by universe
+    sequences: BTreeMap<u16, u8>,
+}
+
+impl SacnSource {
+    ///The number of bytes of a data packet with 512 slots
+    pub const DATA_PACKET_LENGTH: usize = PROPERTY_VALUES_OFFSET + PROPERTY_VALUE_COUNT as usize;
+    ///The priority that is used if no other is set
+    pub const DEFAULT_PRIORITY: u8 = 100;
+    ///The highest priority
+    pub const MAX_PRIORITY: u8 = 200;
+
+    ///Creates a source with the CID, which is a UUID that identifies the source, and a name for the user. The priority is 100. Returns an error if the name is longer than 63 bytes
+    pub fn new(cid: [u8; 16], source_name: &str) -> Result<Self, SacnPacketError> {
+        if source_name.len() > 63 {
+            return Err(SacnPacketError::SourceNameTooLong(source_name.len()));
+        }
+        Ok(SacnSource {
+            cid,
+            source_name: source_name.to_string(),
+            priority: Self::DEFAULT_PRIORITY,
+            sequences: BTreeMap::new(),
+        })
+    }
+
+    ///Returns the CID of the source
+    pub fn cid(&self) -> [u8; 16] {
+        self.cid
+    }
+
+    ///Returns the name of the source
+    pub fn source_name(&self) -> &str {
+        &self.source_name
+    }
+
+    ///Returns the priority of the packets
+    pub fn priority(&self) -> u8 {
+        self.priority
+    }
+
+    ///Sets the priority of the packets. Receivers use the data of the sources with the highest priority. Returns an error if the priority is higher than 200
+    pub fn set_priority(&mut self, priority: u8) -> Result<(), SacnPacketError> {
+        if priority > Self::MAX_PRIORITY {
+            return Err(SacnPacketError::InvalidPriority(priority));
+        }
+        self.priority = priority;
+        Ok(())
+    }
+
+    ///Builds a data packet with the levels of the buffer for its universe. Each packet of the universe advances its sequence number, which starts at 0 and wraps after 255
+    pub fn data_packet(&mut self, buffer: &DMXUniverseBuffer) -> [u8; Self::DATA_PACKET_LENGTH] {
+        let universe = buffer.universe();
+        let sequence = self.sequences.entry(universe).or_insert(0);
+        let mut packet = [0; Self::DATA_PACKET_LENGTH];
+        //Root layer
+        packet[0..2].copy_from_slice(&0x0010u16.to_be_bytes());
+        packet[4..16].copy_from_slice(&ACN_PACKET_IDENTIFIER);
+        packet[16..18].copy_from_slice(&flags_and_length(Self::DATA_PACKET_LENGTH - 16));
+        packet[18..22].copy_from_slice(&VECTOR_ROOT_E131_DATA.to_be_bytes());
+        packet[22..38].copy_from_slice(&self.cid);
+        //Framing layer
+        packet[38..40]
+            .copy_from_slice(&flags_and_length(Self::DATA_PACKET_LENGTH - FRAMING_OFFSET));
+        packet[40..44].copy_from_slice(&VECTOR_E131_DATA_PACKET.to_be_bytes());
+        let name = self.source_name.as_bytes();
+        packet[44..44 + name.len()].copy_from_slice(name);
+        packet[108] = self.priority;
+        packet[111] = *sequence;
+        packet[113..115].copy_from_slice(&universe.to_be_bytes());
+        //DMP layer
+        packet[115..117].copy_from_slice(&flags_and_length(Self::DATA_PACKET_LENGTH - DMP_OFFSET));
+        packet[117] = VECTOR_DMP_SET_PROPERTY;
+        packet[118] = DMP_ADDRESS_TYPE;
+        packet[119..121].copy_from_slice(&DMP_FIRST_PROPERTY_ADDRESS.to_be_bytes());
+        packet[121..123].copy_from_slice(&DMP_ADDRESS_INCREMENT.to_be_bytes());
+        packet[123..125].copy_from_slice(&PROPERTY_VALUE_COUNT.to_be_bytes());
+        packet[PROPERTY_VALUES_OFFSET..].copy_from_slice(&buffer.to_dmp_payload());
+        *sequence = sequence.wrapping_add(1);
+        packet
+    }
+}
+
+///Parses a received sACN data packet. Returns the universe, the priority and the levels of the slots in the packet together with their address. Senders may send less than 512 slots. Returns an error if the packet is not a valid data packet with the null start code
+///
+///```rust
+/// use dmx_struct::{parse_sacn_data_packet, DMXUniverseBuffer, SacnSource};
+///
+/// let mut source = SacnSource::new([1; 16], "Console").unwrap();
+/// let mut buffer = DMXUniverseBuffer::new(2).unwrap();
+/// buffer.as_mut_slice()[9] = 255;
+/// let packet = source.data_packet(&buffer);
+/// let (universe, priority, levels) = parse_sacn_data_packet(&packet).unwrap();
+/// assert_eq!(2, universe.value());
+/// assert_eq!(100, priority);
+/// let (address, level) = levels.filter(|(_, level)| *level > 0).next().unwrap();
+/// assert_eq!("2.010", address.to_string());
+/// assert_eq!(255, level);
+/// ```
+pub fn parse_sacn_data_packet(
+    packet: &[u8],
+) -> Result<
+    (
+        SacnUniverse,
+        u8,
+        impl Iterator<Item = (DMXAddress, u8)> + '_,
+    ),
+    SacnPacketError,
+> {
+    if packet.len() <= PROPERTY_VALUES_OFFSET {
+        return Err(SacnPacketError::TooShort(packet.len()));
+    }
+    if packet[0..4] != [0x00, 0x10, 0x00, 0x00] || packet[4..16] != ACN_PACKET_IDENTIFIER {
+        return Err(SacnPacketError::InvalidIdentifier);
+    }
+    for offset in [16, FRAMING_OFFSET, DMP_OFFSET] {
+        let length = u16_at(packet, offset) & 0x0FFF;
+        if usize::from(length) != packet.len() - offset {
+            return Err(SacnPacketError::InvalidLength { offset, length });
+        }
+    }
+    expect_vector(u32_at(packet, 18), VECTOR_ROOT_E131_DATA, 18)?;
+    expect_vector(u32_at(packet, 40), VECTOR_E131_DATA_PACKET, 40)?;
+    expect_vector(packet[117].into(), VECTOR_DMP_SET_PROPERTY.into(), 117)?;
+    expect_vector(packet[118].into(), DMP_ADDRESS_TYPE.into(), 118)?;
+    for (offset, expected) in [
+        (119, DMP_FIRST_PROPERTY_ADDRESS),
+        (121, DMP_ADDRESS_INCREMENT),
+    ] {
+        let value = u16_at(packet, offset);
+        if value != expected {
+            return Err(SacnPacketError::InvalidPropertyAddress { offset, value });
+        }
+    }
+    let priority = packet[108];
+    if priority > SacnSource::MAX_PRIORITY {
+        return Err(SacnPacketError::InvalidPriority(priority));
+    }
+    let universe = u16_at(packet, 113);
+    let universe = SacnUniverse::from_dmx_universe(universe)
+        .map_err(|_| SacnPacketError::InvalidUniverse(universe))?;
+    let count = u16_at(packet, 123);
+    if count > PROPERTY_VALUE_COUNT || usize::from(count) != packet.len() - PROPERTY_VALUES_OFFSET {
+        return Err(SacnPacketError::InvalidLength {
+            offset: 123,
+            length: count,
+        });
+    }
+    let start_code = packet[PROPERTY_VALUES_OFFSET];
+    if start_code != 0 {
+        return Err(SacnPacketError::InvalidStartCode(start_code));
+    }
+    let levels = packet[PROPERTY_VALUES_OFFSET + 1..]
+        .iter()
+        .enumerate()
+        .filter_map(move |(index, level)| {
+            //The universe and the number of slots were validated, so the addresses are valid
+            DMXAddress::from_parts(universe.value().into(), index as u32 + 1)
+                .ok()
+                .map(|address| (address, *level))
+        });
+    Ok((universe, priority, levels))
+}
+
+///Returns an error if the vector at the offset is not the expected one
+fn expect_vector(vector: u32, expected: u32, offset: usize) -> Result<(), SacnPacketError> {
+    if vector != expected {
+        return Err(SacnPacketError::InvalidVector { offset, vector });
+    }
+    Ok(())
+}
+
+///Reads the big endian u16 at the offset
+fn u16_at(packet: &[u8], offset: usize) -> u16 {
+    u16::from_be_bytes([packet[offset], packet[offset + 1]])
+}
+
+///Reads the big endian u32 at the offset
+fn u32_at(packet: &[u8], offset: usize) -> u32 {
+    u32::from_be_bytes([
+        packet[offset],
+        packet[offset + 1],
+        packet[offset + 2],
+        packet[offset + 3],
+    ])
+}
+
+///Returns the flags and length field of a layer. The length counts the bytes from the field to the end of the packet
+fn flags_and_length(length: usize) -> [u8; 2] {
+    (0x7000 | length as u16).to_be_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use crate::{
+        parse_sacn_data_packet, DMXAddress, DMXUniverseBuffer, SacnPacketError, SacnSource,
+    };
+
+    fn packet() -> [u8; SacnSource::DATA_PACKET_LENGTH] {
+        let mut buffer = DMXUniverseBuffer::new(3).unwrap();
+        buffer.as_mut_slice()[0] = 10;
+        buffer.as_mut_slice()[511] = 20;
+        SacnSource::new([0; 16], "Test")
+            .unwrap()
+            .data_packet(&buffer)
+    }
+
+    fn parse_error(packet: &[u8]) -> SacnPacketError {
+        match parse_sacn_data_packet(packet) {
+            Ok(_) => panic!("packet is valid"),
+            Err(error) => error,
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let source = SacnSource::new([7; 16], "Console").unwrap();
+        assert_eq!([7; 16], source.cid());
+        assert_eq!("Console", source.source_name());
+        assert_eq!(100, source.priority());
+        assert!(SacnSource::new([0; 16], &"a".repeat(63)).is_ok());
+        assert_eq!(
+            Err(SacnPacketError::SourceNameTooLong(64)),
+            SacnSource::new([0; 16], &"a".repeat(64))
+        );
+    }
+
+    #[test]
+    fn test_priority() {
+        let mut source = SacnSource::new([0; 16], "").unwrap();
+        source.set_priority(200).unwrap();
+        assert_eq!(200, source.priority());
+        assert_eq!(
+            Err(SacnPacketError::InvalidPriority(201)),
+            source.set_priority(201)
+        );
+        assert_eq!(200, source.priority());
+    }
+
+    #[test]
+    fn test_data_packet() {
+        let mut source = SacnSource::new([0xAB; 16], "Test").unwrap();
+        source.set_priority(150).unwrap();
+        let mut buffer = DMXUniverseBuffer::new(0x1234).unwrap();
+        buffer
+            .set(&DMXAddress::try_from("4660.1").unwrap(), 1)
+            .unwrap();
+        buffer
+            .set(&DMXAddress::try_from("4660.512").unwrap(), 2)
+            .unwrap();
+        let packet = source.data_packet(&buffer);
+        assert_eq!(&[0x00, 0x10, 0x00, 0x00], &packet[0..4]);
+        assert_eq!(b"ASC-E1.17\0\0\0", &packet[4..16]);
+        assert_eq!(&[0x72, 0x6E], &packet[16..18]);
+        assert_eq!(&[0, 0, 0, 4], &packet[18..22]);
+        assert_eq!(&[0xAB; 16], &packet[22..38]);
+        assert_eq!(&[0x72, 0x58], &packet[38..40]);
+        assert_eq!(&[0, 0, 0, 2], &packet[40..44]);
+        assert_eq!(b"Test\0", &packet[44..49]);
+        assert_eq!(&[0; 59][..], &packet[49..108]);
+        assert_eq!(150, packet[108]);
+        assert_eq!(&[0, 0], &packet[109..111]);
+        assert_eq!(0, packet[111]);
+        assert_eq!(0, packet[112]);
+        assert_eq!(&[0x12, 0x34], &packet[113..115]);
+        assert_eq!(&[0x72, 0x0B], &packet[115..117]);
+        assert_eq!(
+            &[0x02, 0xA1, 0x00, 0x00, 0x00, 0x01, 0x02, 0x01],
+            &packet[117..125]
+        );
+        assert_eq!(0, packet[125]);
+        assert_eq!(1, packet[126]);
+        assert_eq!(2, packet[637]);
+    }
+
+    #[test]
+    fn test_sequence() {
+        let mut source = SacnSource::new([0; 16], "").unwrap();
+        let one = DMXUniverseBuffer::new(1).unwrap();
+        let two = DMXUniverseBuffer::new(2).unwrap();
+        for sequence in 0..=255 {
+            assert_eq!(sequence, source.data_packet(&one)[111]);
+        }
+        assert_eq!(0, source.data_packet(&one)[111]);
+        assert_eq!(0, source.data_packet(&two)[111]);
+        assert_eq!(1, source.data_packet(&two)[111]);
+    }
+
+    #[test]
+    fn test_parse() {
+        let packet = packet();
+        let (universe, priority, levels) = parse_sacn_data_packet(&packet).unwrap();
+        assert_eq!(3, universe.value());
+        assert_eq!(100, priority);
+        let levels: Vec<(DMXAddress, u8)> = levels.collect();
+        assert_eq!(512, levels.len());
+        assert_eq!((DMXAddress::try_from("3.1").unwrap(), 10), levels[0]);
+        assert_eq!((DMXAddress::try_from("3.512").unwrap(), 20), levels[511]);
+    }
+
+    #[test]
+    fn test_parse_short() {
+        //A packet with 2 slots
+        let mut packet = packet()[..128].to_vec();
+        packet[16..18].copy_from_slice(&(0x7000u16 | 112).to_be_bytes());
+        packet[38..40].copy_from_slice(&(0x7000u16 | 90).to_be_bytes());
+        packet[115..117].copy_from_slice(&(0x7000u16 | 13).to_be_bytes());
+        packet[123..125].copy_from_slice(&3u16.to_be_bytes());
+        let (_, _, levels) = parse_sacn_data_packet(&packet).unwrap();
+        assert_eq!(
+            vec![
+                (DMXAddress::try_from("3.1").unwrap(), 10),
+                (DMXAddress::try_from("3.2").unwrap(), 0)
+            ],
+            levels.collect::<Vec<(DMXAddress, u8)>>()
+        );
+        packet[123..125].copy_from_slice(&2u16.to_be_bytes());
+        assert_eq!(
+            SacnPacketError::InvalidLength {
+                offset: 123,
+                length: 2
+            },
+            parse_error(&packet)
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert_eq!(
+            SacnPacketError::TooShort(125),
+            parse_error(&packet()[..125])
+        );
+        assert_eq!(SacnPacketError::TooShort(0), parse_error(&[]));
+        let mut long = self::packet().to_vec();
+        long.push(0);
+        long[16..18].copy_from_slice(&(0x7000u16 | 623).to_be_bytes());
+        long[38..40].copy_from_slice(&(0x7000u16 | 601).to_be_bytes());
+        long[115..117].copy_from_slice(&(0x7000u16 | 524).to_be_bytes());
+        long[123..125].copy_from_slice(&514u16.to_be_bytes());
+        assert_eq!(
+            SacnPacketError::InvalidLength {
+                offset: 123,
+                length: 514
+            },
+            parse_error(&long)
+        );
+        let mut packet = packet();
+        packet[5] = b'X';
+        assert_eq!(SacnPacketError::InvalidIdentifier, parse_error(&packet));
+        let mut packet = self::packet();
+        packet[17] = 0;
+        assert_eq!(
+            SacnPacketError::InvalidLength {
+                offset: 16,
+                length: 0x200
+            },
+            parse_error(&packet)
+        );
+        assert_eq!(
+            SacnPacketError::InvalidLength {
+                offset: 16,
+                length: 622
+            },
+            parse_error(&self::packet()[..637])
+        );
+        let mut packet = self::packet();
+        packet[43] = 8;
+        assert_eq!(
+            SacnPacketError::InvalidVector {
+                offset: 40,
+                vector: 8
+            },
+            parse_error(&packet)
+        );
+        let mut packet = self::packet();
+        packet[118] = 0;
+        assert_eq!(
+            SacnPacketError::InvalidVector {
+                offset: 118,
+                vector: 0
+            },
+            parse_error(&packet)
+        );
+        let mut packet = self::packet();
+        packet[120] = 1;
+        assert_eq!(
+            SacnPacketError::InvalidPropertyAddress {
+                offset: 119,
+                value: 1
+            },
+            parse_error(&packet)
+        );
+        let mut packet = self::packet();
+        packet[122] = 2;
+        assert_eq!(
+            SacnPacketError::InvalidPropertyAddress {
+                offset: 121,
+                value: 2
+            },
+            parse_error(&packet)
+        );
+        let mut packet = self::packet();
+        packet[108] = 201;
+        assert_eq!(SacnPacketError::InvalidPriority(201), parse_error(&packet));
+        let mut packet = self::packet();
+        packet[113..115].copy_from_slice(&64_214u16.to_be_bytes());
+        assert_eq!(
+            SacnPacketError::InvalidUniverse(64_214),
+            parse_error(&packet)
+        );
+        let mut packet = self::packet();
+        packet[125] = 0xDD;
+        assert_eq!(
+            SacnPacketError::InvalidStartCode(0xDD),
+            parse_error(&packet)
+        );
+        assert_eq!(
+            "vector 0x8 at position 40 is not valid for a sACN data packet",
+            SacnPacketError::InvalidVector {
+                offset: 40,
+                vector: 8
+            }
+            .to_string()
+        );
+    }
+}
@@ -0,0 +1,204 @@
+//!Frames the levels of a universe for the Enttec DMX USB Pro widget. Only available with the feature `enttec`
+//!
+//!A frame starts with the delimiter 0x7E and the label, followed by the length of the data as little endian u16, the data and the end delimiter 0xE7. The label 6 (Output Only Send DMX Packet) carries the start code and the levels of the slots
+//!
+//!```rust
+//! use dmx_struct::DMXUniverseBuffer;
+//!
+//! let mut buffer = DMXUniverseBuffer::new(1).unwrap();
+//! buffer.as_mut_slice()[0] = 255;
+//! let frame = buffer.to_enttec_frame();
+//! assert_eq!(&[0x7E, 6, 0x01, 0x02, 0, 255], &frame[0..6]);
+//! assert_eq!(0xE7, frame[517]);
+//! assert_eq!(buffer, DMXUniverseBuffer::parse_enttec_frame(1, &frame).unwrap());
+//! ```
+use core::fmt::{Display, Formatter};
+
+use crate::{DMXAddress, DMXParseError, DMXUniverseBuffer, DmpPayloadError};
+
+///Delimiter at the start of a frame
+const START_DELIMITER: u8 = 0x7E;
+///Delimiter at the end of a frame
+const END_DELIMITER: u8 = 0xE7;
+///Label of the message that sends dmx data to the widget
+const LABEL_SEND_DMX: u8 = 6;
+///Number of bytes before the data
+const HEADER_LENGTH: usize = 4;
+///Number of bytes of the data, which is the start code and the slots of a universe
+const DATA_LENGTH: u16 = DMXAddress::ADDRESSES_PER_UNIVERSE + 1;
+
+///Error returned if an Enttec DMX USB Pro frame can not be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum EnttecFrameError {
+    ///The frame is too short for the header, the start code and the end delimiter. Holds the length in bytes
+    TooShort(usize),
+    ///The frame does not start with 0x7E or its data is not followed by 0xE7
+    InvalidDelimiter,
+    ///The label is not 6 (Output Only Send DMX Packet)
+    InvalidLabel(u8),
+    ///The length of the data is not between 1 and 513 or does not match the frame. Holds the length of the data
+    InvalidLength(u16),
+    ///The start code is not the null start code 0 that holds levels
+    InvalidStartCode(u8),
+    ///The universe of the buffer is not valid
+    InvalidAddress(DMXParseError),
+}
+
+impl Display for EnttecFrameError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            EnttecFrameError::TooShort(length) => {
+                write!(f, "frame of {} bytes is too short for a dmx packet", length)
+            }
+            EnttecFrameError::InvalidDelimiter => {
+                write!(f, "the frame must start with 0x7e and end with 0xe7")
+            }
+            EnttecFrameError::InvalidLabel(label) => write!(
+                f,
+                "label {} is not supported, only label 6 sends a dmx packet",
+                label
+            ),
+            EnttecFrameError::InvalidLength(length) => write!(
+                f,
+                "data length {} is not valid, it must be between 1 and {} and match the frame",
+                length, DATA_LENGTH
+            ),
+            EnttecFrameError::InvalidStartCode(start_code) => write!(
+                f,
+                "start code {} is not supported, only the null start code 0 holds levels",
+                start_code
+            ),
+            EnttecFrameError::InvalidAddress(error) => write!(f, "invalid universe: {}", error),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EnttecFrameError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            EnttecFrameError::InvalidAddress(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl DMXUniverseBuffer {
+    ///The number of bytes of a frame with the start code and 512 slots
+    pub const ENTTEC_FRAME_LENGTH: usize = HEADER_LENGTH + DATA_LENGTH as usize + 1;
+
+    ///Returns the frame that sends the levels of all 512 slots to an Enttec DMX USB Pro widget
+    pub fn to_enttec_frame(&self) -> [u8; Self::ENTTEC_FRAME_LENGTH] {
+        let mut frame = [0; Self::ENTTEC_FRAME_LENGTH];
+        frame[0] = START_DELIMITER;
+        frame[1] = LABEL_SEND_DMX;
+        frame[2..4].copy_from_slice(&DATA_LENGTH.to_le_bytes());
+        frame[HEADER_LENGTH + 1..HEADER_LENGTH + usize::from(DATA_LENGTH)]
+            .copy_from_slice(self.as_slice());
+        frame[HEADER_LENGTH + usize::from(DATA_LENGTH)] = END_DELIMITER;
+        frame
+    }
+
+    ///Creates a buffer for the universe from a frame with label 6. Frames may hold less than 512 slots, the missing slots are at level 0. Returns an error if the frame is not valid or the universe is out of range (1-63999)
+    pub fn parse_enttec_frame(universe: u16, frame: &[u8]) -> Result<Self, EnttecFrameError> {
+        if frame.len() < HEADER_LENGTH + 2 {
+            return Err(EnttecFrameError::TooShort(frame.len()));
+        }
+        if frame[0] != START_DELIMITER {
+            return Err(EnttecFrameError::InvalidDelimiter);
+        }
+        if frame[1] != LABEL_SEND_DMX {
+            return Err(EnttecFrameError::InvalidLabel(frame[1]));
+        }
+        let length = u16::from_le_bytes([frame[2], frame[3]]);
+        let end = HEADER_LENGTH + usize::from(length);
+        if length == 0 || length > DATA_LENGTH || frame.len() != end + 1 {
+            return Err(EnttecFrameError::InvalidLength(length));
+        }
+        if frame[end] != END_DELIMITER {
+            return Err(EnttecFrameError::InvalidDelimiter);
+        }
+        DMXUniverseBuffer::from_dmp_payload(universe, &frame[HEADER_LENGTH..end]).map_err(|error| {
+            match error {
+                DmpPayloadError::InvalidUniverse(universe) => EnttecFrameError::InvalidAddress(
+                    DMXParseError::InvalidUniverse(universe.into()),
+                ),
+                DmpPayloadError::InvalidStartCode(start_code) => {
+                    EnttecFrameError::InvalidStartCode(start_code)
+                }
+                //The length was checked against the frame above
+                DmpPayloadError::InvalidLength(_) => EnttecFrameError::InvalidLength(length),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DMXParseError, DMXUniverseBuffer, EnttecFrameError};
+
+    fn frame() -> [u8; DMXUniverseBuffer::ENTTEC_FRAME_LENGTH] {
+        let mut buffer = DMXUniverseBuffer::new(1).unwrap();
+        buffer.as_mut_slice()[0] = 1;
+        buffer.as_mut_slice()[511] = 2;
+        buffer.to_enttec_frame()
+    }
+
+    #[test]
+    fn test_to_enttec_frame() {
+        let frame = frame();
+        assert_eq!(518, frame.len());
+        assert_eq!(&[0x7E, 0x06, 0x01, 0x02, 0x00, 1], &frame[0..6]);
+        assert_eq!(&[2, 0xE7], &frame[516..518]);
+    }
+
+    #[test]
+    fn test_parse_enttec_frame() {
+        let buffer = DMXUniverseBuffer::parse_enttec_frame(3, &frame()).unwrap();
+        assert_eq!(3, buffer.universe());
+        assert_eq!(1, buffer.as_slice()[0]);
+        assert_eq!(2, buffer.as_slice()[511]);
+        let buffer =
+            DMXUniverseBuffer::parse_enttec_frame(1, &[0x7E, 6, 3, 0, 0, 10, 20, 0xE7]).unwrap();
+        assert_eq!(&[10, 20, 0], &buffer.as_slice()[0..3]);
+    }
+
+    #[test]
+    fn test_parse_enttec_frame_invalid() {
+        assert!(matches!(
+            DMXUniverseBuffer::parse_enttec_frame(1, &[0x7E, 6, 1, 0, 0]),
+            Err(EnttecFrameError::TooShort(5))
+        ));
+        assert!(matches!(
+            DMXUniverseBuffer::parse_enttec_frame(1, &[0x7F, 6, 1, 0, 0, 0xE7]),
+            Err(EnttecFrameError::InvalidDelimiter)
+        ));
+        assert!(matches!(
+            DMXUniverseBuffer::parse_enttec_frame(1, &[0x7E, 6, 1, 0, 0, 0xE6]),
+            Err(EnttecFrameError::InvalidDelimiter)
+        ));
+        assert!(matches!(
+            DMXUniverseBuffer::parse_enttec_frame(1, &[0x7E, 5, 1, 0, 0, 0xE7]),
+            Err(EnttecFrameError::InvalidLabel(5))
+        ));
+        assert!(matches!(
+            DMXUniverseBuffer::parse_enttec_frame(1, &[0x7E, 6, 2, 0, 0, 0xE7]),
+            Err(EnttecFrameError::InvalidLength(2))
+        ));
+        assert!(matches!(
+            DMXUniverseBuffer::parse_enttec_frame(1, &[0x7E, 6, 0, 0, 0, 0xE7]),
+            Err(EnttecFrameError::InvalidLength(0))
+        ));
+        assert!(matches!(
+            DMXUniverseBuffer::parse_enttec_frame(1, &[0x7E, 6, 1, 0, 0xCC, 0xE7]),
+            Err(EnttecFrameError::InvalidStartCode(0xCC))
+        ));
+        assert!(matches!(
+            DMXUniverseBuffer::parse_enttec_frame(0, &frame()),
+            Err(EnttecFrameError::InvalidAddress(
+                DMXParseError::InvalidUniverse(0)
+            ))
+        ));
+    }
+}
@@ -0,0 +1,353 @@
+//!Holds the error that is returned if a dmx address can not be parsed or is out of range
+use core::fmt::{Display, Formatter};
+use core::num::ParseIntError;
+
+use crate::{AddressFormat, DMXAddress, DMXValue, Protocol};
+
+///This Error is return if an invalid &str is tried to be deparsed as dmx-address instead of panicing. Each variant describes what was wrong with the input. Use `kind()` to branch on the category of the error, new variants may be added in any release
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DMXParseError {
+    ///The input was empty
+    Empty,
+    ///The input or a part of it is not a positive number
+    NotANumber {
+        ///Byte offset of the part of the input that could not be parsed
+        offset: usize,
+        ///The underlying error returned when parsing the number
+        source: ParseIntError,
+    },
+    ///The input contains more than one separator between universe and address (eg. `.`)
+    TooManyDots {
+        ///Byte offset of the second separator in the input
+        offset: usize,
+    },
+    ///The input separates universe and address with a separator that is not accepted by the `ParseOptions`, eg. 1.234 for the notation 1/234 of Hog
+    InvalidSeparator {
+        ///Byte offset of the separator in the input
+        offset: usize,
+        ///The separator that is not accepted
+        separator: char,
+    },
+    ///The input contains bytes that are not valid UTF-8. Only returned when parsing bytes
+    InvalidByte {
+        ///Byte offset of the first invalid byte in the input
+        offset: usize,
+    },
+    ///The input is in a format that is not accepted by the `ParseOptions`. Holds the format of the input
+    UnsupportedFormat(AddressFormat),
+    ///The universe is out of range (1-63999)
+    InvalidUniverse(u32),
+    ///The address is out of range (1-512)
+    InvalidAddress(u32),
+    ///The absolute dmx address is out of range (1-32767488)
+    InvalidAbsolute(u32),
+    ///The Art-Net net is out of range (0-127)
+    InvalidNet(u32),
+    ///The Art-Net sub-net is out of range (0-15)
+    InvalidSubNet(u32),
+    ///The universe inside an Art-Net sub-net is out of range (0-15)
+    InvalidArtNetUniverse(u32),
+    ///The Art-Net port address is out of range (0-32767)
+    InvalidPortAddress(u32),
+    ///The number of slots per universe of a `UniverseLayout` must not be 0
+    InvalidUniverseSize(u32),
+    ///The universe is out of range of the `Protocol` that was used to validate it. sACN returns `InvalidUniverse` instead
+    InvalidProtocolUniverse {
+        ///The universe that is out of range
+        universe: u32,
+        ///The protocol that limits the universe
+        protocol: Protocol,
+    },
+    ///The absolute dmx address is out of range of the `Protocol` that was used to validate it. sACN returns `InvalidAbsolute` instead
+    InvalidProtocolAbsolute {
+        ///The absolute dmx address that is out of range
+        absolute: u32,
+        ///The protocol that limits the absolute dmx address
+        protocol: Protocol,
+    },
+    ///The number of bytes of a `DMXValue` is not supported
+    InvalidByteCount(u32),
+    ///The value of a `DMXValue` does not fit into its number of bytes
+    InvalidValue {
+        ///The value that is out of range
+        value: u32,
+        ///The number of bytes the value is sent with
+        byte_count: u8,
+    },
+    ///The end of a `DMXAddressRange` is before its start
+    InvalidRange {
+        ///The absolute dmx address of the start
+        start: u32,
+        ///The absolute dmx address of the end
+        end: u32,
+    },
+}
+
+///Category of a `DMXParseError`, returned by `DMXParseError::kind()`. The category of a variant does not change between releases
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DMXParseErrorKind {
+    ///The input was empty
+    Empty,
+    ///The input is not a number or not a valid notation of a dmx address
+    Syntax,
+    ///The input is in a notation that is not accepted by the `ParseOptions`
+    UnsupportedFormat,
+    ///A number is out of range, eg. a universe, an address or a value
+    OutOfRange,
+    ///The parts are valid on their own but do not fit together, eg. the end of a range is before its start
+    Mismatch,
+}
+
+impl Display for DMXParseError {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        match self {
+            DMXParseError::Empty => write!(f, "the dmx address is empty"),
+            DMXParseError::NotANumber { offset, .. } => write!(
+                f,
+                "the dmx address must be a positive number or of format 'universe.address' (invalid number at position {})",
+                offset
+            ),
+            DMXParseError::TooManyDots { offset } => write!(
+                f,
+                "the dmx address must not contain more than one separator between universe and address (second separator at position {})",
+                offset
+            ),
+            DMXParseError::InvalidSeparator { offset, separator } => write!(
+                f,
+                "the separator '{}' at position {} is not accepted between universe and address",
+                separator, offset
+            ),
+            DMXParseError::InvalidByte { offset } => write!(
+                f,
+                "the dmx address contains an invalid byte at position {}",
+                offset
+            ),
+            DMXParseError::UnsupportedFormat(AddressFormat::Separated) => write!(
+                f,
+                "the dmx address must be an absolute dmx address, not of format 'universe.address'"
+            ),
+            DMXParseError::UnsupportedFormat(_) => write!(
+                f,
+                "the dmx address must be of format 'universe.address', not an absolute dmx address"
+            ),
+            DMXParseError::InvalidUniverse(universe) => write!(
+                f,
+                "universe {} is out of range, it must be between 1 and {}",
+                universe,
+                DMXAddress::MAX_UNIVERSE
+            ),
+            DMXParseError::InvalidAddress(address) => write!(
+                f,
+                "address {} is out of range, it must be between 1 and {}",
+                address,
+                DMXAddress::ADDRESSES_PER_UNIVERSE
+            ),
+            DMXParseError::InvalidAbsolute(absolute) => write!(
+                f,
+                "absolute dmx address {} is out of range, it must be between 1 and {}",
+                absolute,
+                DMXAddress::MAX_ABSOLUTE
+            ),
+            DMXParseError::InvalidNet(net) => write!(
+                f,
+                "Art-Net net {} is out of range, it must be between 0 and 127",
+                net
+            ),
+            DMXParseError::InvalidSubNet(sub_net) => write!(
+                f,
+                "Art-Net sub-net {} is out of range, it must be between 0 and 15",
+                sub_net
+            ),
+            DMXParseError::InvalidArtNetUniverse(universe) => write!(
+                f,
+                "Art-Net universe {} is out of range, it must be between 0 and 15",
+                universe
+            ),
+            DMXParseError::InvalidPortAddress(port_address) => write!(
+                f,
+                "Art-Net port address {} is out of range, it must be between 0 and 32767",
+                port_address
+            ),
+            DMXParseError::InvalidUniverseSize(size) => write!(
+                f,
+                "universe size {} is not valid, a universe must have at least 1 slot",
+                size
+            ),
+            DMXParseError::InvalidProtocolUniverse { universe, protocol } => write!(
+                f,
+                "universe {} is out of range, it must be between 1 and {} for {}",
+                universe,
+                protocol.max_universe(),
+                protocol
+            ),
+            DMXParseError::InvalidProtocolAbsolute { absolute, protocol } => write!(
+                f,
+                "absolute dmx address {} is out of range, it must be between 1 and {} for {}",
+                absolute,
+                protocol.max_absolute(),
+                protocol
+            ),
+            DMXParseError::InvalidByteCount(byte_count) => write!(
+                f,
+                "byte count {} is not valid, it must be between 1 and {}",
+                byte_count,
+                DMXValue::MAX_BYTE_COUNT
+            ),
+            DMXParseError::InvalidValue { value, byte_count } => write!(
+                f,
+                "dmx value {} is out of range, it must be between 0 and {} for {} byte(s)",
+                value,
+                DMXValue::max_for(*byte_count).unwrap_or(0),
+                byte_count
+            ),
+            DMXParseError::InvalidRange { start, end } => write!(
+                f,
+                "range from absolute dmx address {} to {} is not valid, the end must not be before the start",
+                start, end
+            ),
+        }
+    }
+}
+
+impl DMXParseError {
+    ///Returns the category of the error. Prefer matching on this over matching on the variants, as new variants may be added
+    pub fn kind(&self) -> DMXParseErrorKind {
+        match self {
+            DMXParseError::Empty => DMXParseErrorKind::Empty,
+            DMXParseError::NotANumber { .. }
+            | DMXParseError::TooManyDots { .. }
+            | DMXParseError::InvalidSeparator { .. }
+            | DMXParseError::InvalidByte { .. } => DMXParseErrorKind::Syntax,
+            DMXParseError::UnsupportedFormat(_) => DMXParseErrorKind::UnsupportedFormat,
+            DMXParseError::InvalidUniverse(_)
+            | DMXParseError::InvalidAddress(_)
+            | DMXParseError::InvalidAbsolute(_)
+            | DMXParseError::InvalidNet(_)
+            | DMXParseError::InvalidSubNet(_)
+            | DMXParseError::InvalidArtNetUniverse(_)
+            | DMXParseError::InvalidPortAddress(_)
+            | DMXParseError::InvalidUniverseSize(_)
+            | DMXParseError::InvalidProtocolUniverse { .. }
+            | DMXParseError::InvalidProtocolAbsolute { .. }
+            | DMXParseError::InvalidByteCount(_)
+            | DMXParseError::InvalidValue { .. } => DMXParseErrorKind::OutOfRange,
+            DMXParseError::InvalidRange { .. } => DMXParseErrorKind::Mismatch,
+        }
+    }
+
+    ///Returns the byte offset in the input where parsing failed. Returns None if the error is not related to a position in the input, eg. if a universe is out of range
+    pub fn offset(&self) -> Option<usize> {
+        match self {
+            DMXParseError::NotANumber { offset, .. }
+            | DMXParseError::TooManyDots { offset }
+            | DMXParseError::InvalidSeparator { offset, .. }
+            | DMXParseError::InvalidByte { offset } => Some(*offset),
+            _ => None,
+        }
+    }
+
+    ///Moves the offset of the error by start. Used if a part of the input was parsed on its own
+    pub(crate) fn shift(self, start: usize) -> Self {
+        match self {
+            DMXParseError::NotANumber { offset, source } => DMXParseError::NotANumber {
+                offset: offset + start,
+                source,
+            },
+            DMXParseError::TooManyDots { offset } => DMXParseError::TooManyDots {
+                offset: offset + start,
+            },
+            DMXParseError::InvalidSeparator { offset, separator } => {
+                DMXParseError::InvalidSeparator {
+                    offset: offset + start,
+                    separator,
+                }
+            }
+            DMXParseError::InvalidByte { offset } => DMXParseError::InvalidByte {
+                offset: offset + start,
+            },
+            error => error,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DMXParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DMXParseError::NotANumber { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::TryFrom;
+
+    use crate::{DMXAddress, DMXAddressRange, DMXParseError, DMXParseErrorKind};
+
+    #[test]
+    fn test_eq() {
+        assert_eq!(
+            Err(DMXParseError::InvalidAddress(513)),
+            DMXAddress::try_from("1.513")
+        );
+        assert_eq!(Err(DMXParseError::Empty), DMXAddress::try_from(""));
+        let error = DMXAddress::try_from("1.a").unwrap_err();
+        assert_eq!(error, error.clone());
+        assert_ne!(error, DMXAddress::try_from("a.1").unwrap_err());
+    }
+
+    #[test]
+    fn test_kind() {
+        assert_eq!(DMXParseErrorKind::Empty, DMXParseError::Empty.kind());
+        assert_eq!(
+            DMXParseErrorKind::Syntax,
+            DMXAddress::try_from("1.a").unwrap_err().kind()
+        );
+        assert_eq!(
+            DMXParseErrorKind::Syntax,
+            DMXAddress::try_from("1.2.3").unwrap_err().kind()
+        );
+        assert_eq!(
+            DMXParseErrorKind::OutOfRange,
+            DMXAddress::try_from("64000.1").unwrap_err().kind()
+        );
+        assert_eq!(
+            DMXParseErrorKind::OutOfRange,
+            DMXAddress::try_from("0").unwrap_err().kind()
+        );
+        assert_eq!(
+            DMXParseErrorKind::Mismatch,
+            DMXAddressRange::try_from("1.5-1.4").unwrap_err().kind()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_limits_in_message() {
+        assert_eq!(
+            format!(
+                "universe 0 is out of range, it must be between 1 and {}",
+                DMXAddress::MAX_UNIVERSE
+            ),
+            DMXParseError::InvalidUniverse(0).to_string()
+        );
+        assert_eq!(
+            format!(
+                "address 513 is out of range, it must be between 1 and {}",
+                DMXAddress::ADDRESSES_PER_UNIVERSE
+            ),
+            DMXParseError::InvalidAddress(513).to_string()
+        );
+        assert_eq!(
+            format!(
+                "absolute dmx address 0 is out of range, it must be between 1 and {}",
+                DMXAddress::MAX_ABSOLUTE
+            ),
+            DMXParseError::InvalidAbsolute(0).to_string()
+        );
+    }
+}
@@ -0,0 +1,320 @@
+//!Holds the footprint of a fixture, which are the consecutive slots it occupies starting at its dmx address
+use core::fmt::{Display, Formatter};
+
+use crate::DMXAddress;
+
+///Error returned if a `DMXFootprint` is not valid
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DMXFootprintError {
+    ///The number of slots must not be 0 and must fit into u16
+    InvalidWidth(u32),
+    ///The footprint does not fit into the universe of its start address and the `BoundaryPolicy` does not allow to continue in the next universe
+    CrossesUniverse {
+        ///The universe of the start address
+        universe: u16,
+        ///The start address in the universe
+        address: u16,
+        ///The number of slots of the footprint
+        width: u16,
+    },
+    ///The last slot is after the last valid dmx address. Holds the absolute dmx address of the last slot
+    InvalidEnd(u32),
+}
+
+impl Display for DMXFootprintError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DMXFootprintError::InvalidWidth(width) => write!(
+                f,
+                "footprint width {} is not valid, a footprint must have at least 1 slot",
+                width
+            ),
+            DMXFootprintError::CrossesUniverse {
+                universe,
+                address,
+                width,
+            } => write!(
+                f,
+                "footprint of {} slots at {}.{:03} does not fit into universe {}",
+                width, universe, address, universe
+            ),
+            DMXFootprintError::InvalidEnd(end) => write!(
+                f,
+                "footprint ends at absolute dmx address {}, it must end at {} or before",
+                end,
+                DMXAddress::MAX_ABSOLUTE
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DMXFootprintError {}
+
+///Defines what happens if a footprint does not fit into the rest of the universe of its start address, eg. a fixture with 30 slots at 1.500
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(::serde::Serialize, ::serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum BoundaryPolicy {
+    ///The footprint is rejected with `DMXFootprintError::CrossesUniverse`
+    Reject,
+    ///The footprint is moved to the first address of the next universe, eg. 1.500 is moved to 2.001
+    WrapToNextUniverse,
+    ///The footprint continues in the next universe, eg. 1.500 with 30 slots ends at 2.017
+    #[default]
+    Allow,
+}
+
+///Consecutive slots a fixture occupies, made of its first address and the number of slots. The slots roll over to the next universe
+///
+///```rust
+/// use std::convert::TryFrom;
+///
+/// use dmx_struct::{DMXAddress, DMXFootprint};
+///
+/// let spot = DMXFootprint::new(DMXAddress::try_from("1.1").unwrap(), 30).unwrap();
+/// let wash = DMXFootprint::new(DMXAddress::try_from("1.21").unwrap(), 16).unwrap();
+/// assert_eq!("1.030", spot.end().unwrap().to_string());
+/// assert!(spot.overlaps(&wash));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DMXFootprint {
+    ///The first address of the footprint
+    pub start: DMXAddress,
+    ///The number of slots of the footprint
+    pub width: u16,
+}
+
+impl DMXFootprint {
+    ///Creates a validated footprint that may continue in the next universe (`BoundaryPolicy::Allow`). Returns an error if the width is 0 or the footprint does not end before the last valid dmx address
+    pub fn new(start: DMXAddress, width: u16) -> Result<DMXFootprint, DMXFootprintError> {
+        Self::with_policy(start, width, BoundaryPolicy::Allow)
+    }
+
+    ///Creates a validated footprint and applies the policy if it does not fit into the universe of the start address. The returned footprint starts at the next universe with `BoundaryPolicy::WrapToNextUniverse`
+    ///
+    ///```rust
+    /// use std::convert::TryFrom;
+    ///
+    /// use dmx_struct::{BoundaryPolicy, DMXAddress, DMXFootprint};
+    ///
+    /// let start = DMXAddress::try_from("1.500").unwrap();
+    /// assert!(DMXFootprint::with_policy(start, 30, BoundaryPolicy::Reject).is_err());
+    /// let footprint = DMXFootprint::with_policy(start, 30, BoundaryPolicy::WrapToNextUniverse).unwrap();
+    /// assert_eq!("2.001", footprint.start.to_string());
+    /// ```
+    pub fn with_policy(
+        start: DMXAddress,
+        width: u16,
+        policy: BoundaryPolicy,
+    ) -> Result<DMXFootprint, DMXFootprintError> {
+        let crosses = DMXFootprintError::CrossesUniverse {
+            universe: start.universe,
+            address: start.address,
+            width,
+        };
+        let start = match policy {
+            BoundaryPolicy::Allow => start,
+            _ if width > DMXAddress::ADDRESSES_PER_UNIVERSE => return Err(crosses),
+            _ if DMXFootprint { start, width }.fits_in_universe() => start,
+            BoundaryPolicy::Reject => return Err(crosses),
+            BoundaryPolicy::WrapToNextUniverse => start
+                .universe
+                .checked_add(1)
+                .and_then(|universe| DMXAddress::from_parts(universe.into(), 1).ok())
+                .ok_or(crosses)?,
+        };
+        if width == 0 {
+            return Err(DMXFootprintError::InvalidWidth(width.into()));
+        }
+        let footprint = DMXFootprint { start, width };
+        match footprint.end() {
+            Some(_) => Ok(footprint),
+            None => Err(DMXFootprintError::InvalidEnd(footprint.end_absolute())),
+        }
+    }
+
+    ///Returns the last address of the footprint. Returns None if the width is 0 or the footprint does not end before the last valid dmx address
+    pub fn end(&self) -> Option<DMXAddress> {
+        if self.width == 0 {
+            return None;
+        }
+        DMXAddress::from_absolute(self.end_absolute()).ok()
+    }
+
+    ///Returns true if all slots of the footprint are in the universe of its start address
+    pub fn fits_in_universe(&self) -> bool {
+        u32::from(self.start.address) + u32::from(self.width)
+            <= u32::from(DMXAddress::ADDRESSES_PER_UNIVERSE) + 1
+    }
+
+    ///Returns true if the address is one of the slots of the footprint
+    pub fn contains(&self, address: &DMXAddress) -> bool {
+        address.absolute >= self.start.absolute && address.absolute <= self.end_absolute()
+    }
+
+    ///Returns true if the footprints share at least one slot
+    pub fn overlaps(&self, other: &DMXFootprint) -> bool {
+        self.width > 0
+            && other.width > 0
+            && self.start.absolute <= other.end_absolute()
+            && other.start.absolute <= self.end_absolute()
+    }
+
+    ///Returns all addresses of the footprint in ascending order
+    pub fn addresses(&self) -> impl Iterator<Item = DMXAddress> {
+        let start = self.start.absolute;
+        (start..start + u32::from(self.width))
+            .map_while(|absolute| DMXAddress::from_absolute(absolute).ok())
+    }
+
+    ///Returns the absolute address of the last slot. Might be out of range if the footprint is not valid
+    fn end_absolute(&self) -> u32 {
+        (self.start.absolute + u32::from(self.width)).saturating_sub(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_helpers::{address, footprint};
+    use crate::{BoundaryPolicy, DMXAddress, DMXFootprint, DMXFootprintError};
+
+    #[test]
+    fn test_new() {
+        assert_eq!(
+            DMXFootprint {
+                start: address("1.1"),
+                width: 30
+            },
+            footprint("1.1", 30)
+        );
+        assert!(DMXFootprint::new(address("63999.1"), 512).is_ok());
+    }
+
+    #[test]
+    fn test_new_invalid() {
+        assert!(matches!(
+            DMXFootprint::new(address("1.1"), 0),
+            Err(DMXFootprintError::InvalidWidth(0))
+        ));
+        assert!(matches!(
+            DMXFootprint::new(address("63999.500"), 14),
+            Err(DMXFootprintError::InvalidEnd(32_767_489))
+        ));
+    }
+
+    #[test]
+    fn test_end() {
+        assert_eq!(address("1.1"), footprint("1.1", 1).end().unwrap());
+        assert_eq!(address("1.30"), footprint("1.1", 30).end().unwrap());
+        assert_eq!(address("2.17"), footprint("1.500", 30).end().unwrap());
+        assert!(DMXFootprint {
+            start: address("1.1"),
+            width: 0
+        }
+        .end()
+        .is_none());
+    }
+
+    #[test]
+    fn test_contains() {
+        let footprint = footprint("1.500", 30);
+        assert!(!footprint.contains(&address("1.499")));
+        assert!(footprint.contains(&address("1.500")));
+        assert!(footprint.contains(&address("2.1")));
+        assert!(footprint.contains(&address("2.17")));
+        assert!(!footprint.contains(&address("2.18")));
+    }
+
+    #[test]
+    fn test_overlaps() {
+        let spot = footprint("1.1", 30);
+        assert!(spot.overlaps(&footprint("1.30", 10)));
+        assert!(footprint("1.30", 10).overlaps(&spot));
+        assert!(spot.overlaps(&footprint("1.5", 2)));
+        assert!(footprint("1.5", 2).overlaps(&spot));
+        assert!(!spot.overlaps(&footprint("1.31", 10)));
+        assert!(!footprint("1.31", 10).overlaps(&spot));
+        assert!(!spot.overlaps(&DMXFootprint {
+            start: address("1.5"),
+            width: 0
+        }));
+    }
+
+    #[test]
+    fn test_addresses() {
+        assert_eq!(
+            vec![address("1.511"), address("1.512"), address("2.1")],
+            footprint("1.511", 3)
+                .addresses()
+                .collect::<Vec<DMXAddress>>()
+        );
+        assert_eq!(512, footprint("1.1", 512).addresses().count());
+    }
+
+    #[test]
+    fn test_fits_in_universe() {
+        assert!(footprint("1.483", 30).fits_in_universe());
+        assert!(!footprint("1.484", 30).fits_in_universe());
+        assert!(footprint("1.1", 512).fits_in_universe());
+        assert!(!footprint("1.1", 513).fits_in_universe());
+    }
+
+    #[test]
+    fn test_policy_allow() {
+        let footprint =
+            DMXFootprint::with_policy(address("1.500"), 30, BoundaryPolicy::Allow).unwrap();
+        assert_eq!(address("1.500"), footprint.start);
+        assert_eq!(address("2.17"), footprint.end().unwrap());
+        assert_eq!(BoundaryPolicy::Allow, BoundaryPolicy::default());
+    }
+
+    #[test]
+    fn test_policy_reject() {
+        assert!(matches!(
+            DMXFootprint::with_policy(address("1.500"), 30, BoundaryPolicy::Reject),
+            Err(DMXFootprintError::CrossesUniverse {
+                universe: 1,
+                address: 500,
+                width: 30
+            })
+        ));
+        assert!(matches!(
+            DMXFootprint::with_policy(address("1.1"), 513, BoundaryPolicy::Reject),
+            Err(DMXFootprintError::CrossesUniverse { width: 513, .. })
+        ));
+        assert_eq!(
+            footprint("1.483", 30),
+            DMXFootprint::with_policy(address("1.483"), 30, BoundaryPolicy::Reject).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_policy_wrap() {
+        assert_eq!(
+            footprint("2.1", 30),
+            DMXFootprint::with_policy(address("1.500"), 30, BoundaryPolicy::WrapToNextUniverse)
+                .unwrap()
+        );
+        assert_eq!(
+            footprint("1.483", 30),
+            DMXFootprint::with_policy(address("1.483"), 30, BoundaryPolicy::WrapToNextUniverse)
+                .unwrap()
+        );
+        assert!(matches!(
+            DMXFootprint::with_policy(address("63999.500"), 30, BoundaryPolicy::WrapToNextUniverse),
+            Err(DMXFootprintError::CrossesUniverse {
+                universe: 63_999,
+                ..
+            })
+        ));
+        assert!(matches!(
+            DMXFootprint::with_policy(address("1.1"), 600, BoundaryPolicy::WrapToNextUniverse),
+            Err(DMXFootprintError::CrossesUniverse { width: 600, .. })
+        ));
+    }
+}
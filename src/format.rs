@@ -0,0 +1,197 @@
+//!Holds the options to format a dmx address in the notation of different consoles, eg. 1.001, 1.1, 001.001 or 1/1
+use core::fmt::{Alignment, Display, Formatter, Write};
+
+use crate::DMXAddress;
+
+///Options to format a dmx address with `DMXAddress::format_with`. The default options format the same way as `Display` does, eg. 1.001
+///
+///```rust
+/// use std::convert::TryFrom;
+///
+/// use dmx_struct::{DMXAddress, FormatOptions};
+///
+/// let address = DMXAddress::try_from("1.1").unwrap();
+/// let options = FormatOptions::new().separator('/').address_width(0);
+/// assert_eq!("1/1", address.format_with(&options).to_string());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FormatOptions {
+    separator: char,
+    address_width: usize,
+    universe_width: usize,
+}
+
+impl FormatOptions {
+    ///Returns the default options. Universe and address are separated by '.' and the address is padded with zeros to 3 digits
+    pub const fn new() -> Self {
+        FormatOptions {
+            separator: '.',
+            address_width: 3,
+            universe_width: 0,
+        }
+    }
+
+    ///Sets the character between universe and address
+    pub const fn separator(mut self, separator: char) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    ///Sets the number of digits the address is padded to with zeros. 0 disables padding
+    pub const fn address_width(mut self, width: usize) -> Self {
+        self.address_width = width;
+        self
+    }
+
+    ///Sets the number of digits the universe is padded to with zeros. 0 disables padding
+    pub const fn universe_width(mut self, width: usize) -> Self {
+        self.universe_width = width;
+        self
+    }
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+///A dmx address together with the options to format it. Returned by `DMXAddress::format_with` and used with {}
+#[derive(Debug, Clone, Copy)]
+pub struct FormattedDMXAddress {
+    address: DMXAddress,
+    options: FormatOptions,
+}
+
+impl FormattedDMXAddress {
+    pub(crate) fn new(address: DMXAddress, options: FormatOptions) -> Self {
+        FormattedDMXAddress { address, options }
+    }
+
+    ///Returns the number of characters of the formatted address
+    pub(crate) fn len(&self) -> usize {
+        digits(self.address.universe.into()).max(self.options.universe_width)
+            + 1
+            + digits(self.address.address.into()).max(self.options.address_width)
+    }
+}
+
+///The width, fill and alignment of the formatter are applied to the whole address, eg. {:>10} or {:*^12}
+impl Display for FormattedDMXAddress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        pad(f, self.len(), |f| {
+            write!(
+                f,
+                "{:0universe_width$}{}{:0address_width$}",
+                self.address.universe,
+                self.options.separator,
+                self.address.address,
+                universe_width = self.options.universe_width,
+                address_width = self.options.address_width
+            )
+        })
+    }
+}
+
+///Writes the output of write with the width, fill and alignment of the formatter like `Formatter::pad` does for text without allocating. Len is the number of characters that are written by write. The output is aligned left by default
+pub(crate) fn pad(
+    f: &mut Formatter<'_>,
+    len: usize,
+    write: impl FnOnce(&mut Formatter<'_>) -> core::fmt::Result,
+) -> core::fmt::Result {
+    let padding = f.width().map_or(0, |width| width.saturating_sub(len));
+    let (before, after) = match f.align() {
+        Some(Alignment::Right) => (padding, 0),
+        Some(Alignment::Center) => (padding / 2, padding - padding / 2),
+        _ => (0, padding),
+    };
+    let fill = f.fill();
+    for _ in 0..before {
+        f.write_char(fill)?;
+    }
+    write(f)?;
+    for _ in 0..after {
+        f.write_char(fill)?;
+    }
+    Ok(())
+}
+
+///Returns the number of decimal digits of the number
+pub(crate) fn digits(mut number: u32) -> usize {
+    let mut digits = 1;
+    while number >= 10 {
+        number /= 10;
+        digits += 1;
+    }
+    digits
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use crate::{DMXAddress, FormatOptions};
+
+    fn format(address: &str, options: FormatOptions) -> String {
+        DMXAddress::try_from(address)
+            .unwrap()
+            .format_with(&options)
+            .to_string()
+    }
+
+    #[test]
+    fn test_default() {
+        assert_eq!("1.001", format("1.1", FormatOptions::new()));
+        assert_eq!("12.345", format("12.345", FormatOptions::default()));
+    }
+
+    #[test]
+    fn test_address_width() {
+        assert_eq!("1.1", format("1.1", FormatOptions::new().address_width(0)));
+        assert_eq!(
+            "1.0001",
+            format("1.1", FormatOptions::new().address_width(4))
+        );
+        assert_eq!(
+            "1.512",
+            format("1.512", FormatOptions::new().address_width(2))
+        );
+    }
+
+    #[test]
+    fn test_universe_width() {
+        assert_eq!(
+            "001.001",
+            format("1.1", FormatOptions::new().universe_width(3))
+        );
+        assert_eq!(
+            "63999.001",
+            format("63999.1", FormatOptions::new().universe_width(3))
+        );
+    }
+
+    #[test]
+    fn test_alignment() {
+        let address = DMXAddress::try_from("1.1").unwrap();
+        let options = FormatOptions::new().separator('/').address_width(0);
+        assert_eq!("1/1  ", format!("{:5}", address.format_with(&options)));
+        assert_eq!("  1/1", format!("{:>5}", address.format_with(&options)));
+        assert_eq!(
+            "*001.001*",
+            format!(
+                "{:*^9}",
+                address.format_with(&FormatOptions::new().universe_width(3))
+            )
+        );
+        assert_eq!("1/1", format!("{:>2}", address.format_with(&options)));
+    }
+
+    #[test]
+    fn test_separator() {
+        assert_eq!(
+            "1/1",
+            format("1.1", FormatOptions::new().separator('/').address_width(0))
+        );
+        assert_eq!("2:034", format("2.34", FormatOptions::new().separator(':')));
+    }
+}
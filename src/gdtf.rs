@@ -0,0 +1,492 @@
+//!Holds dmx addresses of GDTF fixtures that are patched with several DMX breaks and the helpers to read and write dmx addresses in GDTF attributes. Each break of a fixture has its own start address, so an address of a fixture is made of the break and a `DMXAddress`. Attributes of type DMXValue like `DMXFrom` are read and written with `DMXValue`
+use core::convert::TryFrom;
+use core::fmt::{Display, Formatter};
+use core::num::ParseIntError;
+use core::str::FromStr;
+
+use crate::parse::parse;
+use crate::{DMXAddress, DMXParseError};
+
+///Error returned if a GDTF attribute can not be parsed or a value of a GDTF fixture is out of range
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum GdtfError {
+    ///The attribute was empty
+    Empty,
+    ///A break or offset is not a positive number
+    NotANumber {
+        ///Byte offset of the part of the attribute that could not be parsed
+        offset: usize,
+        ///The underlying error returned when parsing the number
+        source: ParseIntError,
+    },
+    ///The DMX break is out of range (1-255)
+    InvalidBreak(u32),
+    ///The relative address of an `Offset` must not be 0
+    InvalidOffset(u32),
+    ///An `Offset` holds more than 4 bytes. Holds the number of bytes
+    InvalidByteCount(u32),
+    ///The dmx address is not valid
+    InvalidAddress(DMXParseError),
+}
+
+impl Display for GdtfError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            GdtfError::Empty => write!(f, "the gdtf attribute is empty"),
+            GdtfError::NotANumber { offset, .. } => write!(
+                f,
+                "the gdtf attribute must hold positive numbers (invalid number at position {})",
+                offset
+            ),
+            GdtfError::InvalidBreak(dmx_break) => write!(
+                f,
+                "dmx break {} is out of range, it must be between 1 and 255",
+                dmx_break
+            ),
+            GdtfError::InvalidOffset(offset) => write!(
+                f,
+                "the relative address {} of a gdtf offset is not valid, it must be at least 1",
+                offset
+            ),
+            GdtfError::InvalidByteCount(byte_count) => write!(
+                f,
+                "gdtf offset of {} bytes is not valid, it must have at most {} bytes",
+                byte_count,
+                GdtfOffset::MAX_BYTE_COUNT
+            ),
+            GdtfError::InvalidAddress(error) => write!(f, "invalid dmx address: {}", error),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for GdtfError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            GdtfError::NotANumber { source, .. } => Some(source),
+            GdtfError::InvalidAddress(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+///Dmx address of one DMX break of a GDTF fixture. Breaks are counted from 1 as in the attribute `DMXBreak` of GDTF, fixtures with a single break only use break 1
+///
+///```rust
+/// use std::convert::TryFrom;
+///
+/// use dmx_struct::GdtfDmxAddress;
+///
+/// let address = GdtfDmxAddress::parse("2", "513").unwrap();
+/// assert_eq!(2, address.dmx_break);
+/// assert_eq!("2.001@2", address.to_string());
+/// assert_eq!(address, GdtfDmxAddress::try_from("2.1@2").unwrap());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GdtfDmxAddress {
+    ///The DMX break (1-255)
+    pub dmx_break: u8,
+    ///The dmx address of the break
+    pub address: DMXAddress,
+}
+
+impl GdtfDmxAddress {
+    ///Creates the address of the DMX break. Returns `InvalidBreak` if the break is 0
+    pub fn new(dmx_break: u8, address: DMXAddress) -> Result<Self, GdtfError> {
+        if dmx_break == 0 {
+            return Err(GdtfError::InvalidBreak(0));
+        }
+        Ok(GdtfDmxAddress { dmx_break, address })
+    }
+
+    ///Parses the values of the GDTF attributes `DMXBreak` and `DMXAddress`. The address is an absolute dmx address or of format 'universe.address' as defined by GDTF. Offsets of errors are positions in the value they occurred in
+    pub fn parse(dmx_break: &str, address: &str) -> Result<Self, GdtfError> {
+        let dmx_break = parse_break(dmx_break, 0)?;
+        Self::new(dmx_break, parse_address(address)?)
+    }
+}
+
+///Parses the break of format 'address@break', eg. 1.001@2 or 513@2. Without @break the address is in break 1
+impl TryFrom<&str> for GdtfDmxAddress {
+    type Error = GdtfError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.find('@') {
+            Some(separator) => {
+                let address = parse_address(&value[..separator])?;
+                let dmx_break = parse_break(&value[separator + 1..], separator + 1)?;
+                Self::new(dmx_break, address)
+            }
+            None => Self::new(1, parse_address(value)?),
+        }
+    }
+}
+
+///Addresses can be used in format with {}. It will return the format 'universe.address@break', eg. 2.001@2. The break is left out for break 1
+impl Display for GdtfDmxAddress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        if self.dmx_break == 1 {
+            write!(f, "{}", self.address)
+        } else {
+            write!(f, "{}@{}", self.address, self.dmx_break)
+        }
+    }
+}
+
+impl DMXAddress {
+    ///Parses the value of a GDTF attribute of type DMXAddress, eg. `DMXOffset` of the `Break` of a geometry reference. GDTF allows an absolute dmx address or the format 'universe.address', eg. 513 or 2.1
+    ///
+    ///```rust
+    /// use dmx_struct::DMXAddress;
+    ///
+    /// let address = DMXAddress::from_gdtf_attribute("2.1").unwrap();
+    /// assert_eq!("513", address.to_gdtf_attribute().to_string());
+    /// ```
+    pub fn from_gdtf_attribute(value: &str) -> Result<DMXAddress, DMXParseError> {
+        parse(value)
+    }
+
+    ///Returns the dmx address as it is written to GDTF attributes of type DMXAddress to be used with {}. GDTF files write the absolute dmx address, eg. 513 for 2.001
+    pub fn to_gdtf_attribute(&self) -> impl Display + Copy {
+        self.absolute
+    }
+}
+
+///The attribute `Offset` of a GDTF DMX channel. Holds the relative addresses of the bytes of the channel from the most to the least significant byte, counted from 1 at the start address of the DMX break. A channel without offsets is virtual and written as 'None'
+///
+///```rust
+/// use std::convert::TryFrom;
+///
+/// use dmx_struct::{DMXAddress, GdtfOffset};
+///
+/// let offset = GdtfOffset::try_from("1,2").unwrap();
+/// let start = DMXAddress::try_from("1.512").unwrap();
+/// assert_eq!("2.001", offset.address(&start, 1).unwrap().to_string());
+/// assert_eq!("1,2", offset.to_string());
+/// assert!(GdtfOffset::try_from("None").unwrap().is_empty());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GdtfOffset {
+    offsets: [u32; 4],
+    len: u8,
+}
+
+impl GdtfOffset {
+    ///The offset of a virtual channel that is not sent over dmx
+    pub const NONE: GdtfOffset = GdtfOffset {
+        offsets: [0; 4],
+        len: 0,
+    };
+
+    ///The highest number of bytes of a channel
+    pub const MAX_BYTE_COUNT: u8 = 4;
+
+    ///Creates the offset from the relative addresses of the bytes (1-4 bytes). Returns `InvalidByteCount` for more than 4 bytes and `InvalidOffset` for the relative address 0
+    pub fn new(offsets: &[u32]) -> Result<Self, GdtfError> {
+        if offsets.len() > usize::from(Self::MAX_BYTE_COUNT) {
+            return Err(GdtfError::InvalidByteCount(offsets.len() as u32));
+        }
+        let mut offset = Self::NONE;
+        for &relative in offsets {
+            offset.push(relative)?;
+        }
+        Ok(offset)
+    }
+
+    ///Creates the offset of the bytes that are sent at the addresses if the DMX break starts at start. Returns `InvalidAddress` with `InvalidRange` if an address is before start
+    pub fn from_addresses(start: &DMXAddress, addresses: &[DMXAddress]) -> Result<Self, GdtfError> {
+        if addresses.len() > usize::from(Self::MAX_BYTE_COUNT) {
+            return Err(GdtfError::InvalidByteCount(addresses.len() as u32));
+        }
+        let mut offset = Self::NONE;
+        for address in addresses {
+            if address.absolute < start.absolute {
+                return Err(GdtfError::InvalidAddress(DMXParseError::InvalidRange {
+                    start: start.absolute,
+                    end: address.absolute,
+                }));
+            }
+            offset.push(address.absolute - start.absolute + 1)?;
+        }
+        Ok(offset)
+    }
+
+    ///Returns the relative addresses of the bytes from the most to the least significant byte
+    pub fn offsets(&self) -> &[u32] {
+        &self.offsets[..usize::from(self.len)]
+    }
+
+    ///Returns true if the channel is virtual and has no offsets
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    ///Returns the address of the byte with the index (0 is the most significant byte) if the DMX break starts at start. Returns None if there is no byte with the index or the address is out of range
+    pub fn address(&self, start: &DMXAddress, index: usize) -> Option<DMXAddress> {
+        let relative = *self.offsets().get(index)?;
+        DMXAddress::from_absolute(start.absolute.checked_add(relative - 1)?).ok()
+    }
+
+    fn push(&mut self, relative: u32) -> Result<(), GdtfError> {
+        if relative == 0 {
+            return Err(GdtfError::InvalidOffset(relative));
+        }
+        self.offsets[usize::from(self.len)] = relative;
+        self.len += 1;
+        Ok(())
+    }
+}
+
+///Parses the attribute `Offset` of a GDTF DMX channel. The relative addresses are separated by ',', eg. '1,2'. 'None' is a virtual channel
+impl TryFrom<&str> for GdtfOffset {
+    type Error = GdtfError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        if value.is_empty() {
+            return Err(GdtfError::Empty);
+        }
+        if value == "None" {
+            return Ok(Self::NONE);
+        }
+        let mut offset = Self::NONE;
+        let mut start = 0;
+        for part in value.split(',') {
+            if offset.len == Self::MAX_BYTE_COUNT {
+                return Err(GdtfError::InvalidByteCount(value.split(',').count() as u32));
+            }
+            offset.push(parse_number(part, start)?)?;
+            start += part.len() + 1;
+        }
+        Ok(offset)
+    }
+}
+
+///Offsets can be used in format with {}. It will return the format of GDTF, eg. 1,2 or None
+impl Display for GdtfOffset {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        if self.is_empty() {
+            return write!(f, "None");
+        }
+        for (i, relative) in self.offsets().iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}", relative)?;
+        }
+        Ok(())
+    }
+}
+
+///Parses a DMX break (1-255). The offset is the position of the break in the attribute and is returned in the error
+fn parse_break(value: &str, offset: usize) -> Result<u8, GdtfError> {
+    if value.is_empty() {
+        return Err(GdtfError::Empty);
+    }
+    let dmx_break = parse_number(value, offset)?;
+    match u8::try_from(dmx_break) {
+        Ok(dmx_break) if dmx_break > 0 => Ok(dmx_break),
+        _ => Err(GdtfError::InvalidBreak(dmx_break)),
+    }
+}
+
+///Parses a number of the attribute. The offset is the position of the number in the attribute and is returned in the error
+pub(crate) fn parse_number(value: &str, offset: usize) -> Result<u32, GdtfError> {
+    u32::from_str(value).map_err(|source| GdtfError::NotANumber { offset, source })
+}
+
+///Parses a dmx address of format 'universe.address' or an absolute dmx address
+pub(crate) fn parse_address(value: &str) -> Result<DMXAddress, GdtfError> {
+    parse(value).map_err(GdtfError::InvalidAddress)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use crate::test_helpers::address;
+    use crate::{DMXAddress, DMXParseError, GdtfDmxAddress, GdtfError, GdtfOffset};
+
+    #[test]
+    fn test_new() {
+        let gdtf = GdtfDmxAddress::new(2, address("1.1")).unwrap();
+        assert_eq!(2, gdtf.dmx_break);
+        assert_eq!(address("1.1"), gdtf.address);
+        assert!(matches!(
+            GdtfDmxAddress::new(0, address("1.1")),
+            Err(GdtfError::InvalidBreak(0))
+        ));
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            GdtfDmxAddress::new(1, address("2.1")).unwrap(),
+            GdtfDmxAddress::parse("1", "513").unwrap()
+        );
+        assert_eq!(
+            GdtfDmxAddress::new(255, address("2.1")).unwrap(),
+            GdtfDmxAddress::parse("255", "2.1").unwrap()
+        );
+        assert!(matches!(
+            GdtfDmxAddress::parse("0", "1"),
+            Err(GdtfError::InvalidBreak(0))
+        ));
+        assert!(matches!(
+            GdtfDmxAddress::parse("256", "1"),
+            Err(GdtfError::InvalidBreak(256))
+        ));
+        assert!(matches!(
+            GdtfDmxAddress::parse("", "1"),
+            Err(GdtfError::Empty)
+        ));
+        assert!(matches!(
+            GdtfDmxAddress::parse("1", "1.513"),
+            Err(GdtfError::InvalidAddress(DMXParseError::InvalidAddress(
+                513
+            )))
+        ));
+    }
+
+    #[test]
+    fn test_try_from() {
+        assert_eq!(
+            GdtfDmxAddress::new(1, address("1.234")).unwrap(),
+            GdtfDmxAddress::try_from("1.234").unwrap()
+        );
+        assert_eq!(
+            GdtfDmxAddress::new(3, address("2.1")).unwrap(),
+            GdtfDmxAddress::try_from("513@3").unwrap()
+        );
+        assert!(matches!(
+            GdtfDmxAddress::try_from("1.1@x"),
+            Err(GdtfError::NotANumber { offset: 4, .. })
+        ));
+        assert!(matches!(
+            GdtfDmxAddress::try_from("1.1@"),
+            Err(GdtfError::Empty)
+        ));
+        assert!(matches!(
+            GdtfDmxAddress::try_from("1.1@0"),
+            Err(GdtfError::InvalidBreak(0))
+        ));
+        assert!(matches!(
+            GdtfDmxAddress::try_from("@2"),
+            Err(GdtfError::InvalidAddress(DMXParseError::Empty))
+        ));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            "1.001",
+            GdtfDmxAddress::new(1, address("1.1")).unwrap().to_string()
+        );
+        assert_eq!(
+            "1.001@2",
+            GdtfDmxAddress::new(2, address("1.1")).unwrap().to_string()
+        );
+        for value in &["1.001", "2.512@2", "63999.512@255"] {
+            assert_eq!(
+                *value,
+                GdtfDmxAddress::try_from(*value).unwrap().to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn test_gdtf_attribute() {
+        assert_eq!(
+            address("2.1"),
+            DMXAddress::from_gdtf_attribute("513").unwrap()
+        );
+        assert_eq!(
+            address("2.1"),
+            DMXAddress::from_gdtf_attribute("2.1").unwrap()
+        );
+        assert!(matches!(
+            DMXAddress::from_gdtf_attribute(""),
+            Err(DMXParseError::Empty)
+        ));
+        assert_eq!("1", address("1.1").to_gdtf_attribute().to_string());
+        assert_eq!("513", address("2.001").to_gdtf_attribute().to_string());
+    }
+
+    #[test]
+    fn test_offset_new() {
+        assert_eq!(&[1, 2], GdtfOffset::new(&[1, 2]).unwrap().offsets());
+        assert!(GdtfOffset::new(&[]).unwrap().is_empty());
+        assert!(matches!(
+            GdtfOffset::new(&[1, 0]),
+            Err(GdtfError::InvalidOffset(0))
+        ));
+        assert!(matches!(
+            GdtfOffset::new(&[1, 2, 3, 4, 5]),
+            Err(GdtfError::InvalidByteCount(5))
+        ));
+    }
+
+    #[test]
+    fn test_offset_from_addresses() {
+        let start = address("1.100");
+        assert_eq!(
+            &[2, 1],
+            GdtfOffset::from_addresses(&start, &[address("1.101"), address("1.100")])
+                .unwrap()
+                .offsets()
+        );
+        assert!(matches!(
+            GdtfOffset::from_addresses(&start, &[address("1.99")]),
+            Err(GdtfError::InvalidAddress(DMXParseError::InvalidRange {
+                start: 100,
+                end: 99
+            }))
+        ));
+    }
+
+    #[test]
+    fn test_offset_address() {
+        let offset = GdtfOffset::new(&[3, 1]).unwrap();
+        let start = address("1.511");
+        assert_eq!(Some(address("2.1")), offset.address(&start, 0));
+        assert_eq!(Some(address("1.511")), offset.address(&start, 1));
+        assert_eq!(None, offset.address(&start, 2));
+        assert_eq!(None, offset.address(&address("63999.512"), 0));
+    }
+
+    #[test]
+    fn test_offset_try_from() {
+        assert_eq!(
+            GdtfOffset::new(&[1, 2]).unwrap(),
+            GdtfOffset::try_from("1,2").unwrap()
+        );
+        assert_eq!(GdtfOffset::NONE, GdtfOffset::try_from("None").unwrap());
+        assert!(matches!(GdtfOffset::try_from(""), Err(GdtfError::Empty)));
+        assert!(matches!(
+            GdtfOffset::try_from("1,x"),
+            Err(GdtfError::NotANumber { offset: 2, .. })
+        ));
+        assert!(matches!(
+            GdtfOffset::try_from("1,,2"),
+            Err(GdtfError::NotANumber { offset: 2, .. })
+        ));
+        assert!(matches!(
+            GdtfOffset::try_from("0"),
+            Err(GdtfError::InvalidOffset(0))
+        ));
+        assert!(matches!(
+            GdtfOffset::try_from("1,2,3,4,5"),
+            Err(GdtfError::InvalidByteCount(5))
+        ));
+    }
+
+    #[test]
+    fn test_offset_display() {
+        assert_eq!("None", GdtfOffset::NONE.to_string());
+        assert_eq!("1", GdtfOffset::new(&[1]).unwrap().to_string());
+        assert_eq!(
+            "4,3,2,1",
+            GdtfOffset::try_from("4,3,2,1").unwrap().to_string()
+        );
+    }
+}
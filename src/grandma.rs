@@ -0,0 +1,125 @@
+//!Implements the export of `DMXPatch` to the XML layer format that is imported by grandMA2 and grandMA3 with `Import "file" At Layer`. Only available with the feature `grandma`
+//!
+//!Each fixture of the patch is written as `Fixture` with its id as name and fixture_id. The start is written as absolute dmx address in the element `Address`, which is the notation MA uses in its XML files (eg. 2.001 is 513). Fixture types are not known to the patch and have to be assigned in the console
+//!
+//!```rust
+//! use std::convert::TryFrom;
+//!
+//! use dmx_struct::{DMXAddress, DMXFootprint, DMXPatch};
+//!
+//! let mut patch = DMXPatch::new();
+//! patch.insert(101, DMXFootprint::new(DMXAddress::try_from("2.1").unwrap(), 30).unwrap()).unwrap();
+//! let mut out = Vec::new();
+//! patch.write_grandma_xml(&mut out).unwrap();
+//! assert!(String::from_utf8(out).unwrap().contains("<Address>513</Address>"));
+//! ```
+use std::fmt::Display;
+use std::io::{Result, Write};
+
+use crate::DMXPatch;
+
+impl<K: Ord + Clone + Display> DMXPatch<K> {
+    ///Writes the patch as grandMA XML layer named Patch in ascending order of the addresses
+    pub fn write_grandma_xml<W: Write>(&self, mut writer: W) -> Result<()> {
+        writeln!(writer, r#"<?xml version="1.0" encoding="utf-8"?>"#)?;
+        writeln!(
+            writer,
+            r#"<MA xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xmlns="http://schemas.malighting.de/grandma2/xml/MA" xsi:schemaLocation="http://schemas.malighting.de/grandma2/xml/MA http://schemas.malighting.de/grandma2/xml/3.9.60/MA.xsd" major_vers="3" minor_vers="9" stream_vers="60">"#
+        )?;
+        writeln!(writer, r#"  <Layer index="0" name="Patch">"#)?;
+        for (index, (id, footprint)) in self.iter().enumerate() {
+            let id = escape(&id.to_string());
+            writeln!(
+                writer,
+                r#"    <Fixture index="{}" name="{}" fixture_id="{}">"#,
+                index, id, id
+            )?;
+            writeln!(writer, r#"      <SubFixture index="0">"#)?;
+            writeln!(writer, "        <Patch>")?;
+            writeln!(
+                writer,
+                "          <Address>{}</Address>",
+                footprint.start.absolute
+            )?;
+            writeln!(writer, "        </Patch>")?;
+            writeln!(writer, "      </SubFixture>")?;
+            writeln!(writer, "    </Fixture>")?;
+        }
+        writeln!(writer, "  </Layer>")?;
+        writeln!(writer, "</MA>")?;
+        writer.flush()
+    }
+}
+
+///Replaces the characters that are not allowed in XML attributes with their entities
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_helpers::footprint;
+    use crate::DMXPatch;
+
+    use super::escape;
+
+    fn write<K: Ord + Clone + std::fmt::Display>(patch: &DMXPatch<K>) -> String {
+        let mut out = Vec::new();
+        patch.write_grandma_xml(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_write() {
+        let mut patch = DMXPatch::new();
+        patch.insert(102, footprint("2.1", 16)).unwrap();
+        patch.insert(101, footprint("1.1", 30)).unwrap();
+        let xml = write(&patch);
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<MA "));
+        assert!(xml.contains(
+            r#"    <Fixture index="0" name="101" fixture_id="101">
+      <SubFixture index="0">
+        <Patch>
+          <Address>1</Address>
+        </Patch>
+      </SubFixture>
+    </Fixture>
+    <Fixture index="1" name="102" fixture_id="102">
+      <SubFixture index="0">
+        <Patch>
+          <Address>513</Address>"#
+        ));
+        assert!(xml.ends_with("  </Layer>\n</MA>\n"));
+    }
+
+    #[test]
+    fn test_write_empty() {
+        let xml = write(&DMXPatch::<u32>::new());
+        assert!(xml.contains("<Layer index=\"0\" name=\"Patch\">\n  </Layer>"));
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!("spot", escape("spot"));
+        assert_eq!(
+            "&lt;a&gt; &amp; &quot;b&quot; &apos;c&apos;",
+            escape(r#"<a> & "b" 'c'"#)
+        );
+        let mut patch = DMXPatch::new();
+        patch
+            .insert("a&b".to_string(), footprint("1.1", 1))
+            .unwrap();
+        assert!(write(&patch).contains(r#"name="a&amp;b" fixture_id="a&amp;b""#));
+    }
+}
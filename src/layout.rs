@@ -0,0 +1,186 @@
+//!Holds the layout that splits absolute dmx addresses into universe and address. Dmx universes have 512 slots, but some legacy and proprietary systems count 256 or 1024 slots per universe
+use crate::{DMXAddress, DMXParseError};
+
+///Number of slots per universe used to convert between absolute dmx addresses and (universe, address). Universe and address are counted from 1
+///
+///```rust
+/// use dmx_struct::{DMXAddress, UniverseLayout};
+///
+/// let layout = UniverseLayout::new(256).unwrap();
+/// let address = DMXAddress::from_layout(3, 1, &layout).unwrap();
+/// assert_eq!("2.001", address.to_string());
+/// assert_eq!((3, 1), address.to_layout(&layout));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UniverseLayout {
+    universe_size: u16,
+}
+
+impl UniverseLayout {
+    ///The layout of dmx with 512 slots per universe that is used by `DMXAddress`
+    pub const DMX: UniverseLayout = UniverseLayout {
+        universe_size: DMXAddress::ADDRESSES_PER_UNIVERSE,
+    };
+
+    ///Creates a layout with the given number of slots per universe. Returns an error if the size is 0
+    pub const fn new(universe_size: u16) -> Result<Self, DMXParseError> {
+        if universe_size == 0 {
+            return Err(DMXParseError::InvalidUniverseSize(0));
+        }
+        Ok(UniverseLayout { universe_size })
+    }
+
+    ///Returns the number of slots per universe
+    pub const fn universe_size(&self) -> u16 {
+        self.universe_size
+    }
+
+    ///Splits an absolute dmx address into universe and address of this layout. Returns an error if the absolute address is 0
+    pub fn split(&self, absolute: u32) -> Result<(u32, u32), DMXParseError> {
+        if absolute == 0 {
+            return Err(DMXParseError::InvalidAbsolute(absolute));
+        }
+        let size = u32::from(self.universe_size);
+        //Counting from 0 first so the last slot of a universe does not roll over into the next one
+        Ok(((absolute - 1) / size + 1, (absolute - 1) % size + 1))
+    }
+
+    ///Joins universe and address of this layout into an absolute dmx address. Returns an error if universe or address are 0, the address is larger than the universe or the result does not fit into u32
+    pub fn join(&self, universe: u32, address: u32) -> Result<u32, DMXParseError> {
+        if universe == 0 {
+            return Err(DMXParseError::InvalidUniverse(universe));
+        }
+        if address == 0 || address > u32::from(self.universe_size) {
+            return Err(DMXParseError::InvalidAddress(address));
+        }
+        (universe - 1)
+            .checked_mul(self.universe_size.into())
+            .and_then(|start| start.checked_add(address))
+            .ok_or(DMXParseError::InvalidUniverse(universe))
+    }
+}
+
+impl Default for UniverseLayout {
+    fn default() -> Self {
+        Self::DMX
+    }
+}
+
+impl DMXAddress {
+    ///Creates a DMXAddress from universe and address of another layout, eg. universe 3 address 1 with 256 slots per universe is the absolute dmx address 513 (2.001)
+    pub fn from_layout(
+        universe: u32,
+        address: u32,
+        layout: &UniverseLayout,
+    ) -> Result<DMXAddress, DMXParseError> {
+        Self::from_absolute(layout.join(universe, address)?)
+    }
+
+    ///Returns universe and address of the absolute dmx address in another layout, eg. 2.001 is universe 3 address 1 with 256 slots per universe
+    pub fn to_layout(&self, layout: &UniverseLayout) -> (u32, u32) {
+        //The absolute address of a DMXAddress is never 0
+        layout.split(self.absolute).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use crate::{DMXAddress, DMXParseError, UniverseLayout};
+
+    #[test]
+    fn test_new() {
+        assert_eq!(512, UniverseLayout::default().universe_size());
+        assert_eq!(256, UniverseLayout::new(256).unwrap().universe_size());
+        assert!(matches!(
+            UniverseLayout::new(0),
+            Err(DMXParseError::InvalidUniverseSize(0))
+        ));
+    }
+
+    #[test]
+    fn test_split() {
+        let layout = UniverseLayout::new(256).unwrap();
+        assert_eq!((1, 1), layout.split(1).unwrap());
+        assert_eq!((1, 256), layout.split(256).unwrap());
+        assert_eq!((2, 1), layout.split(257).unwrap());
+        assert_eq!((1, 512), UniverseLayout::DMX.split(512).unwrap());
+        assert_eq!((3, 1), UniverseLayout::new(1).unwrap().split(3).unwrap());
+        assert!(matches!(
+            layout.split(0),
+            Err(DMXParseError::InvalidAbsolute(0))
+        ));
+    }
+
+    #[test]
+    fn test_join() {
+        let layout = UniverseLayout::new(1024).unwrap();
+        assert_eq!(1, layout.join(1, 1).unwrap());
+        assert_eq!(1024, layout.join(1, 1024).unwrap());
+        assert_eq!(1025, layout.join(2, 1).unwrap());
+        assert!(matches!(
+            layout.join(0, 1),
+            Err(DMXParseError::InvalidUniverse(0))
+        ));
+        assert!(matches!(
+            layout.join(1, 0),
+            Err(DMXParseError::InvalidAddress(0))
+        ));
+        assert!(matches!(
+            layout.join(1, 1025),
+            Err(DMXParseError::InvalidAddress(1025))
+        ));
+        assert!(matches!(
+            layout.join(u32::MAX, 1),
+            Err(DMXParseError::InvalidUniverse(u32::MAX))
+        ));
+    }
+
+    #[test]
+    fn test_from_layout() {
+        let layout = UniverseLayout::new(256).unwrap();
+        assert_eq!(
+            DMXAddress {
+                universe: 1,
+                address: 256,
+                absolute: 256
+            },
+            DMXAddress::from_layout(1, 256, &layout).unwrap()
+        );
+        assert_eq!(
+            DMXAddress {
+                universe: 2,
+                address: 1,
+                absolute: 513
+            },
+            DMXAddress::from_layout(3, 1, &layout).unwrap()
+        );
+        assert_eq!(
+            DMXAddress {
+                universe: 2,
+                address: 488,
+                absolute: 1000
+            },
+            DMXAddress::from_layout(1, 1000, &UniverseLayout::new(1024).unwrap()).unwrap()
+        );
+        assert!(matches!(
+            DMXAddress::from_layout(200_000, 1, &layout),
+            Err(DMXParseError::InvalidAbsolute(51_199_745))
+        ));
+    }
+
+    #[test]
+    fn test_to_layout() {
+        let address = DMXAddress::try_from("2.234").unwrap();
+        assert_eq!((2, 234), address.to_layout(&UniverseLayout::DMX));
+        assert_eq!(
+            (3, 234),
+            address.to_layout(&UniverseLayout::new(256).unwrap())
+        );
+        assert_eq!(
+            (1, 746),
+            address.to_layout(&UniverseLayout::new(1024).unwrap())
+        );
+    }
+}
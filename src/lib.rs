@@ -4,11 +4,25 @@
 //!
 //! ## Description
 //!
-//! This is a simple crate that contains a struct `DMXAddress`. The struct implements the trait `TryFrom` that understands notation with dot (eg. 1.234, 5.231) and absolute dmx addresses to parse.
+//! This is a simple crate that contains a struct `DMXAddress`. The struct implements the trait `TryFrom` that understands notation with dot (eg. 1.234, 5.231), the ETC-style notation with slash (eg. 1/234), the notation with colon used by OLA (eg. 1:234) and absolute dmx addresses to parse. `DMXAddress::parse_dialect` only accepts the notation of one lighting console (`Dialect::MA`, `Dialect::Hog` or `Dialect::Eos`) and `DMXAddress::format_dialect` writes it back in the same notation. The macro `dmx!` creates addresses that are validated at compile time (eg. `dmx!("1.234")` or `dmx!(1, 234)`)
 //!
-//! The module is designed to never panic but return `DMXParseError` instead
+//! Addresses in Art-Net notation 'net-subnet-universe.address' (eg. 0-1-15.234) can be parsed with `DMXAddress::parse_artnet` or the struct `ArtNetAddress`, which converts to and from `DMXAddress`. Port address 0-0-0 is universe 1
 //!
-//! The module holds both, the absolute address and the address separated by universe and address so no calculation needed for further oparations
+//! The struct `SacnUniverse` validates sACN universe numbers (1-63999 and the discovery universe 64214)
+//!
+//! The struct `OscPathTemplate` maps dmx addresses to OSC paths like /dmx/1/234 and parses them back
+//!
+//! The struct `RdmUid` holds the 48 bit unique id of an RDM device in the notation manufacturer:device (eg. 464C:0A001234)
+//!
+//! The struct `DMXValue` holds a dmx value in the GDTF notation 'value/byte-count' (eg. 255/1 or 65535/2). `GdtfDmxAddress` adds the DMX break of GDTF fixtures with several breaks to a `DMXAddress` (eg. 1.001@2) and `GdtfOffset` holds the attribute `Offset` of GDTF DMX channels (eg. 1,2). `GdtfDmxAddress::from_mvr` reads the `Address` node of MVR scene files (eg. `<Address break="0">1537</Address>`)
+//!
+//! The struct `DMXAddressRange` holds an inclusive range of dmx addresses that may span several universes (eg. 1.1 through 1.120). It parses the notations 1.1-1.20, 1.1..1.20 and 1.1 thru 2.40
+//!
+//! The struct `DMXUniverseBuffer` holds the levels of the 512 slots of one universe and is addressed by `DMXAddress`. `DimmerCurve` maps levels before output, eg. to correct the gamma of LEDs
+//!
+//! The module is designed to never panic but return `DMXParseError` instead. Builder, footprints, buffers and GDTF attributes return their own error types, eg. `DMXFootprintError`. Only the compile time helpers `DMXAddress::parse_const` and `dmx!` and indexing buffers with `buffer[address]` panic on invalid input
+//!
+//! The module holds both, the absolute address and the address separated by universe and address so no calculation needed for further oparations. `CompactDMXAddress` only stores the absolute address in 4 bytes for large collections
 //!
 //! ## Usage
 //! The main struct `DMXAddress` implements the trait `TryFrom<&str>` so usage is straight forward:
@@ -43,29 +57,129 @@
 //!     let dmx_address: Result<DMXAddress, DMXParseError> = "1024".try_into();
 //! }
 //! ```
+//!
+//! ## Features
+//!
+//! - `std` (default): Implements `std::error::Error` for `DMXParseError` and the other error types and enables the methods that return a `String` and the collections `DMXPatch` with import and export of USITT ASCII `Patch` records, `DMXAddressSet`, `UniverseMap` and `DMXBuffer`. Without this feature the crate is `#![no_std]` and can be used on embedded targets
+//! - `serde`: Implements `Serialize` and `Deserialize` for `DMXAddress` and `CompactDMXAddress`. Addresses are serialized as 'universe.address' in human readable formats and as absolute dmx address in compact formats and validated on deserialization. The modules `dmx_struct::serde::as_string`, `as_absolute` and `as_struct` select a fixed representation with `#[serde(with = ...)]`. `DMXFootprint`, `BoundaryPolicy` and `DMXPatch` are (de)serialized as well, the patch with a versioned schema that is documented on `DMXPatch`
+//! - `grandma`: Implements `DMXPatch::write_grandma_xml` to export a patch as XML layer that can be imported by grandMA2 and grandMA3. Enables `std`
+//! - `csv`: Implements `DMXPatch::read_csv` and `DMXPatch::write_csv` to import and export a patch with the columns fixture, universe, address and footprint. Errors report the line of the invalid row. Enables `std`
+//! - `rkyv`: Implements zero-copy (de)serialization with rkyv. The archived form `ArchivedDMXAddress` only stores the absolute dmx address as `u32` and is validated on access. Enables `std`
+//! - `schemars`: Implements `schemars::JsonSchema` for `DMXAddress`. The schema documents the string notations and the ranges of universe, address and absolute dmx address that are accepted by the feature `serde`. Enables `std`
+//! - `arbitrary`: Implements `arbitrary::Arbitrary` for `DMXAddress` to fuzz with cargo-fuzz. Only valid dmx addresses are generated. Enables `std`
+//! - `proptest`: Implements `Arbitrary` for `DMXAddress` and adds strategies for property tests in `dmx_struct::proptest`, eg. `any_dmx_address()` and `dmx_address_in_universes(1..=4)`. Enables `std`
+//! - `e131`: Implements `SacnSource`, which builds sACN (E1.31) data packets with root, framing and DMP layer from a `DMXUniverseBuffer` and keeps the sequence numbers of each universe. `parse_sacn_data_packet` validates received data packets and returns their levels by `DMXAddress`. Enables `std`
+//! - `artnet`: Implements `ArtDmx`, which builds and parses Art-Net ArtDmx packets with sequence and physical port from and to a `DMXUniverseBuffer`. Works without `std`
+//! - `enttec`: Implements `DMXUniverseBuffer::to_enttec_frame` and `DMXUniverseBuffer::parse_enttec_frame` to send the levels of a universe to an Enttec DMX USB Pro widget. Works without `std`
+//! - `sacn`: Implements conversions to use the `sacn` crate for transport. `DMXUniverseBuffer` converts from the received `DMXData` and into the `DataPacketDmpLayer` of a data packet, universes convert with `SacnUniverse`. Enables `std`
+//! - `artnet_protocol`: Implements conversions to use the `artnet_protocol` crate for transport. `ArtNetPortAddress` converts from and to `PortAddress` and `ArtDmx` with the levels in a `DMXUniverseBuffer` from and to `Output`. Enables `artnet` and `std`
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+use core::cmp::Ordering;
+use core::convert::{TryFrom, TryInto};
+use core::fmt::{Display, Formatter, LowerHex, UpperHex};
 
-use std::convert::{TryFrom, TryInto};
-use std::error::Error;
-use std::fmt::{Display, Formatter};
-use std::str::FromStr;
+#[cfg(feature = "artnet")]
+pub use crate::artdmx::{ArtDmx, ArtDmxError};
+pub use crate::artnet::{ArtNetAddress, ArtNetPortAddress};
+#[cfg(feature = "std")]
+pub use crate::buffer::{DMXBuffer, DMXBufferSnapshot};
+pub use crate::buffer::{DMXBufferError, DMXUniverseBuffer, DmpPayloadError};
+pub use crate::builder::{DMXAddressBuilder, DMXAddressBuilderError};
+pub use crate::channel::DMXChannel;
+pub use crate::compact::CompactDMXAddress;
+#[cfg(feature = "csv")]
+pub use crate::csv::PatchCsvError;
+pub use crate::curve::DimmerCurve;
+pub use crate::dialect::Dialect;
+#[cfg(feature = "e131")]
+pub use crate::e131::{parse_sacn_data_packet, SacnPacketError, SacnSource};
+#[cfg(feature = "enttec")]
+pub use crate::enttec::EnttecFrameError;
+pub use crate::error::{DMXParseError, DMXParseErrorKind};
+pub use crate::footprint::{BoundaryPolicy, DMXFootprint, DMXFootprintError};
+pub use crate::format::{FormatOptions, FormattedDMXAddress};
+pub use crate::gdtf::{GdtfDmxAddress, GdtfError, GdtfOffset};
+pub use crate::layout::UniverseLayout;
+#[cfg(feature = "std")]
+pub use crate::map::UniverseMap;
+pub use crate::mvr::MvrAddress;
+pub use crate::osc::{FormattedOscPath, OscPathError, OscPathTemplate};
+pub use crate::parse::{AddressFormat, ParseOptions, DEFAULT_SEPARATORS};
+#[cfg(feature = "std")]
+pub use crate::patch::{DMXPatch, PatchCollision};
+pub use crate::protocol::Protocol;
+pub use crate::range::DMXAddressRange;
+pub use crate::rdm::{RdmUid, RdmUidError, RdmUidRange};
+#[cfg(feature = "rkyv")]
+pub use crate::rkyv::ArchivedDMXAddress;
+pub use crate::sacn::SacnUniverse;
+#[cfg(feature = "std")]
+pub use crate::set::DMXAddressSet;
+#[cfg(feature = "std")]
+pub use crate::usitt::PatchAsciiError;
+pub use crate::value::{DMXValue, DMXValueParseError};
 
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+#[cfg(feature = "artnet")]
+mod artdmx;
+mod artnet;
+#[cfg(feature = "artnet_protocol")]
+mod artnet_protocol;
+mod buffer;
+mod builder;
+mod channel;
+mod compact;
+#[cfg(feature = "csv")]
+mod csv;
+mod curve;
+mod dialect;
 #[cfg(test)]
 mod doc_test;
-
-///This Error is return if an invalid &str is tried to be deparsed as dmx-address instead of panicing
-#[derive(Debug)]
-pub struct DMXParseError;
-
-impl std::fmt::Display for DMXParseError {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "something went terribly wrong")
-    }
-}
-
-impl Error for DMXParseError {}
+#[cfg(feature = "e131")]
+mod e131;
+#[cfg(feature = "enttec")]
+mod enttec;
+mod error;
+mod footprint;
+mod format;
+mod gdtf;
+#[cfg(feature = "grandma")]
+mod grandma;
+mod layout;
+mod macros;
+#[cfg(feature = "std")]
+mod map;
+mod mvr;
+mod osc;
+mod parse;
+#[cfg(feature = "std")]
+mod patch;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+mod protocol;
+mod range;
+mod rdm;
+#[cfg(feature = "rkyv")]
+mod rkyv;
+mod sacn;
+#[cfg(feature = "sacn")]
+mod sacn_interop;
+#[cfg(feature = "schemars")]
+mod schemars;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "std")]
+mod set;
+#[cfg(test)]
+mod test_helpers;
+#[cfg(feature = "std")]
+mod usitt;
+mod value;
 
 ///DMXAddress color representation used in GDTF
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct DMXAddress {
     ///Universe of the DMXAddress starting from 1
     pub universe: u16,
@@ -75,54 +189,374 @@ pub struct DMXAddress {
     pub absolute: u32,
 }
 
-impl TryFrom<&str> for DMXAddress {
-    type Error = DMXParseError;
+impl DMXAddress {
+    ///Number of addresses (slots) in a dmx universe. All limits of addresses are derived from this and `MAX_UNIVERSE`
+    pub const ADDRESSES_PER_UNIVERSE: u16 = 512;
 
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let universe;
-        let address;
-        let absolute;
-
-        if value.contains('.') {
-            //The input is of format 1.234
-            //Splitting the input by .
-            let value: Vec<&str> = value.split('.').collect();
-            //Only one . allowed in this format
-            if value.len() != 2 {
-                return Err(DMXParseError);
-            }
-            //Value before . is universe
-            universe = u32::from_str(value[0]).map_err(|_| DMXParseError)?;
-            //If the universe is 0, the input was not valid
-            if universe == 0 {
-                return Err(DMXParseError);
+    ///The highest universe that is valid by default. sACN (E1.31) supports the universes 1-63999
+    pub const MAX_UNIVERSE: u16 = 63_999;
+
+    ///The highest absolute dmx address that is valid by default, which is the last address in `MAX_UNIVERSE` (32767488)
+    pub const MAX_ABSOLUTE: u32 = Self::MAX_UNIVERSE as u32 * Self::ADDRESSES_PER_UNIVERSE as u32;
+
+    ///Parses a dmx address from bytes without converting them to &str first. Accepts the same formats as `TryFrom<&str>`, eg. b"1.234" or b"1024"
+    pub fn parse_bytes(value: &[u8]) -> Result<DMXAddress, DMXParseError> {
+        parse::parse_bytes(value)
+    }
+
+    ///Parses a dmx address of format 'universe.address' (eg. 1.234) or an absolute dmx address (eg. 1024) in const context, eg. for lookup tables in firmware. Accepts the same input as `TryFrom<&str>`, including a leading '+' of universe, address or absolute dmx address. Unlike the rest of the module this panics on invalid input, which fails the build if it is used in a const
+    ///
+    ///```rust
+    /// use dmx_struct::DMXAddress;
+    ///
+    /// const ADDRESS: DMXAddress = DMXAddress::parse_const("1.234");
+    /// assert_eq!(234, ADDRESS.address);
+    /// ```
+    ///
+    ///```compile_fail
+    /// use dmx_struct::DMXAddress;
+    ///
+    /// const ADDRESS: DMXAddress = DMXAddress::parse_const("1.513");
+    /// ```
+    pub const fn parse_const(value: &str) -> DMXAddress {
+        parse::parse_const(value)
+    }
+
+    ///Creates a dmx address from universe and address in const context. Panics if universe or address is out of range, which fails the build if it is used in a const. The macro `dmx!` calls this with integer literals
+    ///
+    ///```rust
+    /// use dmx_struct::DMXAddress;
+    ///
+    /// const ADDRESS: DMXAddress = DMXAddress::new_const(4, 465);
+    /// assert_eq!(2001, ADDRESS.absolute);
+    /// ```
+    pub const fn new_const(universe: u16, address: u16) -> DMXAddress {
+        parse::from_parts_const(universe as u32, address as u32)
+    }
+
+    ///Parses a dmx address like `TryFrom<&str>` but is more tolerant with user input. In addition whitespace around the input and around universe and address is ignored, eg. ' 1.001 ' or '1 . 5'. A comma is accepted between universe and address, eg. '1,234'. Universe and address may be prefixed with U and A as in export files of fixture planning tools, eg. 'U1.234', 'u001.512' or 'U1 A234', or with the words of rigging paperwork, eg. 'Universe 1 Address 234' or 'universe 1 channel 234'. Anything else is still rejected
+    pub fn try_from_lenient(value: &str) -> Result<DMXAddress, DMXParseError> {
+        Self::parse_with(value, &ParseOptions::new().lenient(true))
+    }
+
+    ///Parses a dmx address and only accepts the input that is allowed by the options, eg. only 'universe.address' for machine generated files
+    pub fn parse_with(value: &str, options: &ParseOptions) -> Result<DMXAddress, DMXParseError> {
+        parse::parse_with(value.as_bytes(), options)
+    }
+
+    ///Parses an absolute dmx address in hexadecimal notation as used by firmware configuration tools, eg. '0x1FF' or '1ff'. The prefix 0x is optional
+    pub fn from_hex_str(value: &str) -> Result<DMXAddress, DMXParseError> {
+        parse::parse_hex(value)
+    }
+
+    ///Parses a dmx address in Art-Net notation of format 'net-subnet-universe.address', eg. '0-1-15.234'. Port address 0 (0-0-0) is universe 1
+    pub fn parse_artnet(value: &str) -> Result<DMXAddress, DMXParseError> {
+        ArtNetAddress::try_from(value).map(DMXAddress::from)
+    }
+
+    ///Creates a DMXAddress from universe and address counted from 0 as used by Art-Net, eg. (0, 0) is 1.001 and (0, 511) is 1.512. The error holds the one-based value that is out of range
+    ///
+    ///```rust
+    /// use dmx_struct::DMXAddress;
+    ///
+    /// let address = DMXAddress::from_zero_based(0, 511).unwrap();
+    /// assert_eq!("1.512", address.to_string());
+    /// assert_eq!((0, 511), address.to_zero_based());
+    /// ```
+    pub fn from_zero_based(universe: u16, address: u16) -> Result<DMXAddress, DMXParseError> {
+        Self::from_parts(u32::from(universe) + 1, u32::from(address) + 1)
+    }
+
+    ///Returns universe and address counted from 0 as used by Art-Net, eg. 1.001 is (0, 0). The fields of DMXAddress are always counted from 1, a universe or address 0 of an address that was built by hand is returned as 0
+    pub fn to_zero_based(&self) -> (u16, u16) {
+        (
+            self.universe.saturating_sub(1),
+            self.address.saturating_sub(1),
+        )
+    }
+
+    ///Parses a dmx address at the start of the input and returns it together with the rest of the input that was not consumed. This allows to parse a dmx address that is part of a larger text, eg. 'patch 101 @ 1.234 range 12'
+    ///
+    ///```rust
+    /// use dmx_struct::DMXAddress;
+    ///
+    /// let (address, rest) = DMXAddress::parse_prefix("1.234 range 12").unwrap();
+    /// assert_eq!(234, address.address);
+    /// assert_eq!(" range 12", rest);
+    /// ```
+    pub fn parse_prefix(value: &str) -> Result<(DMXAddress, &str), DMXParseError> {
+        parse::parse_prefix(value)
+    }
+
+    ///Returns the dmx address with the given format options to be used with {}, eg. to format it as 1/1 or 001.001
+    pub fn format_with(&self, options: &FormatOptions) -> FormattedDMXAddress {
+        FormattedDMXAddress::new(*self, *options)
+    }
+
+    ///Returns the absolute dmx address as string, eg. '1024'. Only available with the feature `std`
+    #[cfg(feature = "std")]
+    pub fn to_absolute_string(&self) -> String {
+        self.absolute.to_string()
+    }
+
+    ///Returns the dmx address in the format 'universe.address' without zero padding, eg. '1.1' instead of '1.001'. Only available with the feature `std`
+    #[cfg(feature = "std")]
+    pub fn to_unpadded_string(&self) -> String {
+        self.format_with(&FormatOptions::new().address_width(0))
+            .to_string()
+    }
+
+    ///Parses many dmx addresses at once, eg. the column of a patch that is imported. Returns the addresses that were parsed in the order of the input and the errors together with the index of the input they belong to. Only available with the feature `std`
+    ///
+    ///```rust
+    /// use dmx_struct::DMXAddress;
+    ///
+    /// let (addresses, errors) = DMXAddress::parse_many(vec!["1.1", "x", "1.513", "513"]);
+    /// assert_eq!(2, addresses.len());
+    /// assert_eq!(vec![1, 2], errors.iter().map(|(index, _)| *index).collect::<Vec<usize>>());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn parse_many<'a, I: IntoIterator<Item = &'a str>>(
+        values: I,
+    ) -> (Vec<DMXAddress>, Vec<(usize, DMXParseError)>) {
+        let mut addresses = Vec::new();
+        let mut errors = Vec::new();
+        for (index, value) in values.into_iter().enumerate() {
+            match DMXAddress::try_from(value) {
+                Ok(address) => addresses.push(address),
+                Err(e) => errors.push((index, e)),
             }
-            //Value after . is address
-            address = u32::from_str(value[1]).map_err(|_| DMXParseError)?;
-            //calculating the absolute address from universe and address
-            absolute = address + ((universe - 1) * 512);
-        } else {
-            //The input holds the absolute address
-            absolute = u32::from_str(value).map_err(|_| DMXParseError)?;
-            //Calculating the address from the absolute address
-            let x = absolute % 512;
-            //Special case if the address is 512 the % operator will return 0 but should return 512 because dmx starts counting at 1
-            address = if x > 0 { x } else { 512 };
-            if x > 0 {
-                //If address was not 512 adding one to the universe because dmx starts counting at 1
-                universe = (absolute / 512) + 1;
-            } else {
-                //If address was 512 not adding one to the universe because dmx starts counting at 1
-                universe = absolute / 512;
+        }
+        (addresses, errors)
+    }
+
+    ///Parses a list of dmx addresses that is typed into a single text field, eg. '1.1, 1.17; 2.1 2.33'. The addresses are separated by commas, semicolons or whitespace. Returns the addresses that were parsed in the order of the input and the errors of the invalid addresses together with their byte offset in the input. Offsets in the errors are positions in the whole input as well. Only available with the feature `std`
+    ///
+    ///```rust
+    /// use dmx_struct::DMXAddress;
+    ///
+    /// let (addresses, errors) = DMXAddress::parse_list("1.1, 1.17, x 2.33");
+    /// assert_eq!(3, addresses.len());
+    /// assert_eq!(11, errors[0].0);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn parse_list(value: &str) -> (Vec<DMXAddress>, Vec<(usize, DMXParseError)>) {
+        let mut addresses = Vec::new();
+        let mut errors = Vec::new();
+        let mut start = 0;
+        for (end, byte) in value.bytes().chain(core::iter::once(b' ')).enumerate() {
+            if byte == b',' || byte == b';' || byte.is_ascii_whitespace() {
+                if start < end {
+                    match DMXAddress::try_from(&value[start..end]) {
+                        Ok(address) => addresses.push(address),
+                        Err(e) => errors.push((start, e.shift(start))),
+                    }
+                }
+                start = end + 1;
             }
         }
-        //Some dmx validity checks.
-        //63'999 is max number of universes supported by sACN
-        //dmx address is max 512 by definition
-        //address 0 and universe 0 are not valid. Start count at 1
-        if universe > 63_999 || address > 512 || address == 0 || universe == 0 {
-            return Err(DMXParseError {});
+        (addresses, errors)
+    }
+
+    ///Returns the address of the following slot. Rolls over to the next universe after address 512. Returns None if the address is the last valid one
+    pub fn next(&self) -> Option<DMXAddress> {
+        Self::from_absolute(self.absolute.checked_add(1)?).ok()
+    }
+
+    ///Returns the address of the preceding slot. Rolls back to the previous universe before address 1. Returns None if the address is 1.001
+    pub fn prev(&self) -> Option<DMXAddress> {
+        Self::from_absolute(self.absolute.checked_sub(1)?).ok()
+    }
+
+    ///Returns every address from this address to end (inclusive) in ascending order. Rolls over to the next universe after address 512. Returns nothing if end is before this address
+    ///
+    ///```rust
+    /// use std::convert::TryFrom;
+    ///
+    /// use dmx_struct::DMXAddress;
+    ///
+    /// let start = DMXAddress::try_from("1.511").unwrap();
+    /// let end = DMXAddress::try_from("2.1").unwrap();
+    /// assert_eq!(3, start.iter_to(&end).count());
+    /// ```
+    pub fn iter_to(&self, end: &DMXAddress) -> impl Iterator<Item = DMXAddress> {
+        DMXAddressRange {
+            start: *self,
+            end: *end,
+        }
+        .addresses()
+    }
+
+    ///Returns the start addresses of consecutive footprints with step slots each, eg. to patch 40 identical 16 channel fixtures. The policy defines what happens with a footprint that does not fit into the rest of a universe: `BoundaryPolicy::Reject` ends the iteration, `BoundaryPolicy::WrapToNextUniverse` moves it to the next universe and `BoundaryPolicy::Allow` lets it continue in the next universe. The iteration ends before the first footprint that does not end before the last valid dmx address. Use `take` to get a limited number of addresses
+    ///
+    ///```rust
+    /// use std::convert::TryFrom;
+    ///
+    /// use dmx_struct::{BoundaryPolicy, DMXAddress};
+    ///
+    /// let start = DMXAddress::try_from("1.481").unwrap();
+    /// let starts: Vec<String> = start.iter_step(16, BoundaryPolicy::WrapToNextUniverse).take(4).map(|a| a.to_string()).collect();
+    /// assert_eq!(vec!["1.481", "1.497", "2.001", "2.017"], starts);
+    /// ```
+    pub fn iter_step(&self, step: u16, policy: BoundaryPolicy) -> impl Iterator<Item = DMXAddress> {
+        let first = DMXFootprint::with_policy(*self, step, policy).ok();
+        core::iter::successors(first, move |previous| {
+            let start = Self::from_absolute(previous.start.absolute + u32::from(step)).ok()?;
+            DMXFootprint::with_policy(start, step, policy).ok()
+        })
+        .map(|footprint| footprint.start)
+    }
+
+    ///Returns the address of the fine byte of a 16 bit channel that has its coarse byte at this address. The fine byte is the following slot and rolls over to the next universe if the coarse byte is at address 512. Returns None if there is no following slot
+    ///
+    ///```rust
+    /// use std::convert::TryFrom;
+    ///
+    /// use dmx_struct::DMXAddress;
+    ///
+    /// let coarse = DMXAddress::try_from("1.512").unwrap();
+    /// assert_eq!("2.001", coarse.fine_address().unwrap().to_string());
+    /// ```
+    pub fn fine_address(&self) -> Option<DMXAddress> {
+        self.byte_address(1)
+    }
+
+    ///Returns the address of a byte of a multi byte channel (16, 24 or 32 bit) that has its first (most significant) byte at this address. Index 0 is this address, 1 the fine byte and so on. The bytes roll over to the next universe. Returns None if there are not enough slots
+    pub fn byte_address(&self, index: u8) -> Option<DMXAddress> {
+        Self::from_absolute(self.absolute.checked_add(index.into())?).ok()
+    }
+
+    ///Returns the distance in slots from `other` to `self`. The result is positive if `self` comes after `other`
+    pub fn offset_from(&self, other: &DMXAddress) -> i64 {
+        i64::from(self.absolute) - i64::from(other.absolute)
+    }
+
+    ///Returns true if both addresses are in the same universe, eg. to group fixtures by universe
+    pub fn same_universe(&self, other: &DMXAddress) -> bool {
+        self.universe == other.universe
+    }
+
+    ///Returns how many universes the addresses are apart regardless of their order, eg. 0 for addresses in the same universe and 1 for 1.512 and 2.001
+    pub fn universe_distance(&self, other: &DMXAddress) -> u16 {
+        self.universe.abs_diff(other.universe)
+    }
+
+    ///Returns a new DMXAddress in the given universe keeping the address. Returns an error if the universe is not valid
+    pub fn with_universe(&self, universe: u16) -> Result<DMXAddress, DMXParseError> {
+        Self::from_parts(universe.into(), self.address.into())
+    }
+
+    ///Returns a new DMXAddress with the given address keeping the universe. Returns an error if the address is not valid
+    pub fn with_address(&self, address: u16) -> Result<DMXAddress, DMXParseError> {
+        Self::from_parts(self.universe.into(), address.into())
+    }
+
+    ///Returns true if the address is the first slot (1) of its universe
+    pub fn is_first_in_universe(&self) -> bool {
+        self.address == 1
+    }
+
+    ///Returns true if the address is the last slot (512) of its universe
+    pub fn is_last_in_universe(&self) -> bool {
+        self.address == Self::ADDRESSES_PER_UNIVERSE
+    }
+
+    ///Returns the first address (slot 1) of the universe this address is in
+    pub fn universe_start(&self) -> DMXAddress {
+        DMXAddress {
+            universe: self.universe,
+            address: 1,
+            absolute: self.absolute.saturating_sub(u32::from(self.address)) + 1,
+        }
+    }
+
+    ///Returns the last address (slot 512) of the universe this address is in
+    pub fn universe_end(&self) -> DMXAddress {
+        DMXAddress {
+            universe: self.universe,
+            address: Self::ADDRESSES_PER_UNIVERSE,
+            absolute: self.absolute.saturating_sub(u32::from(self.address))
+                + u32::from(Self::ADDRESSES_PER_UNIVERSE),
+        }
+    }
+
+    ///Returns true if the universe of the address is valid in the protocol, eg. 40000.001 is valid for sACN but not for Art-Net
+    pub fn is_valid_for(&self, protocol: Protocol) -> bool {
+        self.universe <= protocol.max_universe()
+    }
+
+    ///Validates a universe on its own, eg. while the user types it into a field. Returns the universe or `InvalidUniverse` if it is out of range (1-63999)
+    ///
+    ///```rust
+    /// use dmx_struct::{DMXAddress, DMXParseError};
+    ///
+    /// assert_eq!(4, DMXAddress::validate_universe(4).unwrap());
+    /// assert!(matches!(DMXAddress::validate_universe(0), Err(DMXParseError::InvalidUniverse(0))));
+    /// assert!(matches!(DMXAddress::validate_address(513), Err(DMXParseError::InvalidAddress(513))));
+    /// ```
+    pub fn validate_universe(universe: u32) -> Result<u16, DMXParseError> {
+        if universe == 0 || universe > u32::from(Self::MAX_UNIVERSE) {
+            return Err(DMXParseError::InvalidUniverse(universe));
+        }
+        Ok(universe as u16)
+    }
+
+    ///Validates an address in a universe on its own, eg. while the user types it into a field. Returns the address or `InvalidAddress` if it is out of range (1-512)
+    pub fn validate_address(address: u32) -> Result<u16, DMXParseError> {
+        if address == 0 || address > u32::from(Self::ADDRESSES_PER_UNIVERSE) {
+            return Err(DMXParseError::InvalidAddress(address));
+        }
+        Ok(address as u16)
+    }
+
+    ///Validates universe and address and creates the dmx address. Returns the error of the universe first if both are out of range, so use `validate_universe` and `validate_address` to report both
+    pub fn validate_parts(universe: u32, address: u32) -> Result<DMXAddress, DMXParseError> {
+        Self::from_parts(universe, address)
+    }
+
+    ///Creates a validated DMXAddress from universe and address.
+    ///63'999 is max number of universes supported by sACN
+    ///dmx address is max 512 by definition
+    ///address 0 and universe 0 are not valid. Start count at 1
+    fn from_parts(universe: u32, address: u32) -> Result<DMXAddress, DMXParseError> {
+        Self::from_parts_in(universe, address, Protocol::Sacn)
+    }
+
+    ///Creates a validated DMXAddress from universe and address. The highest universe is defined by the protocol
+    pub(crate) fn from_parts_in(
+        universe: u32,
+        address: u32,
+        protocol: Protocol,
+    ) -> Result<DMXAddress, DMXParseError> {
+        if universe == 0 || universe > u32::from(protocol.max_universe()) {
+            return Err(protocol.invalid_universe(universe));
+        }
+        Self::validate_address(address)?;
+        //Calculating the absolute address from universe and address. The calculation is checked, so it can never overflow even if the limits change
+        let absolute = UniverseLayout::DMX.join(universe, address)?;
+        Ok(DMXAddress {
+            universe: universe.try_into().unwrap(),
+            address: address.try_into().unwrap(),
+            absolute,
+        })
+    }
+
+    ///Creates a validated DMXAddress from an absolute dmx address
+    fn from_absolute(absolute: u32) -> Result<DMXAddress, DMXParseError> {
+        Self::from_absolute_in(absolute, Protocol::Sacn)
+    }
+
+    ///Creates a validated DMXAddress from an absolute dmx address. The highest absolute address is defined by the protocol
+    pub(crate) fn from_absolute_in(
+        absolute: u32,
+        protocol: Protocol,
+    ) -> Result<DMXAddress, DMXParseError> {
+        //The highest absolute address is address 512 in the highest universe of the protocol
+        if absolute == 0 || absolute > protocol.max_absolute() {
+            return Err(protocol.invalid_absolute(absolute));
         }
+        //Calculating universe and address from the absolute address
+        let (universe, address) = UniverseLayout::DMX.split(absolute)?;
         Ok(DMXAddress {
             universe: universe.try_into().unwrap(),
             address: address.try_into().unwrap(),
@@ -131,6 +565,14 @@ impl TryFrom<&str> for DMXAddress {
     }
 }
 
+impl TryFrom<&str> for DMXAddress {
+    type Error = DMXParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        parse::parse(value)
+    }
+}
+
 ///Dmx addresses can be compared with ==
 impl PartialEq for DMXAddress {
     fn eq(&self, other: &Self) -> bool {
@@ -140,19 +582,110 @@ impl PartialEq for DMXAddress {
     }
 }
 
-///Dmx addresses can be used in format with {}. It will return the format 'universe.address'
+///Compares with a dmx address in any notation of `TryFrom<&str>`, eg. 1.234, 1/234 or 1024. Text that is not a valid dmx address is never equal
+impl PartialEq<&str> for DMXAddress {
+    fn eq(&self, other: &&str) -> bool {
+        DMXAddress::try_from(*other) == Ok(*self)
+    }
+}
+
+impl PartialEq<DMXAddress> for &str {
+    fn eq(&self, other: &DMXAddress) -> bool {
+        other == self
+    }
+}
+
+///Compares with an absolute dmx address, eg. 1.001 is equal to 1
+impl PartialEq<u32> for DMXAddress {
+    fn eq(&self, other: &u32) -> bool {
+        self.absolute == *other
+    }
+}
+
+impl PartialEq<DMXAddress> for u32 {
+    fn eq(&self, other: &DMXAddress) -> bool {
+        other == self
+    }
+}
+
+///Compares with an absolute dmx address, eg. to filter addresses with bounds from a configuration like `(513..=1024).contains(&address)` or `address > 512`
+impl PartialOrd<u32> for DMXAddress {
+    fn partial_cmp(&self, other: &u32) -> Option<Ordering> {
+        self.absolute.partial_cmp(other)
+    }
+}
+
+impl PartialOrd<DMXAddress> for u32 {
+    fn partial_cmp(&self, other: &DMXAddress) -> Option<Ordering> {
+        self.partial_cmp(&other.absolute)
+    }
+}
+
+///Dmx addresses can be used in format with {}. It will return the format 'universe.address'. With the alternate flag {:#} it will return the absolute dmx address
+///
+///The format 'universe.address' with the address padded to 3 digits (eg. 1.001) is the canonical form of a dmx address and will stay stable, so it can be used as key. `DMXAddress::try_from(address.to_string())` always returns an equal address
+///
+///With the flag 0 the universe is padded with zeros until the whole address fills the width like numbers in std, so sorted output has the same width, eg. {:09} returns 00001.001 and {:#09} returns 000001024. Use `FormatOptions::universe_width` to pad the universe in other notations
+///
+///Otherwise width, fill and alignment are applied to the whole address like to text, eg. {:>9} returns '    1.001' and {:-<9} returns '1.001----'
 impl Display for DMXAddress {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}.{:03}", self.universe, self.address)
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        if f.alternate() && f.sign_aware_zero_pad() {
+            write!(
+                f,
+                "{:0width$}",
+                self.absolute,
+                width = f.width().unwrap_or(0)
+            )
+        } else if f.alternate() {
+            format::pad(f, format::digits(self.absolute), |f| {
+                write!(f, "{}", self.absolute)
+            })
+        } else if f.sign_aware_zero_pad() {
+            let formatted = self.format_with(&FormatOptions::new());
+            //The universe takes the width that is left after the rest of the address
+            let universe_width = f.width().unwrap_or(0).saturating_sub(formatted.len())
+                + format::digits(self.universe.into());
+            let options = FormatOptions::new().universe_width(universe_width);
+            write!(f, "{}", self.format_with(&options))
+        } else {
+            self.format_with(&FormatOptions::new()).fmt(f)
+        }
+    }
+}
+
+///Returns the canonical form of the dmx address like `to_string`, eg. 1.001. Only available with the feature `std`
+#[cfg(feature = "std")]
+impl From<DMXAddress> for String {
+    fn from(address: DMXAddress) -> Self {
+        address.to_string()
+    }
+}
+
+///Dmx addresses can be used in format with {:x}. It will return the absolute dmx address in hexadecimal notation. All flags of the formatter are supported, eg. {:#x} adds the prefix 0x
+impl LowerHex for DMXAddress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        LowerHex::fmt(&self.absolute, f)
+    }
+}
+
+///Dmx addresses can be used in format with {:X}. It will return the absolute dmx address in hexadecimal notation. All flags of the formatter are supported, eg. {:#X} adds the prefix 0x
+impl UpperHex for DMXAddress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        UpperHex::fmt(&self.absolute, f)
     }
 }
 
 ///Some tests
 #[cfg(test)]
 mod tests {
+    use std::cmp::Ordering;
     use std::convert::TryFrom;
 
-    use crate::DMXAddress;
+    use crate::{
+        AddressFormat, BoundaryPolicy, CompactDMXAddress, DMXAddress, DMXParseError, FormatOptions,
+        ParseOptions, Protocol, SacnUniverse, UniverseLayout,
+    };
 
     #[test]
     fn test_valid_separated() {
@@ -324,151 +857,106 @@ mod tests {
 
     #[test]
     fn test_invalid_1() {
-        match DMXAddress::try_from("something invalid") {
-            Ok(_) => {
-                panic!("test_invalid should return an error");
-            }
-            Err(_) => {}
+        if DMXAddress::try_from("something invalid").is_ok() {
+            panic!("test_invalid should return an error");
         }
     }
 
     #[test]
     fn test_invalid_2() {
-        match DMXAddress::try_from("2.") {
-            Ok(_) => {
-                panic!("test_invalid should return an error");
-            }
-            Err(_) => {}
+        if DMXAddress::try_from("2.").is_ok() {
+            panic!("test_invalid should return an error");
         }
     }
 
     #[test]
     fn test_invalid_3() {
-        match DMXAddress::try_from(".2") {
-            Ok(_) => {
-                panic!("test_invalid should return an error");
-            }
-            Err(_) => {}
+        if DMXAddress::try_from(".2").is_ok() {
+            panic!("test_invalid should return an error");
         }
     }
 
     #[test]
     fn test_invalid_4() {
-        match DMXAddress::try_from(".") {
-            Ok(_) => {
-                panic!("test_invalid should return an error");
-            }
-            Err(_) => {}
+        if DMXAddress::try_from(".").is_ok() {
+            panic!("test_invalid should return an error");
         }
     }
 
     #[test]
     fn test_invalid_5() {
-        match DMXAddress::try_from("0.1") {
-            Ok(_) => {
-                panic!("test_invalid should return an error");
-            }
-            Err(_) => {}
+        if DMXAddress::try_from("0.1").is_ok() {
+            panic!("test_invalid should return an error");
         }
     }
 
     #[test]
     fn test_invalid_6() {
-        match DMXAddress::try_from("2.0") {
-            Ok(_) => {
-                panic!("test_invalid should return an error");
-            }
-            Err(_) => {}
+        if DMXAddress::try_from("2.0").is_ok() {
+            panic!("test_invalid should return an error");
         }
     }
 
     #[test]
     fn test_invalid_7() {
-        match DMXAddress::try_from("0.0") {
-            Ok(_) => {
-                panic!("test_invalid should return an error");
-            }
-            Err(_) => {}
+        if DMXAddress::try_from("0.0").is_ok() {
+            panic!("test_invalid should return an error");
         }
     }
 
     #[test]
     fn test_invalid_8() {
-        match DMXAddress::try_from("2.513") {
-            Ok(_) => {
-                panic!("test_invalid should return an error");
-            }
-            Err(_) => {}
+        if DMXAddress::try_from("2.513").is_ok() {
+            panic!("test_invalid should return an error");
         }
     }
 
     #[test]
     fn test_invalid_9() {
-        match DMXAddress::try_from("63999.513") {
-            Ok(_) => {
-                panic!("test_invalid should return an error");
-            }
-            Err(_) => {}
+        if DMXAddress::try_from("63999.513").is_ok() {
+            panic!("test_invalid should return an error");
         }
     }
 
     #[test]
     fn test_invalid_10() {
-        match DMXAddress::try_from("0") {
-            Ok(_) => {
-                panic!("test_invalid should return an error");
-            }
-            Err(_) => {}
+        if DMXAddress::try_from("0").is_ok() {
+            panic!("test_invalid should return an error");
         }
     }
 
     #[test]
     fn test_invalid_11() {
-        match DMXAddress::try_from("98981265123519681981681514984984984464984984") {
-            Ok(_) => {
-                panic!("test_invalid should return an error");
-            }
-            Err(_) => {}
+        if DMXAddress::try_from("98981265123519681981681514984984984464984984").is_ok() {
+            panic!("test_invalid should return an error");
         }
     }
 
     #[test]
     fn test_invalid_12() {
-        match DMXAddress::try_from("-3") {
-            Ok(_) => {
-                panic!("test_invalid should return an error");
-            }
-            Err(_) => {}
+        if DMXAddress::try_from("-3").is_ok() {
+            panic!("test_invalid should return an error");
         }
     }
 
     #[test]
     fn test_invalid_13() {
-        match DMXAddress::try_from("-1.3") {
-            Ok(_) => {
-                panic!("test_invalid should return an error");
-            }
-            Err(_) => {}
+        if DMXAddress::try_from("-1.3").is_ok() {
+            panic!("test_invalid should return an error");
         }
     }
 
     #[test]
     fn test_invalid_14() {
-        match DMXAddress::try_from("1.-3") {
-            Ok(_) => {
-                panic!("test_invalid should return an error");
-            }
-            Err(_) => {}
+        if DMXAddress::try_from("1.-3").is_ok() {
+            panic!("test_invalid should return an error");
         }
     }
 
     #[test]
     fn test_invalid_15() {
-        match DMXAddress::try_from("-1.-4") {
-            Ok(_) => {
-                panic!("test_invalid should return an error");
-            }
-            Err(_) => {}
+        if DMXAddress::try_from("-1.-4").is_ok() {
+            panic!("test_invalid should return an error");
         }
     }
 
@@ -516,4 +1004,1667 @@ mod tests {
             "1.009"
         );
     }
+
+    #[test]
+    fn test_next() {
+        assert_eq!(
+            DMXAddress::try_from("1.2").unwrap(),
+            DMXAddress::try_from("1.1").unwrap().next().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_next_2() {
+        assert_eq!(
+            DMXAddress::try_from("2.1").unwrap(),
+            DMXAddress::try_from("1.512").unwrap().next().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_next_3() {
+        assert_eq!(
+            DMXAddress {
+                universe: 3,
+                address: 512,
+                absolute: 1536
+            },
+            DMXAddress::try_from("3.511").unwrap().next().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_next_4() {
+        assert_eq!(None, DMXAddress::try_from("63999.512").unwrap().next());
+    }
+
+    #[test]
+    fn test_prev() {
+        assert_eq!(
+            DMXAddress::try_from("1.1").unwrap(),
+            DMXAddress::try_from("1.2").unwrap().prev().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_prev_2() {
+        assert_eq!(
+            DMXAddress::try_from("1.512").unwrap(),
+            DMXAddress::try_from("2.1").unwrap().prev().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_prev_3() {
+        assert_eq!(None, DMXAddress::try_from("1.1").unwrap().prev());
+    }
+
+    #[test]
+    fn test_iter_to() {
+        let addresses: Vec<DMXAddress> = DMXAddress::try_from("1.511")
+            .unwrap()
+            .iter_to(&DMXAddress::try_from("2.2").unwrap())
+            .collect();
+        assert_eq!(
+            vec![
+                DMXAddress::try_from("1.511").unwrap(),
+                DMXAddress::try_from("1.512").unwrap(),
+                DMXAddress::try_from("2.1").unwrap(),
+                DMXAddress::try_from("2.2").unwrap()
+            ],
+            addresses
+        );
+    }
+
+    #[test]
+    fn test_iter_to_2() {
+        let address = DMXAddress::try_from("63999.512").unwrap();
+        assert_eq!(
+            vec![address],
+            address.iter_to(&address).collect::<Vec<DMXAddress>>()
+        );
+    }
+
+    #[test]
+    fn test_iter_to_3() {
+        assert_eq!(
+            0,
+            DMXAddress::try_from("1.2")
+                .unwrap()
+                .iter_to(&DMXAddress::try_from("1.1").unwrap())
+                .count()
+        );
+    }
+
+    #[test]
+    fn test_iter_step() {
+        let starts: Vec<DMXAddress> = DMXAddress::try_from("1.481")
+            .unwrap()
+            .iter_step(16, BoundaryPolicy::Allow)
+            .take(4)
+            .collect();
+        assert_eq!(
+            vec![
+                DMXAddress::try_from("1.481").unwrap(),
+                DMXAddress::try_from("1.497").unwrap(),
+                DMXAddress::try_from("2.1").unwrap(),
+                DMXAddress::try_from("2.17").unwrap()
+            ],
+            starts
+        );
+        assert_eq!(
+            32,
+            DMXAddress::try_from("1.1")
+                .unwrap()
+                .iter_step(16, BoundaryPolicy::Allow)
+                .take(32)
+                .count()
+        );
+    }
+
+    #[test]
+    fn test_iter_step_2() {
+        let starts: Vec<DMXAddress> = DMXAddress::try_from("1.480")
+            .unwrap()
+            .iter_step(16, BoundaryPolicy::WrapToNextUniverse)
+            .take(4)
+            .collect();
+        assert_eq!(
+            vec![
+                DMXAddress::try_from("1.480").unwrap(),
+                DMXAddress::try_from("1.496").unwrap(),
+                DMXAddress::try_from("2.1").unwrap(),
+                DMXAddress::try_from("2.17").unwrap()
+            ],
+            starts
+        );
+    }
+
+    #[test]
+    fn test_iter_step_3() {
+        let starts: Vec<DMXAddress> = DMXAddress::try_from("1.480")
+            .unwrap()
+            .iter_step(16, BoundaryPolicy::Reject)
+            .collect();
+        assert_eq!(2, starts.len());
+        assert_eq!(DMXAddress::try_from("1.496").unwrap(), starts[1]);
+        assert_eq!(
+            0,
+            DMXAddress::try_from("1.1")
+                .unwrap()
+                .iter_step(0, BoundaryPolicy::Allow)
+                .count()
+        );
+    }
+
+    #[test]
+    fn test_iter_step_4() {
+        let starts: Vec<DMXAddress> = DMXAddress::try_from("63999.481")
+            .unwrap()
+            .iter_step(16, BoundaryPolicy::Allow)
+            .collect();
+        assert_eq!(2, starts.len());
+        assert_eq!(DMXAddress::try_from("63999.497").unwrap(), starts[1]);
+    }
+
+    #[test]
+    fn test_offset_from() {
+        assert_eq!(
+            9,
+            DMXAddress::try_from("1.10")
+                .unwrap()
+                .offset_from(&DMXAddress::try_from("1.1").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_limits() {
+        assert_eq!(512, DMXAddress::ADDRESSES_PER_UNIVERSE);
+        assert_eq!(63_999, DMXAddress::MAX_UNIVERSE);
+        assert_eq!(32_767_488, DMXAddress::MAX_ABSOLUTE);
+        assert_eq!(DMXAddress::MAX_UNIVERSE, Protocol::Sacn.max_universe());
+        assert_eq!(DMXAddress::MAX_ABSOLUTE, Protocol::Sacn.max_absolute());
+        assert_eq!(DMXAddress::MAX_UNIVERSE, SacnUniverse::MAX);
+        assert_eq!(
+            DMXAddress::ADDRESSES_PER_UNIVERSE,
+            UniverseLayout::DMX.universe_size()
+        );
+        let last = DMXAddress::try_from("63999.512").unwrap();
+        assert_eq!(DMXAddress::MAX_ABSOLUTE, last.absolute);
+        assert!(last.next().is_none());
+    }
+
+    #[test]
+    fn test_limits_parts() {
+        for protocol in &[Protocol::Sacn, Protocol::ArtNet, Protocol::Unlimited] {
+            let max = u32::from(protocol.max_universe());
+            for universe in [0, 1, 2, max - 1, max, max + 1, 65_536, u32::MAX] {
+                for address in [0, 1, 2, 511, 512, 513, 65_536, u32::MAX] {
+                    let result = DMXAddress::from_parts_in(universe, address, *protocol);
+                    let valid = (1..=max).contains(&universe) && (1..=512).contains(&address);
+                    assert_eq!(
+                        valid,
+                        result.is_ok(),
+                        "{}.{} in {}",
+                        universe,
+                        address,
+                        protocol
+                    );
+                    let options = ParseOptions::new().protocol(*protocol);
+                    let parsed =
+                        DMXAddress::parse_with(&format!("{}.{}", universe, address), &options);
+                    assert_eq!(
+                        valid,
+                        parsed.is_ok(),
+                        "{}.{} in {}",
+                        universe,
+                        address,
+                        protocol
+                    );
+                    if let Ok(result) = result {
+                        assert_eq!(
+                            u64::from(universe - 1) * 512 + u64::from(address),
+                            u64::from(result.absolute)
+                        );
+                        assert_eq!(
+                            result,
+                            DMXAddress::from_absolute_in(result.absolute, *protocol).unwrap()
+                        );
+                        assert_eq!(result, parsed.unwrap());
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_limits_absolute() {
+        for protocol in &[Protocol::Sacn, Protocol::ArtNet, Protocol::Unlimited] {
+            let max = protocol.max_absolute();
+            for absolute in [
+                0,
+                1,
+                511,
+                512,
+                513,
+                max - 512,
+                max - 1,
+                max,
+                max + 1,
+                u32::MAX,
+            ] {
+                let result = DMXAddress::from_absolute_in(absolute, *protocol);
+                assert_eq!(
+                    (1..=max).contains(&absolute),
+                    result.is_ok(),
+                    "{} in {}",
+                    absolute,
+                    protocol
+                );
+                if let Ok(result) = result {
+                    assert_eq!(
+                        result,
+                        DMXAddress::from_parts_in(
+                            result.universe.into(),
+                            result.address.into(),
+                            *protocol
+                        )
+                        .unwrap()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_limits_universes() {
+        for universe in [1, DMXAddress::MAX_UNIVERSE] {
+            for address in 1..=DMXAddress::ADDRESSES_PER_UNIVERSE {
+                let value = format!("{}.{}", universe, address);
+                let parsed = DMXAddress::try_from(value.as_str()).unwrap();
+                let absolute = DMXAddress::try_from(parsed.absolute.to_string().as_str()).unwrap();
+                assert_eq!(parsed, absolute);
+                assert_eq!(parsed, DMXAddress::parse_const(&value));
+                assert_eq!(parsed, DMXAddress::new_const(universe, address));
+                assert_eq!(parsed, DMXAddress::from(CompactDMXAddress::from(parsed)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_validate_universe() {
+        assert_eq!(1, DMXAddress::validate_universe(1).unwrap());
+        assert_eq!(63_999, DMXAddress::validate_universe(63_999).unwrap());
+        assert!(matches!(
+            DMXAddress::validate_universe(0),
+            Err(DMXParseError::InvalidUniverse(0))
+        ));
+        assert!(matches!(
+            DMXAddress::validate_universe(64_000),
+            Err(DMXParseError::InvalidUniverse(64_000))
+        ));
+        assert!(matches!(
+            DMXAddress::validate_universe(u32::MAX),
+            Err(DMXParseError::InvalidUniverse(u32::MAX))
+        ));
+    }
+
+    #[test]
+    fn test_validate_address() {
+        assert_eq!(1, DMXAddress::validate_address(1).unwrap());
+        assert_eq!(512, DMXAddress::validate_address(512).unwrap());
+        assert!(matches!(
+            DMXAddress::validate_address(0),
+            Err(DMXParseError::InvalidAddress(0))
+        ));
+        assert!(matches!(
+            DMXAddress::validate_address(513),
+            Err(DMXParseError::InvalidAddress(513))
+        ));
+    }
+
+    #[test]
+    fn test_validate_parts() {
+        assert_eq!(
+            DMXAddress::try_from("4.465").unwrap(),
+            DMXAddress::validate_parts(4, 465).unwrap()
+        );
+        assert!(matches!(
+            DMXAddress::validate_parts(4, 513),
+            Err(DMXParseError::InvalidAddress(513))
+        ));
+        assert!(matches!(
+            DMXAddress::validate_parts(0, 513),
+            Err(DMXParseError::InvalidUniverse(0))
+        ));
+    }
+
+    #[test]
+    fn test_same_universe() {
+        let address = DMXAddress::try_from("2.1").unwrap();
+        assert!(address.same_universe(&DMXAddress::try_from("2.512").unwrap()));
+        assert!(address.same_universe(&address));
+        assert!(!address.same_universe(&DMXAddress::try_from("1.512").unwrap()));
+    }
+
+    #[test]
+    fn test_universe_distance() {
+        let address = DMXAddress::try_from("2.1").unwrap();
+        assert_eq!(
+            0,
+            address.universe_distance(&DMXAddress::try_from("2.512").unwrap())
+        );
+        assert_eq!(
+            1,
+            address.universe_distance(&DMXAddress::try_from("1.512").unwrap())
+        );
+        assert_eq!(
+            1,
+            DMXAddress::try_from("1.512")
+                .unwrap()
+                .universe_distance(&address)
+        );
+        assert_eq!(
+            63_998,
+            DMXAddress::try_from("1.1")
+                .unwrap()
+                .universe_distance(&DMXAddress::try_from("63999.512").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_offset_from_2() {
+        assert_eq!(
+            -9,
+            DMXAddress::try_from("1.1")
+                .unwrap()
+                .offset_from(&DMXAddress::try_from("1.10").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_offset_from_3() {
+        assert_eq!(
+            1,
+            DMXAddress::try_from("2.1")
+                .unwrap()
+                .offset_from(&DMXAddress::try_from("1.512").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_offset_from_4() {
+        assert_eq!(
+            -1025,
+            DMXAddress::try_from("1.500")
+                .unwrap()
+                .offset_from(&DMXAddress::try_from("3.501").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_offset_from_5() {
+        let address = DMXAddress::try_from("3.17").unwrap();
+        assert_eq!(0, address.offset_from(&address));
+    }
+
+    #[test]
+    fn test_with_universe() {
+        assert_eq!(
+            DMXAddress {
+                universe: 7,
+                address: 12,
+                absolute: 3084
+            },
+            DMXAddress::try_from("1.12")
+                .unwrap()
+                .with_universe(7)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_with_universe_2() {
+        assert!(DMXAddress::try_from("1.12")
+            .unwrap()
+            .with_universe(0)
+            .is_err());
+    }
+
+    #[test]
+    fn test_with_universe_3() {
+        assert!(DMXAddress::try_from("1.12")
+            .unwrap()
+            .with_universe(64_000)
+            .is_err());
+    }
+
+    #[test]
+    fn test_with_address() {
+        assert_eq!(
+            DMXAddress {
+                universe: 3,
+                address: 512,
+                absolute: 1536
+            },
+            DMXAddress::try_from("3.1")
+                .unwrap()
+                .with_address(512)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_with_address_2() {
+        assert!(DMXAddress::try_from("3.1")
+            .unwrap()
+            .with_address(0)
+            .is_err());
+    }
+
+    #[test]
+    fn test_with_address_3() {
+        assert!(DMXAddress::try_from("3.1")
+            .unwrap()
+            .with_address(513)
+            .is_err());
+    }
+
+    #[test]
+    fn test_is_first_in_universe() {
+        assert!(DMXAddress::try_from("3.1").unwrap().is_first_in_universe());
+        assert!(!DMXAddress::try_from("3.2").unwrap().is_first_in_universe());
+        assert!(!DMXAddress::try_from("3.512")
+            .unwrap()
+            .is_first_in_universe());
+    }
+
+    #[test]
+    fn test_is_last_in_universe() {
+        assert!(DMXAddress::try_from("3.512").unwrap().is_last_in_universe());
+        assert!(!DMXAddress::try_from("3.511").unwrap().is_last_in_universe());
+        assert!(!DMXAddress::try_from("4.1").unwrap().is_last_in_universe());
+    }
+
+    #[test]
+    fn test_universe_start() {
+        assert_eq!(
+            DMXAddress {
+                universe: 3,
+                address: 1,
+                absolute: 1025
+            },
+            DMXAddress::try_from("3.234").unwrap().universe_start()
+        );
+    }
+
+    #[test]
+    fn test_universe_start_2() {
+        assert_eq!(
+            DMXAddress::try_from("1.1").unwrap(),
+            DMXAddress::try_from("1.512").unwrap().universe_start()
+        );
+    }
+
+    #[test]
+    fn test_universe_end() {
+        assert_eq!(
+            DMXAddress {
+                universe: 3,
+                address: 512,
+                absolute: 1536
+            },
+            DMXAddress::try_from("3.234").unwrap().universe_end()
+        );
+    }
+
+    #[test]
+    fn test_universe_end_2() {
+        assert_eq!(
+            DMXAddress::try_from("63999.512").unwrap(),
+            DMXAddress::try_from("63999.1").unwrap().universe_end()
+        );
+    }
+
+    #[test]
+    fn test_universe_start_end_hand_built() {
+        let invalid = DMXAddress {
+            universe: 1,
+            address: 5,
+            absolute: 0,
+        };
+        assert_eq!(1, invalid.universe_start().absolute);
+        assert_eq!(512, invalid.universe_end().absolute);
+    }
+
+    #[test]
+    fn test_error_empty() {
+        assert!(matches!(
+            DMXAddress::try_from(""),
+            Err(DMXParseError::Empty)
+        ));
+    }
+
+    #[test]
+    fn test_error_not_a_number() {
+        assert!(matches!(
+            DMXAddress::try_from("something invalid"),
+            Err(DMXParseError::NotANumber { offset: 0, .. })
+        ));
+        assert!(matches!(
+            DMXAddress::try_from("2."),
+            Err(DMXParseError::NotANumber { offset: 2, .. })
+        ));
+        assert!(matches!(
+            DMXAddress::try_from("-1.3"),
+            Err(DMXParseError::NotANumber { offset: 0, .. })
+        ));
+        assert!(matches!(
+            DMXAddress::try_from("12.x3"),
+            Err(DMXParseError::NotANumber { offset: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn test_error_too_many_dots() {
+        assert!(matches!(
+            DMXAddress::try_from("1.2.3"),
+            Err(DMXParseError::TooManyDots { offset: 3 })
+        ));
+        assert!(matches!(
+            DMXAddress::try_from(".."),
+            Err(DMXParseError::TooManyDots { offset: 1 })
+        ));
+    }
+
+    #[test]
+    fn test_error_invalid_universe() {
+        assert!(matches!(
+            DMXAddress::try_from("0.1"),
+            Err(DMXParseError::InvalidUniverse(0))
+        ));
+        assert!(matches!(
+            DMXAddress::try_from("64000.1"),
+            Err(DMXParseError::InvalidUniverse(64_000))
+        ));
+    }
+
+    #[test]
+    fn test_error_invalid_address() {
+        assert!(matches!(
+            DMXAddress::try_from("2.0"),
+            Err(DMXParseError::InvalidAddress(0))
+        ));
+        assert!(matches!(
+            DMXAddress::try_from("63999.513"),
+            Err(DMXParseError::InvalidAddress(513))
+        ));
+    }
+
+    #[test]
+    fn test_error_invalid_absolute() {
+        assert!(matches!(
+            DMXAddress::try_from("0"),
+            Err(DMXParseError::InvalidAbsolute(0))
+        ));
+        assert!(matches!(
+            DMXAddress::try_from("32767489"),
+            Err(DMXParseError::InvalidAbsolute(32_767_489))
+        ));
+    }
+
+    #[test]
+    fn test_error_display() {
+        assert_eq!(
+            "universe 0 is out of range, it must be between 1 and 63999",
+            format!("{}", DMXParseError::InvalidUniverse(0))
+        );
+        assert_eq!(
+            "address 513 is out of range, it must be between 1 and 512",
+            format!("{}", DMXParseError::InvalidAddress(513))
+        );
+    }
+
+    #[test]
+    fn test_error_offset() {
+        assert_eq!(
+            Some(4),
+            DMXAddress::try_from("100.abc").unwrap_err().offset()
+        );
+        assert_eq!(None, DMXAddress::try_from("100.513").unwrap_err().offset());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_error_source() {
+        use std::error::Error;
+        use std::num::IntErrorKind;
+
+        let error =
+            DMXAddress::try_from("1.98981265123519681981681514984984984464984984").unwrap_err();
+        let source = error
+            .source()
+            .unwrap()
+            .downcast_ref::<std::num::ParseIntError>()
+            .unwrap();
+        assert_eq!(&IntErrorKind::PosOverflow, source.kind());
+        assert!(DMXAddress::try_from("0.1").unwrap_err().source().is_none());
+    }
+
+    #[test]
+    fn test_parse_bytes() {
+        assert_eq!(
+            DMXAddress {
+                universe: 4,
+                address: 465,
+                absolute: 2001
+            },
+            DMXAddress::parse_bytes(b"4.465").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_bytes_2() {
+        assert_eq!(
+            DMXAddress {
+                universe: 3,
+                address: 210,
+                absolute: 1234
+            },
+            DMXAddress::parse_bytes(b"1234").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_bytes_invalid() {
+        assert!(matches!(
+            DMXAddress::parse_bytes(b""),
+            Err(DMXParseError::Empty)
+        ));
+        assert!(matches!(
+            DMXAddress::parse_bytes(b"2.513"),
+            Err(DMXParseError::InvalidAddress(513))
+        ));
+        assert!(matches!(
+            DMXAddress::parse_bytes(b"1.2.3"),
+            Err(DMXParseError::TooManyDots { offset: 3 })
+        ));
+        assert!(matches!(
+            DMXAddress::parse_bytes(b"1.2a"),
+            Err(DMXParseError::NotANumber { offset: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_bytes_invalid_byte() {
+        assert!(matches!(
+            DMXAddress::parse_bytes(b"1.2\xff"),
+            Err(DMXParseError::InvalidByte { offset: 3 })
+        ));
+        assert!(matches!(
+            DMXAddress::parse_bytes(b"\xc3\x28"),
+            Err(DMXParseError::InvalidByte { offset: 0 })
+        ));
+    }
+
+    #[test]
+    fn test_parse_prefix() {
+        assert_eq!(
+            (DMXAddress::try_from("1.234").unwrap(), " range 12"),
+            DMXAddress::parse_prefix("1.234 range 12").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_prefix_2() {
+        assert_eq!(
+            (DMXAddress::try_from("1024").unwrap(), " @ 1.234"),
+            DMXAddress::parse_prefix("1024 @ 1.234").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_prefix_3() {
+        assert_eq!(
+            (DMXAddress::try_from("4.465").unwrap(), ""),
+            DMXAddress::parse_prefix("4.465").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_prefix_4() {
+        assert_eq!(
+            (DMXAddress::try_from("12").unwrap(), "."),
+            DMXAddress::parse_prefix("12.").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_prefix_5() {
+        assert_eq!(
+            (DMXAddress::try_from("2.3").unwrap(), ".4"),
+            DMXAddress::parse_prefix("2.3.4").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_prefix_invalid() {
+        assert!(matches!(
+            DMXAddress::parse_prefix(""),
+            Err(DMXParseError::Empty)
+        ));
+        assert!(matches!(
+            DMXAddress::parse_prefix("patch 1.1"),
+            Err(DMXParseError::NotANumber { offset: 0, .. })
+        ));
+        assert!(matches!(
+            DMXAddress::parse_prefix(" 1.1"),
+            Err(DMXParseError::NotANumber { offset: 0, .. })
+        ));
+        assert!(matches!(
+            DMXAddress::parse_prefix("1.513 range"),
+            Err(DMXParseError::InvalidAddress(513))
+        ));
+        assert!(matches!(
+            DMXAddress::parse_prefix("0 range"),
+            Err(DMXParseError::InvalidAbsolute(0))
+        ));
+    }
+
+    #[test]
+    fn test_parse_const() {
+        const ADDRESS: DMXAddress = DMXAddress::parse_const("1.234");
+        const ABSOLUTE: DMXAddress = DMXAddress::parse_const("1024");
+        assert_eq!(DMXAddress::try_from("1.234").unwrap(), ADDRESS);
+        assert_eq!(234, ADDRESS.address);
+        assert_eq!(DMXAddress::try_from("2.512").unwrap(), ABSOLUTE);
+        assert_eq!(2, ABSOLUTE.universe);
+        for value in &[
+            "1/1",
+            "1:512",
+            "63999.512",
+            "32767488",
+            "1",
+            "512",
+            "513",
+            "+1",
+            "1.+1",
+            "+2.+512",
+            "001.001",
+        ] {
+            let address = DMXAddress::try_from(*value).unwrap();
+            let parsed = DMXAddress::parse_const(value);
+            assert_eq!(address.universe, parsed.universe);
+            assert_eq!(address.address, parsed.address);
+            assert_eq!(address.absolute, parsed.absolute);
+        }
+    }
+
+    #[test]
+    fn test_parse_const_invalid() {
+        for value in &[
+            "",
+            "1.",
+            ".1",
+            "1.2.3",
+            "a",
+            "1.x",
+            "0.1",
+            "1.0",
+            "1.513",
+            "64000.1",
+            "0",
+            "32767489",
+            "99999999999",
+            "+",
+            "1.+",
+            "++1",
+            "-1",
+            "1+",
+        ] {
+            assert!(DMXAddress::try_from(*value).is_err());
+            assert!(
+                std::panic::catch_unwind(|| DMXAddress::parse_const(value)).is_err(),
+                "{} should not be parsed",
+                value
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_parse_many() {
+        let (addresses, errors) = DMXAddress::parse_many(vec!["1.1", "513", "63999.512"]);
+        assert_eq!(
+            vec![
+                DMXAddress::try_from("1.1").unwrap(),
+                DMXAddress::try_from("2.1").unwrap(),
+                DMXAddress::try_from("63999.512").unwrap()
+            ],
+            addresses
+        );
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_parse_many_errors() {
+        let values = ["", "1.1", "1.x", "0.1", "2.2"];
+        let (addresses, errors) = DMXAddress::parse_many(values.iter().copied());
+        assert_eq!(
+            vec![
+                DMXAddress::try_from("1.1").unwrap(),
+                DMXAddress::try_from("2.2").unwrap()
+            ],
+            addresses
+        );
+        assert_eq!(3, errors.len());
+        assert!(matches!(errors[0], (0, DMXParseError::Empty)));
+        assert!(matches!(
+            errors[1],
+            (2, DMXParseError::NotANumber { offset: 2, .. })
+        ));
+        assert!(matches!(errors[2], (3, DMXParseError::InvalidUniverse(0))));
+        let (addresses, errors) = DMXAddress::parse_many(Vec::new());
+        assert!(addresses.is_empty() && errors.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_parse_list() {
+        let (addresses, errors) = DMXAddress::parse_list("1.1, 1.17, 2.1 2.33");
+        assert_eq!(
+            vec![
+                DMXAddress::try_from("1.1").unwrap(),
+                DMXAddress::try_from("1.17").unwrap(),
+                DMXAddress::try_from("2.1").unwrap(),
+                DMXAddress::try_from("2.33").unwrap()
+            ],
+            addresses
+        );
+        assert!(errors.is_empty());
+        let (addresses, errors) = DMXAddress::parse_list(" ;1/1;;\t513\n,, ");
+        assert_eq!(
+            vec![
+                DMXAddress::try_from("1.1").unwrap(),
+                DMXAddress::try_from("2.1").unwrap()
+            ],
+            addresses
+        );
+        assert!(errors.is_empty());
+        let (addresses, errors) = DMXAddress::parse_list("");
+        assert!(addresses.is_empty() && errors.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_parse_list_errors() {
+        let (addresses, errors) = DMXAddress::parse_list("1.1, 1.x;0.1 1.2.3 2.2");
+        assert_eq!(
+            vec![
+                DMXAddress::try_from("1.1").unwrap(),
+                DMXAddress::try_from("2.2").unwrap()
+            ],
+            addresses
+        );
+        assert_eq!(3, errors.len());
+        assert!(matches!(
+            errors[0],
+            (5, DMXParseError::NotANumber { offset: 7, .. })
+        ));
+        assert!(matches!(errors[1], (9, DMXParseError::InvalidUniverse(0))));
+        assert!(matches!(
+            errors[2],
+            (13, DMXParseError::TooManyDots { offset: 16 })
+        ));
+    }
+
+    #[test]
+    fn test_try_from_lenient() {
+        assert_eq!(
+            DMXAddress::try_from("1.1").unwrap(),
+            DMXAddress::try_from_lenient(" 1.001 ").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_try_from_lenient_2() {
+        assert_eq!(
+            DMXAddress::try_from("1.5").unwrap(),
+            DMXAddress::try_from_lenient("01.05").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_try_from_lenient_3() {
+        assert_eq!(
+            DMXAddress::try_from("12.345").unwrap(),
+            DMXAddress::try_from_lenient("\t12 .  345\n").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_try_from_lenient_4() {
+        assert_eq!(
+            DMXAddress::try_from("1024").unwrap(),
+            DMXAddress::try_from_lenient(" +1024").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_try_from_lenient_comma() {
+        assert_eq!(
+            DMXAddress::try_from("1.234").unwrap(),
+            DMXAddress::try_from_lenient("1,234").unwrap()
+        );
+        assert_eq!(
+            DMXAddress::try_from("2.1").unwrap(),
+            DMXAddress::try_from_lenient(" U2 , A1 ").unwrap()
+        );
+        assert!(matches!(
+            DMXAddress::try_from("1,234"),
+            Err(DMXParseError::NotANumber { offset: 0, .. })
+        ));
+        assert!(matches!(
+            DMXAddress::try_from_lenient("1,2.3"),
+            Err(DMXParseError::TooManyDots { offset: 3 })
+        ));
+        assert!(matches!(
+            DMXAddress::try_from_lenient("1,513"),
+            Err(DMXParseError::InvalidAddress(513))
+        ));
+    }
+
+    #[test]
+    fn test_try_from_lenient_invalid() {
+        assert!(matches!(
+            DMXAddress::try_from_lenient("   "),
+            Err(DMXParseError::Empty)
+        ));
+        assert!(matches!(
+            DMXAddress::try_from_lenient(" 1 2.3"),
+            Err(DMXParseError::NotANumber { offset: 1, .. })
+        ));
+        assert!(matches!(
+            DMXAddress::try_from_lenient(" 1. x"),
+            Err(DMXParseError::NotANumber { offset: 4, .. })
+        ));
+        assert!(matches!(
+            DMXAddress::try_from_lenient("  1.2.3"),
+            Err(DMXParseError::TooManyDots { offset: 5 })
+        ));
+        assert!(matches!(
+            DMXAddress::try_from_lenient(" 1.513 "),
+            Err(DMXParseError::InvalidAddress(513))
+        ));
+        assert!(DMXAddress::try_from_lenient("something invalid").is_err());
+    }
+
+    #[test]
+    fn test_parse_with() {
+        let options = ParseOptions::new().format(AddressFormat::Separated);
+        assert_eq!(
+            DMXAddress::try_from("4.465").unwrap(),
+            DMXAddress::parse_with("4.465", &options).unwrap()
+        );
+        assert!(matches!(
+            DMXAddress::parse_with("2001", &options),
+            Err(DMXParseError::UnsupportedFormat(AddressFormat::Absolute))
+        ));
+    }
+
+    #[test]
+    fn test_parse_with_2() {
+        let options = ParseOptions::new().format(AddressFormat::Absolute);
+        assert_eq!(
+            DMXAddress::try_from("2001").unwrap(),
+            DMXAddress::parse_with("2001", &options).unwrap()
+        );
+        assert!(matches!(
+            DMXAddress::parse_with("4.465", &options),
+            Err(DMXParseError::UnsupportedFormat(AddressFormat::Separated))
+        ));
+    }
+
+    #[test]
+    fn test_parse_with_3() {
+        let options = ParseOptions::default();
+        assert_eq!(
+            DMXAddress::try_from("2001").unwrap(),
+            DMXAddress::parse_with("2001", &options).unwrap()
+        );
+        assert_eq!(
+            DMXAddress::try_from("4.465").unwrap(),
+            DMXAddress::parse_with("4.465", &options).unwrap()
+        );
+        assert!(DMXAddress::parse_with(" 4.465", &options).is_err());
+    }
+
+    #[test]
+    fn test_parse_with_4() {
+        let options = ParseOptions::new()
+            .format(AddressFormat::Separated)
+            .lenient(true);
+        assert_eq!(
+            DMXAddress::try_from("4.465").unwrap(),
+            DMXAddress::parse_with(" 4 . 465 ", &options).unwrap()
+        );
+        assert!(matches!(
+            DMXAddress::parse_with(" 2001 ", &options),
+            Err(DMXParseError::UnsupportedFormat(AddressFormat::Absolute))
+        ));
+    }
+
+    #[test]
+    fn test_display_alternate() {
+        assert_eq!(
+            format!(
+                "{:#}",
+                DMXAddress {
+                    universe: 4,
+                    address: 465,
+                    absolute: 2001
+                }
+            ),
+            "2001"
+        );
+    }
+
+    #[test]
+    fn test_display_alternate_2() {
+        assert_eq!(
+            format!(
+                "{:#}",
+                DMXAddress {
+                    universe: 1,
+                    address: 9,
+                    absolute: 9
+                }
+            ),
+            "9"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_to_absolute_string() {
+        assert_eq!(
+            "2001",
+            DMXAddress::try_from("4.465").unwrap().to_absolute_string()
+        );
+        assert_eq!(
+            "1",
+            DMXAddress::try_from("1.1").unwrap().to_absolute_string()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_to_unpadded_string() {
+        assert_eq!(
+            "1.1",
+            DMXAddress::try_from("1.1").unwrap().to_unpadded_string()
+        );
+        assert_eq!(
+            "12.34",
+            DMXAddress::try_from("12.34").unwrap().to_unpadded_string()
+        );
+        assert_eq!(
+            "63999.512",
+            DMXAddress::try_from("63999.512")
+                .unwrap()
+                .to_unpadded_string()
+        );
+    }
+
+    #[test]
+    fn test_hex() {
+        let address = DMXAddress::try_from("1.511").unwrap();
+        assert_eq!("1ff", format!("{:x}", address));
+        assert_eq!("1FF", format!("{:X}", address));
+        assert_eq!("0x1ff", format!("{:#x}", address));
+        assert_eq!("0x01FF", format!("{:#06X}", address));
+    }
+
+    #[test]
+    fn test_from_hex_str() {
+        assert_eq!(
+            DMXAddress::try_from("1.511").unwrap(),
+            DMXAddress::from_hex_str("0x1FF").unwrap()
+        );
+        assert_eq!(
+            DMXAddress::try_from("1.511").unwrap(),
+            DMXAddress::from_hex_str("1ff").unwrap()
+        );
+        assert_eq!(
+            DMXAddress::try_from("2001").unwrap(),
+            DMXAddress::from_hex_str("0X7d1").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_from_hex_str_invalid() {
+        assert!(matches!(
+            DMXAddress::from_hex_str(""),
+            Err(DMXParseError::Empty)
+        ));
+        assert!(matches!(
+            DMXAddress::from_hex_str("0x"),
+            Err(DMXParseError::NotANumber { offset: 2, .. })
+        ));
+        assert!(matches!(
+            DMXAddress::from_hex_str("0x1G"),
+            Err(DMXParseError::NotANumber { offset: 2, .. })
+        ));
+        assert!(matches!(
+            DMXAddress::from_hex_str("0x0"),
+            Err(DMXParseError::InvalidAbsolute(0))
+        ));
+        assert!(matches!(
+            DMXAddress::from_hex_str("0x1F40001"),
+            Err(DMXParseError::InvalidAbsolute(0x1F4_0001))
+        ));
+    }
+
+    #[test]
+    fn test_valid_slash() {
+        assert_eq!(
+            DMXAddress::try_from("1.234").unwrap(),
+            DMXAddress::try_from("1/234").unwrap()
+        );
+        assert_eq!(
+            DMXAddress {
+                universe: 4,
+                address: 512,
+                absolute: 2048
+            },
+            DMXAddress::try_from("4/512").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_invalid_slash() {
+        assert!(matches!(
+            DMXAddress::try_from("0/1"),
+            Err(DMXParseError::InvalidUniverse(0))
+        ));
+        assert!(matches!(
+            DMXAddress::try_from("1/513"),
+            Err(DMXParseError::InvalidAddress(513))
+        ));
+        assert!(matches!(
+            DMXAddress::try_from("1/2.3"),
+            Err(DMXParseError::TooManyDots { offset: 3 })
+        ));
+        assert!(matches!(
+            DMXAddress::try_from("1/"),
+            Err(DMXParseError::NotANumber { offset: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_slash_round_trip() {
+        let options = FormatOptions::new().separator('/');
+        for value in &["1.1", "1.512", "2.1", "4.465", "63999.512"] {
+            let address = DMXAddress::try_from(*value).unwrap();
+            let formatted = address.format_with(&options).to_string();
+            assert_eq!(address, DMXAddress::try_from(formatted.as_str()).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_parse_with_separators() {
+        let options = ParseOptions::new().separators(b".");
+        assert!(DMXAddress::parse_with("1.234", &options).is_ok());
+        assert!(matches!(
+            DMXAddress::parse_with("1/234", &options),
+            Err(DMXParseError::InvalidSeparator {
+                offset: 1,
+                separator: '/'
+            })
+        ));
+    }
+
+    #[test]
+    fn test_parse_prefix_slash() {
+        assert_eq!(
+            (DMXAddress::try_from("1.234").unwrap(), " range 12"),
+            DMXAddress::parse_prefix("1/234 range 12").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_valid_colon() {
+        assert_eq!(
+            DMXAddress::try_from("1.234").unwrap(),
+            DMXAddress::try_from("1:234").unwrap()
+        );
+        assert_eq!(
+            DMXAddress {
+                universe: 5,
+                address: 1,
+                absolute: 2049
+            },
+            DMXAddress::try_from("5:1").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_invalid_colon() {
+        assert!(matches!(
+            DMXAddress::try_from("0:1"),
+            Err(DMXParseError::InvalidUniverse(0))
+        ));
+        assert!(matches!(
+            DMXAddress::try_from("2:0"),
+            Err(DMXParseError::InvalidAddress(0))
+        ));
+        assert!(matches!(
+            DMXAddress::try_from("1:2:3"),
+            Err(DMXParseError::TooManyDots { offset: 3 })
+        ));
+        assert!(matches!(
+            DMXAddress::try_from(":1"),
+            Err(DMXParseError::NotANumber { offset: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_colon_round_trip() {
+        let options = FormatOptions::new().separator(':').address_width(0);
+        for value in &["1.1", "1.512", "2.1", "4.465", "63999.512"] {
+            let address = DMXAddress::try_from(*value).unwrap();
+            let formatted = address.format_with(&options).to_string();
+            assert_eq!(address, DMXAddress::try_from(formatted.as_str()).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_parse_prefix_colon() {
+        assert_eq!(
+            (DMXAddress::try_from("3.17").unwrap(), ""),
+            DMXAddress::parse_prefix("3:17").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_try_from_lenient_prefixed() {
+        assert_eq!(
+            DMXAddress::try_from("1.234").unwrap(),
+            DMXAddress::try_from_lenient("U1.234").unwrap()
+        );
+        assert_eq!(
+            DMXAddress::try_from("1.512").unwrap(),
+            DMXAddress::try_from_lenient("u001.512").unwrap()
+        );
+        assert_eq!(
+            DMXAddress::try_from("1.234").unwrap(),
+            DMXAddress::try_from_lenient("U1 A234").unwrap()
+        );
+        assert_eq!(
+            DMXAddress::try_from("12.34").unwrap(),
+            DMXAddress::try_from_lenient(" u12a34 ").unwrap()
+        );
+        assert_eq!(
+            DMXAddress::try_from("3.4").unwrap(),
+            DMXAddress::try_from_lenient("U3/A4").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_try_from_lenient_verbose() {
+        assert_eq!(
+            DMXAddress::try_from("1.234").unwrap(),
+            DMXAddress::try_from_lenient("Universe 1 Address 234").unwrap()
+        );
+        assert_eq!(
+            DMXAddress::try_from("12.5").unwrap(),
+            DMXAddress::try_from_lenient("  UNIVERSE12   channel\t5 ").unwrap()
+        );
+        assert_eq!(
+            DMXAddress::try_from("2.1").unwrap(),
+            DMXAddress::try_from_lenient("universe 2, address 1").unwrap()
+        );
+        assert_eq!(
+            DMXAddress::try_from("3.4").unwrap(),
+            DMXAddress::try_from_lenient("Universe 3 A 4").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_try_from_lenient_verbose_invalid() {
+        assert!(matches!(
+            DMXAddress::try_from_lenient("Universe 0 Address 1"),
+            Err(DMXParseError::InvalidUniverse(0))
+        ));
+        assert!(matches!(
+            DMXAddress::try_from_lenient("Universe 1 Channel 513"),
+            Err(DMXParseError::InvalidAddress(513))
+        ));
+        assert!(matches!(
+            DMXAddress::try_from_lenient("Universe 1 Address"),
+            Err(DMXParseError::NotANumber { offset: 18, .. })
+        ));
+        assert!(matches!(
+            DMXAddress::try_from_lenient("Universe 1"),
+            Err(DMXParseError::NotANumber { offset: 0, .. })
+        ));
+        assert!(matches!(
+            DMXAddress::try_from_lenient("Universe x Address 1"),
+            Err(DMXParseError::NotANumber { offset: 9, .. })
+        ));
+        assert!(DMXAddress::try_from("Universe 1 Address 234").is_err());
+    }
+
+    #[test]
+    fn test_try_from_lenient_prefixed_invalid() {
+        assert!(matches!(
+            DMXAddress::try_from_lenient("U0.1"),
+            Err(DMXParseError::InvalidUniverse(0))
+        ));
+        assert!(matches!(
+            DMXAddress::try_from_lenient("U1 A513"),
+            Err(DMXParseError::InvalidAddress(513))
+        ));
+        assert!(matches!(
+            DMXAddress::try_from_lenient("U1"),
+            Err(DMXParseError::NotANumber { offset: 0, .. })
+        ));
+        assert!(matches!(
+            DMXAddress::try_from_lenient("X1.1"),
+            Err(DMXParseError::NotANumber { offset: 0, .. })
+        ));
+        assert!(matches!(
+            DMXAddress::try_from_lenient("U1 A"),
+            Err(DMXParseError::NotANumber { offset: 4, .. })
+        ));
+        assert!(DMXAddress::try_from("U1.234").is_err());
+        assert!(DMXAddress::try_from("U1 A234").is_err());
+    }
+
+    #[test]
+    fn test_from_zero_based() {
+        assert_eq!(
+            DMXAddress {
+                universe: 1,
+                address: 1,
+                absolute: 1
+            },
+            DMXAddress::from_zero_based(0, 0).unwrap()
+        );
+        assert_eq!(
+            DMXAddress {
+                universe: 2,
+                address: 512,
+                absolute: 1024
+            },
+            DMXAddress::from_zero_based(1, 511).unwrap()
+        );
+        assert_eq!(
+            DMXAddress {
+                universe: 63_999,
+                address: 512,
+                absolute: 32_767_488
+            },
+            DMXAddress::from_zero_based(63_998, 511).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_from_zero_based_invalid() {
+        assert!(matches!(
+            DMXAddress::from_zero_based(0, 512),
+            Err(DMXParseError::InvalidAddress(513))
+        ));
+        assert!(matches!(
+            DMXAddress::from_zero_based(63_999, 0),
+            Err(DMXParseError::InvalidUniverse(64_000))
+        ));
+        assert!(matches!(
+            DMXAddress::from_zero_based(u16::MAX, u16::MAX),
+            Err(DMXParseError::InvalidUniverse(65_536))
+        ));
+    }
+
+    #[test]
+    fn test_to_zero_based() {
+        assert_eq!((0, 0), DMXAddress::try_from("1.1").unwrap().to_zero_based());
+        assert_eq!(
+            (1, 233),
+            DMXAddress::try_from("2.234").unwrap().to_zero_based()
+        );
+        assert_eq!(
+            (63_998, 511),
+            DMXAddress::try_from("63999.512").unwrap().to_zero_based()
+        );
+        let invalid = DMXAddress {
+            universe: 0,
+            address: 0,
+            absolute: 0,
+        };
+        assert_eq!((0, 0), invalid.to_zero_based());
+    }
+
+    #[test]
+    fn test_parse_zero_based() {
+        let options = ParseOptions::new().zero_based(true);
+        assert_eq!(
+            DMXAddress {
+                universe: 1,
+                address: 1,
+                absolute: 1
+            },
+            DMXAddress::parse_with("0.0", &options).unwrap()
+        );
+        assert_eq!(
+            DMXAddress {
+                universe: 1,
+                address: 512,
+                absolute: 512
+            },
+            DMXAddress::parse_with("0.511", &options).unwrap()
+        );
+        assert_eq!(
+            DMXAddress {
+                universe: 2,
+                address: 1,
+                absolute: 513
+            },
+            DMXAddress::parse_with("512", &options).unwrap()
+        );
+        assert_eq!(
+            DMXAddress {
+                universe: 1,
+                address: 1,
+                absolute: 1
+            },
+            DMXAddress::parse_with(
+                " U0 A0 ",
+                &ParseOptions::new().zero_based(true).lenient(true)
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_zero_based_invalid() {
+        let options = ParseOptions::new().zero_based(true);
+        assert!(matches!(
+            DMXAddress::parse_with("0.512", &options),
+            Err(DMXParseError::InvalidAddress(513))
+        ));
+        assert!(matches!(
+            DMXAddress::parse_with("63999.0", &options),
+            Err(DMXParseError::InvalidUniverse(64_000))
+        ));
+        assert!(matches!(
+            DMXAddress::parse_with("32767488", &options),
+            Err(DMXParseError::InvalidAbsolute(32_767_489))
+        ));
+        assert!(matches!(
+            DMXAddress::parse_with("4294967295", &options),
+            Err(DMXParseError::InvalidAbsolute(u32::MAX))
+        ));
+    }
+
+    #[test]
+    fn test_eq_str() {
+        let address = DMXAddress::try_from("1.234").unwrap();
+        assert!(address == "1.234");
+        assert!(address == "1/234");
+        assert!(address == "234");
+        assert!("1.234" == address);
+        assert!(address != "1.235");
+        assert!(address != "1.2.3");
+        assert!("" != address);
+    }
+
+    #[test]
+    fn test_eq_u32() {
+        let address = DMXAddress::try_from("4.1").unwrap();
+        assert!(address == 1537u32);
+        assert!(1537u32 == address);
+        assert!(address != 1538u32);
+        assert!(0u32 != address);
+    }
+
+    #[test]
+    fn test_cmp_u32() {
+        let address = DMXAddress::try_from("2.1").unwrap();
+        assert!((513..=1024).contains(&address));
+        assert!(address > 512 && address < 514);
+        assert!(!(address > 513));
+        assert!(512 < address && 1024 >= address);
+        assert_eq!(Some(Ordering::Less), address.partial_cmp(&u32::MAX));
+        assert_eq!(Some(Ordering::Less), 0.partial_cmp(&address));
+    }
+
+    #[test]
+    fn test_to_string_round_trip() {
+        for absolute in (1..=32_767_488)
+            .step_by(4099)
+            .chain([511, 512, 513, 32_767_488])
+        {
+            let address = DMXAddress::try_from(absolute.to_string().as_str()).unwrap();
+            let canonical = address.to_string();
+            assert_eq!(address, DMXAddress::try_from(canonical.as_str()).unwrap());
+            assert_eq!(
+                canonical,
+                DMXAddress::try_from(canonical.as_str())
+                    .unwrap()
+                    .to_string()
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_into_string() {
+        assert_eq!("1.001", String::from(DMXAddress::try_from("1.1").unwrap()));
+        let canonical: String = DMXAddress::try_from("63999/512").unwrap().into();
+        assert_eq!("63999.512", canonical);
+    }
+
+    #[test]
+    fn test_display_alignment() {
+        let address = DMXAddress::try_from("1.1").unwrap();
+        assert_eq!("1.001    ", format!("{:9}", address));
+        assert_eq!("    1.001", format!("{:>9}", address));
+        assert_eq!("  1.001  ", format!("{:^9}", address));
+        assert_eq!("1.001----", format!("{:-<9}", address));
+        assert_eq!("1.001", format!("{:>3}", address));
+        assert_eq!(
+            "   63999.512",
+            format!("{:>12}", DMXAddress::try_from("63999.512").unwrap())
+        );
+        assert_eq!(
+            "  513",
+            format!("{:>#5}", DMXAddress::try_from("2.1").unwrap())
+        );
+        assert_eq!(
+            "513__",
+            format!("{:_<#5}", DMXAddress::try_from("2.1").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_display_universe_padding() {
+        assert_eq!(
+            "00001.001",
+            format!("{:09}", DMXAddress::try_from("1.1").unwrap())
+        );
+        assert_eq!(
+            "63999.001",
+            format!("{:09}", DMXAddress::try_from("63999.1").unwrap())
+        );
+        assert_eq!(
+            "00012.345",
+            format!(
+                "{:0width$}",
+                DMXAddress::try_from("12.345").unwrap(),
+                width = 9
+            )
+        );
+        assert_eq!(
+            "1.001",
+            format!("{:05}", DMXAddress::try_from("1.1").unwrap())
+        );
+        assert_eq!(
+            "12345.001",
+            format!("{:05}", DMXAddress::try_from("12345.1").unwrap())
+        );
+        assert_eq!(
+            "000001024",
+            format!("{:#09}", DMXAddress::try_from("2.512").unwrap())
+        );
+        assert_eq!(
+            "1.001",
+            format!("{:0}", DMXAddress::try_from("1.1").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_fine_address() {
+        assert_eq!(
+            DMXAddress {
+                universe: 1,
+                address: 2,
+                absolute: 2
+            },
+            DMXAddress::try_from("1.1").unwrap().fine_address().unwrap()
+        );
+        assert_eq!(
+            DMXAddress {
+                universe: 2,
+                address: 1,
+                absolute: 513
+            },
+            DMXAddress::try_from("1.512")
+                .unwrap()
+                .fine_address()
+                .unwrap()
+        );
+        assert!(DMXAddress::try_from("63999.512")
+            .unwrap()
+            .fine_address()
+            .is_none());
+    }
+
+    #[test]
+    fn test_byte_address() {
+        let address = DMXAddress::try_from("1.510").unwrap();
+        assert_eq!(address, address.byte_address(0).unwrap());
+        assert_eq!(
+            DMXAddress::try_from("1.512").unwrap(),
+            address.byte_address(2).unwrap()
+        );
+        assert_eq!(
+            DMXAddress::try_from("2.001").unwrap(),
+            address.byte_address(3).unwrap()
+        );
+        assert!(DMXAddress::try_from("63999.510")
+            .unwrap()
+            .byte_address(3)
+            .is_none());
+    }
 }
@@ -0,0 +1,62 @@
+//!Holds the macro `dmx!` that creates dmx addresses at compile time
+
+///Creates a `DMXAddress` that is validated at compile time. Accepts a string literal in any notation of `DMXAddress::parse_const` (eg. `dmx!("1.234")` or `dmx!("1024")`) or universe and address as integers (eg. `dmx!(1, 234)`). Invalid addresses fail the build
+///
+///```rust
+/// use dmx_struct::{dmx, DMXAddress};
+///
+/// assert_eq!(dmx!("1.234"), dmx!(1, 234));
+/// assert_eq!(2001, dmx!(4, 465).absolute);
+/// ```
+///
+///```compile_fail
+/// use dmx_struct::dmx;
+///
+/// let address = dmx!(1, 513);
+/// ```
+#[macro_export]
+macro_rules! dmx {
+    ($value:literal) => {{
+        const ADDRESS: $crate::DMXAddress = $crate::DMXAddress::parse_const($value);
+        ADDRESS
+    }};
+    ($universe:expr, $address:expr $(,)?) => {{
+        const ADDRESS: $crate::DMXAddress = $crate::DMXAddress::new_const($universe, $address);
+        ADDRESS
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use crate::DMXAddress;
+
+    #[test]
+    fn test_dmx_str() {
+        assert_eq!(DMXAddress::try_from("1.234").unwrap(), dmx!("1.234"));
+        assert_eq!(DMXAddress::try_from("2.1").unwrap(), dmx!("513"));
+        assert_eq!(DMXAddress::try_from("3.4").unwrap(), dmx!("3/4"));
+    }
+
+    #[test]
+    fn test_dmx_numbers() {
+        assert_eq!(DMXAddress::try_from("1.234").unwrap(), dmx!(1, 234));
+        assert_eq!(DMXAddress::try_from("63999.512").unwrap(), dmx!(63999, 512));
+        assert_eq!(DMXAddress::try_from("1.1").unwrap(), dmx!(1, 1,));
+        const UNIVERSE: u16 = 2;
+        assert_eq!(DMXAddress::try_from("2.100").unwrap(), dmx!(UNIVERSE, 100));
+    }
+
+    #[test]
+    fn test_new_const_invalid() {
+        for (universe, address) in &[(0, 1), (64000, 1), (1, 0), (1, 513)] {
+            assert!(
+                std::panic::catch_unwind(|| DMXAddress::new_const(*universe, *address)).is_err(),
+                "{}.{} should not be created",
+                universe,
+                address
+            );
+        }
+    }
+}
@@ -0,0 +1,423 @@
+//!Holds a map from dmx addresses to arbitrary data that stores each universe either sparse or dense. Only available with the feature `std`
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+
+use crate::DMXAddress;
+
+///A universe with more values than this is stored dense
+const DENSE_THRESHOLD: usize = 64;
+
+///A dense universe with less values than this is stored sparse again. Lower than `DENSE_THRESHOLD`, so a universe does not switch the storage on every insert and remove
+const SPARSE_THRESHOLD: usize = DENSE_THRESHOLD / 2;
+
+///Map from dmx addresses to values, eg. fixture ids, labels or levels. Universes with few values are stored as ordered map, universes with many values as array of 512 slots. Values are iterated in ascending order of the addresses
+///
+///```rust
+/// use std::convert::TryFrom;
+///
+/// use dmx_struct::{DMXAddress, UniverseMap};
+///
+/// let mut labels = UniverseMap::new();
+/// labels.insert(DMXAddress::try_from("1.1").unwrap(), "dimmer");
+/// labels.insert(DMXAddress::try_from("2.1").unwrap(), "spot");
+/// assert_eq!(Some(&"spot"), labels.get(&DMXAddress::try_from("513").unwrap()));
+/// let first: Vec<_> = labels
+///     .range(&DMXAddress::try_from("1.1").unwrap(), &DMXAddress::try_from("1.512").unwrap())
+///     .map(|(_, label)| *label)
+///     .collect();
+/// assert_eq!(vec!["dimmer"], first);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct UniverseMap<T> {
+    universes: BTreeMap<u16, Slots<T>>,
+    len: usize,
+}
+
+///Values of one universe by the address in the universe
+#[derive(Debug, Clone, PartialEq)]
+enum Slots<T> {
+    Sparse(BTreeMap<u16, T>),
+    //Holds 512 slots, the value of address 1 is at index 0
+    Dense(Vec<Option<T>>),
+}
+
+impl<T> UniverseMap<T> {
+    ///Creates an empty map
+    pub fn new() -> Self {
+        UniverseMap {
+            universes: BTreeMap::new(),
+            len: 0,
+        }
+    }
+
+    ///Inserts the value at the address. Returns the value that was at the address before. Addresses outside of 1-512 are never inserted and the value is returned
+    pub fn insert(&mut self, address: DMXAddress, value: T) -> Option<T> {
+        if !is_valid(&address) {
+            return Some(value);
+        }
+        let slots = self
+            .universes
+            .entry(address.universe)
+            .or_insert_with(|| Slots::Sparse(BTreeMap::new()));
+        let previous = match slots {
+            Slots::Sparse(values) => values.insert(address.address, value),
+            Slots::Dense(values) => values[usize::from(address.address - 1)].replace(value),
+        };
+        if previous.is_none() {
+            self.len += 1;
+            slots.switch_to_dense();
+        }
+        previous
+    }
+
+    ///Removes the value at the address and returns it
+    pub fn remove(&mut self, address: &DMXAddress) -> Option<T> {
+        if !is_valid(address) {
+            return None;
+        }
+        let slots = self.universes.get_mut(&address.universe)?;
+        let removed = match slots {
+            Slots::Sparse(values) => values.remove(&address.address),
+            Slots::Dense(values) => values[usize::from(address.address - 1)].take(),
+        }?;
+        self.len -= 1;
+        if slots.len() == 0 {
+            self.universes.remove(&address.universe);
+        } else {
+            slots.switch_to_sparse();
+        }
+        Some(removed)
+    }
+
+    ///Returns the value at the address
+    pub fn get(&self, address: &DMXAddress) -> Option<&T> {
+        if !is_valid(address) {
+            return None;
+        }
+        match self.universes.get(&address.universe)? {
+            Slots::Sparse(values) => values.get(&address.address),
+            Slots::Dense(values) => values[usize::from(address.address - 1)].as_ref(),
+        }
+    }
+
+    ///Returns the value at the address to change it
+    pub fn get_mut(&mut self, address: &DMXAddress) -> Option<&mut T> {
+        if !is_valid(address) {
+            return None;
+        }
+        match self.universes.get_mut(&address.universe)? {
+            Slots::Sparse(values) => values.get_mut(&address.address),
+            Slots::Dense(values) => values[usize::from(address.address - 1)].as_mut(),
+        }
+    }
+
+    ///Returns true if there is a value at the address
+    pub fn contains_key(&self, address: &DMXAddress) -> bool {
+        self.get(address).is_some()
+    }
+
+    ///Returns the number of values in the map
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    ///Returns true if the map does not hold any value
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    ///Returns all addresses with their value in ascending order of the addresses
+    pub fn iter(&self) -> impl Iterator<Item = (DMXAddress, &T)> {
+        self.universes.iter().flat_map(|(universe, slots)| {
+            let universe = *universe;
+            slots
+                .range(1, DMXAddress::ADDRESSES_PER_UNIVERSE)
+                .map(move |(address, value)| (to_address(universe, address), value))
+        })
+    }
+
+    ///Returns the addresses from start to end (inclusive) with their value in ascending order of the addresses. The range may span several universes
+    pub fn range<'a>(
+        &'a self,
+        start: &DMXAddress,
+        end: &DMXAddress,
+    ) -> impl Iterator<Item = (DMXAddress, &'a T)> {
+        let (start, end) = (*start, *end);
+        //A range of universes that ends before it starts would panic
+        let last = end.universe.max(start.universe);
+        self.universes
+            .range(start.universe..=last)
+            .flat_map(move |(universe, slots)| {
+                let universe = *universe;
+                let from = if universe == start.universe {
+                    start.address
+                } else {
+                    1
+                };
+                let to = if universe == end.universe {
+                    end.address
+                } else {
+                    DMXAddress::ADDRESSES_PER_UNIVERSE
+                };
+                slots
+                    .range(from, to)
+                    .map(move |(address, value)| (to_address(universe, address), value))
+            })
+            .filter(move |(address, _)| {
+                address.absolute >= start.absolute && address.absolute <= end.absolute
+            })
+    }
+}
+
+impl<T> Slots<T> {
+    ///Returns the number of values in the universe
+    fn len(&self) -> usize {
+        match self {
+            Slots::Sparse(values) => values.len(),
+            Slots::Dense(values) => values.iter().filter(|value| value.is_some()).count(),
+        }
+    }
+
+    ///Returns the values from address from to address to (inclusive) in ascending order. Returns nothing if from is greater than to
+    fn range(&self, from: u16, to: u16) -> SlotsIter<'_, T> {
+        let to = to.min(DMXAddress::ADDRESSES_PER_UNIVERSE);
+        if from == 0 || from > to {
+            return SlotsIter::Dense([].iter().enumerate(), 0);
+        }
+        match self {
+            Slots::Sparse(values) => SlotsIter::Sparse(values.range(from..=to)),
+            Slots::Dense(values) => SlotsIter::Dense(
+                values[usize::from(from - 1)..usize::from(to)]
+                    .iter()
+                    .enumerate(),
+                from,
+            ),
+        }
+    }
+
+    ///Stores the values in an array of 512 slots if there are more than `DENSE_THRESHOLD`
+    fn switch_to_dense(&mut self) {
+        if let Slots::Sparse(values) = self {
+            if values.len() > DENSE_THRESHOLD {
+                let mut dense: Vec<Option<T>> = (0..DMXAddress::ADDRESSES_PER_UNIVERSE)
+                    .map(|_| None)
+                    .collect();
+                for (address, value) in std::mem::take(values) {
+                    dense[usize::from(address - 1)] = Some(value);
+                }
+                *self = Slots::Dense(dense);
+            }
+        }
+    }
+
+    ///Stores the values in a map if there are less than `SPARSE_THRESHOLD`
+    fn switch_to_sparse(&mut self) {
+        if let Slots::Dense(values) = self {
+            if values.iter().filter(|value| value.is_some()).count() < SPARSE_THRESHOLD {
+                let sparse = std::mem::take(values)
+                    .into_iter()
+                    .zip(1..)
+                    .filter_map(|(value, address)| value.map(|value| (address, value)))
+                    .collect();
+                *self = Slots::Sparse(sparse);
+            }
+        }
+    }
+}
+
+///Iterator over the values of one universe together with their address in the universe
+enum SlotsIter<'a, T> {
+    Sparse(std::collections::btree_map::Range<'a, u16, T>),
+    //The second field is the address of the first slot
+    Dense(std::iter::Enumerate<std::slice::Iter<'a, Option<T>>>, u16),
+}
+
+impl<'a, T> Iterator for SlotsIter<'a, T> {
+    type Item = (u16, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            SlotsIter::Sparse(values) => values.next().map(|(address, value)| (*address, value)),
+            SlotsIter::Dense(values, first) => values.find_map(|(index, value)| {
+                value.as_ref().map(|value| (*first + index as u16, value))
+            }),
+        }
+    }
+}
+
+///Returns true if the address can be stored in the slots of its universe
+fn is_valid(address: &DMXAddress) -> bool {
+    address.address >= 1 && address.address <= DMXAddress::ADDRESSES_PER_UNIVERSE
+}
+
+///Creates the dmx address of a slot of a universe
+fn to_address(universe: u16, address: u16) -> DMXAddress {
+    DMXAddress {
+        universe,
+        address,
+        absolute: u32::from(address)
+            + (u32::from(universe).saturating_sub(1))
+                * u32::from(DMXAddress::ADDRESSES_PER_UNIVERSE),
+    }
+}
+
+impl<T> Default for UniverseMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> FromIterator<(DMXAddress, T)> for UniverseMap<T> {
+    fn from_iter<I: IntoIterator<Item = (DMXAddress, T)>>(iter: I) -> Self {
+        let mut map = UniverseMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<T> Extend<(DMXAddress, T)> for UniverseMap<T> {
+    fn extend<I: IntoIterator<Item = (DMXAddress, T)>>(&mut self, iter: I) {
+        for (address, value) in iter {
+            self.insert(address, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use crate::test_helpers::address;
+    use crate::{DMXAddress, UniverseMap};
+
+    use super::{Slots, DENSE_THRESHOLD};
+
+    fn is_dense<T>(map: &UniverseMap<T>, universe: u16) -> bool {
+        matches!(map.universes.get(&universe), Some(Slots::Dense(_)))
+    }
+
+    #[test]
+    fn test_insert_get_remove() {
+        let mut map = UniverseMap::new();
+        assert!(map.is_empty());
+        assert_eq!(None, map.insert(address("1.1"), "a"));
+        assert_eq!(Some("a"), map.insert(address("1.1"), "b"));
+        assert_eq!(None, map.insert(address("3.512"), "c"));
+        assert_eq!(2, map.len());
+        assert_eq!(Some(&"b"), map.get(&address("1.1")));
+        assert_eq!(None, map.get(&address("1.2")));
+        assert_eq!(None, map.get(&address("2.1")));
+        *map.get_mut(&address("3.512")).unwrap() = "d";
+        assert!(map.contains_key(&address("3.512")));
+        assert_eq!(Some("d"), map.remove(&address("3.512")));
+        assert_eq!(None, map.remove(&address("3.512")));
+        assert_eq!(1, map.len());
+        assert_eq!(1, map.universes.len());
+    }
+
+    #[test]
+    fn test_insert_invalid() {
+        let mut map = UniverseMap::new();
+        let invalid = DMXAddress {
+            universe: 1,
+            address: 513,
+            absolute: 513,
+        };
+        assert_eq!(Some(1), map.insert(invalid, 1));
+        assert_eq!(None, map.get(&invalid));
+        assert_eq!(None, map.remove(&invalid));
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn test_dense() {
+        let mut map: UniverseMap<u32> = (1..=512)
+            .map(|absolute| {
+                (
+                    DMXAddress::try_from(absolute.to_string().as_str()).unwrap(),
+                    absolute,
+                )
+            })
+            .collect();
+        assert!(is_dense(&map, 1));
+        assert_eq!(512, map.len());
+        assert_eq!(Some(&100), map.get(&address("1.100")));
+        let values: Vec<u32> = map.iter().map(|(_, value)| *value).collect();
+        assert_eq!((1..=512).collect::<Vec<u32>>(), values);
+        for absolute in 1..=500 {
+            map.remove(&DMXAddress::try_from(absolute.to_string().as_str()).unwrap());
+        }
+        assert!(!is_dense(&map, 1));
+        assert_eq!(12, map.len());
+        assert_eq!(Some((address("1.501"), &501)), map.iter().next());
+    }
+
+    #[test]
+    fn test_dense_threshold() {
+        let mut map = UniverseMap::new();
+        for index in 1..=DENSE_THRESHOLD as u16 {
+            map.insert(address(&format!("2.{}", index * 2)), index);
+        }
+        assert!(!is_dense(&map, 2));
+        map.insert(address("2.1"), 0);
+        assert!(is_dense(&map, 2));
+        assert_eq!(Some(&0), map.get(&address("2.1")));
+        assert_eq!(Some(&3), map.get(&address("2.6")));
+    }
+
+    #[test]
+    fn test_iter() {
+        let map: UniverseMap<&str> = vec![
+            (address("2.1"), "c"),
+            (address("1.512"), "b"),
+            (address("1.1"), "a"),
+        ]
+        .into_iter()
+        .collect();
+        let entries: Vec<(DMXAddress, &&str)> = map.iter().collect();
+        assert_eq!(
+            vec![
+                (address("1.1"), &"a"),
+                (address("1.512"), &"b"),
+                (address("2.1"), &"c")
+            ],
+            entries
+        );
+        assert_eq!(513, entries[2].0.absolute);
+    }
+
+    #[test]
+    fn test_range() {
+        let mut map = UniverseMap::new();
+        for value in &["1.1", "1.10", "1.512", "2.5", "3.1", "4.1"] {
+            map.insert(address(value), *value);
+        }
+        let range = |start: &str, end: &str| -> Vec<&str> {
+            map.range(&address(start), &address(end))
+                .map(|(_, value)| *value)
+                .collect()
+        };
+        assert_eq!(vec!["1.10", "1.512", "2.5", "3.1"], range("1.2", "3.1"));
+        assert_eq!(vec!["1.1"], range("1.1", "1.1"));
+        assert_eq!(Vec::<&str>::new(), range("1.11", "1.511"));
+        assert_eq!(Vec::<&str>::new(), range("3.1", "1.1"));
+        assert_eq!(vec!["2.5"], range("2.1", "2.512"));
+    }
+
+    #[test]
+    fn test_range_dense() {
+        let map: UniverseMap<u32> = (1..=600)
+            .map(|absolute| {
+                (
+                    DMXAddress::try_from(absolute.to_string().as_str()).unwrap(),
+                    absolute,
+                )
+            })
+            .collect();
+        let values: Vec<u32> = map
+            .range(&address("1.510"), &address("2.3"))
+            .map(|(_, value)| *value)
+            .collect();
+        assert_eq!(vec![510, 511, 512, 513, 514, 515], values);
+    }
+}
@@ -0,0 +1,143 @@
+//!Holds the helpers to read and write the `Address` node of MVR scene files, eg. `<Address break="0">1537</Address>`. MVR counts the DMX breaks from 0 while GDTF and `GdtfDmxAddress` count them from 1
+use core::convert::TryFrom;
+use core::fmt::{Display, Formatter};
+
+use crate::gdtf::{parse_address, parse_number};
+use crate::{GdtfDmxAddress, GdtfError};
+
+impl GdtfDmxAddress {
+    ///Parses the content of the MVR node `Address` and its attribute `break`. The break is counted from 0 and defaults to 0 if the attribute is missing. The content is an absolute dmx address or of format 'universe.address'. A break that is out of range returns `InvalidBreak` with the break counted from 1
+    ///
+    ///```rust
+    /// use dmx_struct::GdtfDmxAddress;
+    ///
+    /// let address = GdtfDmxAddress::from_mvr(Some("1"), "1537").unwrap();
+    /// assert_eq!(2, address.dmx_break);
+    /// assert_eq!("4.001", address.address.to_string());
+    /// assert_eq!("<Address break=\"1\">1537</Address>", address.to_mvr().unwrap().to_string());
+    /// ```
+    pub fn from_mvr(dmx_break: Option<&str>, address: &str) -> Result<Self, GdtfError> {
+        let dmx_break = match dmx_break {
+            Some("") => return Err(GdtfError::Empty),
+            Some(dmx_break) => parse_number(dmx_break, 0)?,
+            None => 0,
+        };
+        let dmx_break = dmx_break.saturating_add(1);
+        match u8::try_from(dmx_break) {
+            Ok(dmx_break) => Self::new(dmx_break, parse_address(address)?),
+            Err(_) => Err(GdtfError::InvalidBreak(dmx_break)),
+        }
+    }
+
+    ///Returns the break counted from 0 as written to the attribute `break` of MVR. Returns None if the break was set to 0 by hand
+    pub fn mvr_break(&self) -> Option<u8> {
+        self.dmx_break.checked_sub(1)
+    }
+
+    ///Returns the address as MVR node `Address` to be used with {}, eg. `<Address break="0">1537</Address>`. Returns `InvalidBreak` if the break was set to 0 by hand
+    pub fn to_mvr(&self) -> Result<MvrAddress, GdtfError> {
+        match self.mvr_break() {
+            Some(_) => Ok(MvrAddress(*self)),
+            None => Err(GdtfError::InvalidBreak(0)),
+        }
+    }
+}
+
+///The MVR node `Address` of a `GdtfDmxAddress`. Returned by `GdtfDmxAddress::to_mvr` and used with {}. The address is written as absolute dmx address
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MvrAddress(GdtfDmxAddress);
+
+impl Display for MvrAddress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "<Address break=\"{}\">{}</Address>",
+            //The break is checked by GdtfDmxAddress::to_mvr
+            self.0.mvr_break().unwrap_or(0),
+            self.0.address.absolute
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use crate::{DMXAddress, DMXParseError, GdtfDmxAddress, GdtfError};
+
+    fn gdtf(dmx_break: u8, address: &str) -> GdtfDmxAddress {
+        GdtfDmxAddress::new(dmx_break, DMXAddress::try_from(address).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_from_mvr() {
+        assert_eq!(
+            gdtf(1, "4.1"),
+            GdtfDmxAddress::from_mvr(Some("0"), "1537").unwrap()
+        );
+        assert_eq!(
+            gdtf(1, "4.1"),
+            GdtfDmxAddress::from_mvr(None, "1537").unwrap()
+        );
+        assert_eq!(
+            gdtf(3, "1.1"),
+            GdtfDmxAddress::from_mvr(Some("2"), "1.1").unwrap()
+        );
+        assert_eq!(
+            gdtf(255, "1.1"),
+            GdtfDmxAddress::from_mvr(Some("254"), "1").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_from_mvr_invalid() {
+        assert!(matches!(
+            GdtfDmxAddress::from_mvr(Some("255"), "1"),
+            Err(GdtfError::InvalidBreak(256))
+        ));
+        assert!(matches!(
+            GdtfDmxAddress::from_mvr(Some(""), "1"),
+            Err(GdtfError::Empty)
+        ));
+        assert!(matches!(
+            GdtfDmxAddress::from_mvr(Some("a"), "1"),
+            Err(GdtfError::NotANumber { offset: 0, .. })
+        ));
+        assert!(matches!(
+            GdtfDmxAddress::from_mvr(Some("4294967295"), "1"),
+            Err(GdtfError::InvalidBreak(4294967295))
+        ));
+        assert!(matches!(
+            GdtfDmxAddress::from_mvr(None, "0"),
+            Err(GdtfError::InvalidAddress(DMXParseError::InvalidAbsolute(0)))
+        ));
+    }
+
+    #[test]
+    fn test_to_mvr() {
+        assert_eq!(Some(0), gdtf(1, "1.1").mvr_break());
+        assert_eq!(
+            "<Address break=\"0\">1537</Address>",
+            gdtf(1, "4.1").to_mvr().unwrap().to_string()
+        );
+        assert_eq!(
+            "<Address break=\"254\">1</Address>",
+            gdtf(255, "1.1").to_mvr().unwrap().to_string()
+        );
+        let address = gdtf(2, "63999.512");
+        assert_eq!(
+            address,
+            GdtfDmxAddress::from_mvr(Some("1"), &address.address.absolute.to_string()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_to_mvr_break_0() {
+        let address = GdtfDmxAddress {
+            dmx_break: 0,
+            address: DMXAddress::try_from("1.1").unwrap(),
+        };
+        assert_eq!(None, address.mvr_break());
+        assert!(matches!(address.to_mvr(), Err(GdtfError::InvalidBreak(0))));
+    }
+}
@@ -0,0 +1,353 @@
+//!Maps dmx addresses to OSC paths and back, eg. to expose each address as /dmx/1/234
+use core::fmt::{Display, Formatter};
+
+use crate::parse::parse_number;
+use crate::{DMXAddress, DMXParseError};
+
+///Template of an OSC path with the placeholders {universe} and {address} or {absolute}, eg. /dmx/{universe}/{address} or /channel/{absolute}. The template is validated when it is created
+///
+///```rust
+/// use std::convert::TryFrom;
+///
+/// use dmx_struct::{DMXAddress, OscPathTemplate};
+///
+/// let template = OscPathTemplate::new("/dmx/{universe}/{address}").unwrap();
+/// let address = DMXAddress::try_from("1.234").unwrap();
+/// assert_eq!("/dmx/1/234", template.format(&address).to_string());
+/// assert_eq!(address, template.parse("/dmx/1/234").unwrap());
+/// assert!(template.parse("/dmx/1").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OscPathTemplate<'a> {
+    template: &'a str,
+    absolute: bool,
+}
+
+///Error returned if an OSC path template is not valid or a path does not match the template. Offsets are byte offsets in the template or the path
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum OscPathError {
+    ///The template contains a placeholder other than {universe}, {address} and {absolute} or a { that is not closed
+    UnknownPlaceholder {
+        ///Byte offset of the placeholder in the template
+        offset: usize,
+    },
+    ///The template contains a placeholder twice or {absolute} together with {universe} or {address}
+    DuplicatePlaceholder {
+        ///Byte offset of the placeholder in the template
+        offset: usize,
+    },
+    ///The template needs either {absolute} or both {universe} and {address}
+    MissingPlaceholder,
+    ///The path does not match the text of the template
+    Mismatch {
+        ///Byte offset of the first byte of the path that does not match
+        offset: usize,
+    },
+    ///A number of the path is not valid or the address it describes is out of range
+    InvalidAddress(DMXParseError),
+}
+
+impl Display for OscPathError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            OscPathError::UnknownPlaceholder { offset } => write!(
+                f,
+                "the placeholder at position {} must be {{universe}}, {{address}} or {{absolute}}",
+                offset
+            ),
+            OscPathError::DuplicatePlaceholder { offset } => write!(
+                f,
+                "the placeholder at position {} is already used by the template",
+                offset
+            ),
+            OscPathError::MissingPlaceholder => write!(
+                f,
+                "the template needs {{absolute}} or both {{universe}} and {{address}}"
+            ),
+            OscPathError::Mismatch { offset } => write!(
+                f,
+                "the path does not match the template at position {}",
+                offset
+            ),
+            OscPathError::InvalidAddress(error) => write!(f, "invalid address: {}", error),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for OscPathError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            OscPathError::InvalidAddress(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+///Part of a template
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Piece<'a> {
+    Text(&'a str),
+    Universe,
+    Address,
+    Absolute,
+}
+
+impl<'a> OscPathTemplate<'a> {
+    ///Creates the template. Returns an error if it contains unknown or duplicate placeholders or does not describe a dmx address
+    pub fn new(template: &'a str) -> Result<Self, OscPathError> {
+        let (mut universe, mut address, mut absolute) = (false, false, false);
+        for piece in pieces(template) {
+            let (offset, piece) = piece?;
+            let seen = match piece {
+                Piece::Text(_) => continue,
+                Piece::Universe => &mut universe,
+                Piece::Address => &mut address,
+                Piece::Absolute => &mut absolute,
+            };
+            if *seen {
+                return Err(OscPathError::DuplicatePlaceholder { offset });
+            }
+            *seen = true;
+            if absolute && (universe || address) {
+                return Err(OscPathError::DuplicatePlaceholder { offset });
+            }
+        }
+        if !(absolute || (universe && address)) {
+            return Err(OscPathError::MissingPlaceholder);
+        }
+        Ok(OscPathTemplate { template, absolute })
+    }
+
+    ///Returns the template
+    pub fn template(&self) -> &'a str {
+        self.template
+    }
+
+    ///Returns the path of the address, which can be used in format with {}
+    pub fn format(&self, address: &DMXAddress) -> FormattedOscPath<'a> {
+        FormattedOscPath {
+            template: *self,
+            address: *address,
+        }
+    }
+
+    ///Parses a path that matches the template into a dmx address. Numbers may not have a sign or leading whitespace. Returns `Mismatch` if the text of the path differs from the template and `InvalidAddress` if a number is not valid or out of range
+    pub fn parse(&self, path: &str) -> Result<DMXAddress, OscPathError> {
+        let bytes = path.as_bytes();
+        let (mut universe, mut address, mut absolute) = (0, 0, 0);
+        let mut position = 0;
+        //The template was validated when it was created, so all pieces are valid
+        for (_, piece) in pieces(self.template).flatten() {
+            let number = match piece {
+                Piece::Text(text) => {
+                    let matching = bytes[position..]
+                        .iter()
+                        .zip(text.as_bytes())
+                        .take_while(|(a, b)| a == b)
+                        .count();
+                    if matching < text.len() {
+                        return Err(OscPathError::Mismatch {
+                            offset: position + matching,
+                        });
+                    }
+                    position += text.len();
+                    continue;
+                }
+                Piece::Universe => &mut universe,
+                Piece::Address => &mut address,
+                Piece::Absolute => &mut absolute,
+            };
+            let end = bytes[position..]
+                .iter()
+                .position(|byte| !byte.is_ascii_digit())
+                .map_or(bytes.len(), |end| position + end);
+            *number = parse_number(&bytes[position..end], position)
+                .map_err(OscPathError::InvalidAddress)?;
+            position = end;
+        }
+        if position < bytes.len() {
+            return Err(OscPathError::Mismatch { offset: position });
+        }
+        if self.absolute {
+            DMXAddress::from_absolute(absolute)
+        } else {
+            DMXAddress::from_parts(universe, address)
+        }
+        .map_err(OscPathError::InvalidAddress)
+    }
+}
+
+///An OSC path of a dmx address. Returned by `OscPathTemplate::format` and used with {}
+#[derive(Debug, Clone, Copy)]
+pub struct FormattedOscPath<'a> {
+    template: OscPathTemplate<'a>,
+    address: DMXAddress,
+}
+
+impl Display for FormattedOscPath<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        for (_, piece) in pieces(self.template.template).flatten() {
+            match piece {
+                Piece::Text(text) => write!(f, "{}", text)?,
+                Piece::Universe => write!(f, "{}", self.address.universe)?,
+                Piece::Address => write!(f, "{}", self.address.address)?,
+                Piece::Absolute => write!(f, "{}", self.address.absolute)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+///Splits the template into text and placeholders together with their byte offset
+fn pieces(template: &str) -> impl Iterator<Item = Result<(usize, Piece<'_>), OscPathError>> {
+    let mut position = 0;
+    core::iter::from_fn(move || {
+        let rest = &template[position..];
+        if rest.is_empty() {
+            return None;
+        }
+        let offset = position;
+        if !rest.starts_with('{') {
+            let end = rest.find('{').unwrap_or(rest.len());
+            position += end;
+            return Some(Ok((offset, Piece::Text(&rest[..end]))));
+        }
+        let piece = match rest.find('}').map(|end| &rest[..=end]) {
+            Some("{universe}") => Piece::Universe,
+            Some("{address}") => Piece::Address,
+            Some("{absolute}") => Piece::Absolute,
+            _ => {
+                //The template is invalid, so the iteration stops
+                position = template.len();
+                return Some(Err(OscPathError::UnknownPlaceholder { offset }));
+            }
+        };
+        position += rest.find('}').map_or(rest.len(), |end| end + 1);
+        Some(Ok((offset, piece)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_helpers::address;
+    use crate::{DMXParseError, OscPathError, OscPathTemplate};
+
+    #[test]
+    fn test_new() {
+        assert!(OscPathTemplate::new("/dmx/{universe}/{address}").is_ok());
+        assert!(OscPathTemplate::new("/{absolute}").is_ok());
+        assert!(OscPathTemplate::new("{address}@{universe}").is_ok());
+        assert_eq!(
+            "/{absolute}",
+            OscPathTemplate::new("/{absolute}").unwrap().template()
+        );
+    }
+
+    #[test]
+    fn test_new_invalid() {
+        assert!(matches!(
+            OscPathTemplate::new("/dmx/{universe}/{channel}"),
+            Err(OscPathError::UnknownPlaceholder { offset: 16 })
+        ));
+        assert!(matches!(
+            OscPathTemplate::new("/dmx/{universe"),
+            Err(OscPathError::UnknownPlaceholder { offset: 5 })
+        ));
+        assert!(matches!(
+            OscPathTemplate::new("/{address}/{universe}/{address}"),
+            Err(OscPathError::DuplicatePlaceholder { offset: 22 })
+        ));
+        assert!(matches!(
+            OscPathTemplate::new("/{absolute}/{address}"),
+            Err(OscPathError::DuplicatePlaceholder { offset: 12 })
+        ));
+        assert!(matches!(
+            OscPathTemplate::new("/dmx/{universe}"),
+            Err(OscPathError::MissingPlaceholder)
+        ));
+        assert!(matches!(
+            OscPathTemplate::new(""),
+            Err(OscPathError::MissingPlaceholder)
+        ));
+    }
+
+    #[test]
+    fn test_format() {
+        let template = OscPathTemplate::new("/dmx/{universe}/{address}").unwrap();
+        assert_eq!("/dmx/2/1", template.format(&address("2.1")).to_string());
+        let template = OscPathTemplate::new("/ch/{absolute}/level").unwrap();
+        assert_eq!(
+            "/ch/513/level",
+            template.format(&address("2.1")).to_string()
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        let template = OscPathTemplate::new("/dmx/{universe}/{address}").unwrap();
+        assert_eq!(address("1.234"), template.parse("/dmx/1/234").unwrap());
+        assert_eq!(
+            address("63999.512"),
+            template.parse("/dmx/63999/512").unwrap()
+        );
+        let template = OscPathTemplate::new("/ch/{absolute}/level").unwrap();
+        assert_eq!(address("2.1"), template.parse("/ch/513/level").unwrap());
+        let template = OscPathTemplate::new("{address}@{universe}").unwrap();
+        assert_eq!(address("3.10"), template.parse("10@3").unwrap());
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        let template = OscPathTemplate::new("/dmx/{universe}/{address}").unwrap();
+        assert!(matches!(
+            template.parse("/dmy/1/234"),
+            Err(OscPathError::Mismatch { offset: 3 })
+        ));
+        assert!(matches!(
+            template.parse("/dmx/1"),
+            Err(OscPathError::Mismatch { offset: 6 })
+        ));
+        assert!(matches!(
+            template.parse("/dmx/1/234/level"),
+            Err(OscPathError::Mismatch { offset: 10 })
+        ));
+        assert!(matches!(
+            template.parse("/dmx/a/234"),
+            Err(OscPathError::InvalidAddress(DMXParseError::NotANumber {
+                offset: 5,
+                ..
+            }))
+        ));
+        assert!(matches!(
+            template.parse("/dmx/1/"),
+            Err(OscPathError::InvalidAddress(DMXParseError::NotANumber {
+                offset: 7,
+                ..
+            }))
+        ));
+        assert!(matches!(
+            template.parse("/dmx/1/513"),
+            Err(OscPathError::InvalidAddress(DMXParseError::InvalidAddress(
+                513
+            )))
+        ));
+        assert!(matches!(
+            template.parse("/dmx/0/1"),
+            Err(OscPathError::InvalidAddress(
+                DMXParseError::InvalidUniverse(0)
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let template = OscPathTemplate::new("/dmx/{universe}/{address}").unwrap();
+        for value in &["1.1", "1.512", "2.1", "63999.512"] {
+            let address = address(value);
+            let path = template.format(&address).to_string();
+            assert_eq!(address, template.parse(&path).unwrap());
+        }
+    }
+}
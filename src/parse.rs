@@ -0,0 +1,384 @@
+//!Holds the parser for dmx addresses. The parser works on the borrowed input only and never allocates
+use core::str::FromStr;
+
+use crate::{DMXAddress, DMXParseError, Protocol};
+
+///Separators between universe and address that are accepted by default. 1.234 is the common notation, 1/234 is used by ETC Eos and Hog and 1:234 by OLA and many node configuration web interfaces
+pub const DEFAULT_SEPARATORS: &[u8] = b"./:";
+
+///Notations of a dmx address that are accepted when parsing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressFormat {
+    ///Accepts 'universe.address' (eg. 1.234) and absolute dmx addresses (eg. 1024)
+    Any,
+    ///Accepts only 'universe.address' (eg. 1.234 or 1/234)
+    Separated,
+    ///Accepts only absolute dmx addresses (eg. 1024)
+    Absolute,
+}
+
+///Options to control which input is accepted when parsing a dmx address with `DMXAddress::parse_with`
+///
+///```rust
+/// use dmx_struct::{AddressFormat, DMXAddress, ParseOptions};
+///
+/// let options = ParseOptions::new().format(AddressFormat::Separated);
+/// assert!(DMXAddress::parse_with("1.234", &options).is_ok());
+/// assert!(DMXAddress::parse_with("1024", &options).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseOptions {
+    format: AddressFormat,
+    lenient: bool,
+    separators: &'static [u8],
+    zero_based: bool,
+    protocol: Protocol,
+}
+
+impl ParseOptions {
+    ///Returns the default options that are also used by `TryFrom<&str>`. All formats and the `DEFAULT_SEPARATORS` are accepted and the input must not contain whitespace
+    pub const fn new() -> Self {
+        ParseOptions {
+            format: AddressFormat::Any,
+            lenient: false,
+            separators: DEFAULT_SEPARATORS,
+            zero_based: false,
+            protocol: Protocol::Sacn,
+        }
+    }
+
+    ///Sets which notations of a dmx address are accepted
+    pub const fn format(mut self, format: AddressFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    ///If lenient is true, whitespace around the input and around universe and address is ignored. Universe and address may also be prefixed with U or Universe and A, Address or Channel (case-insensitive), eg. U1.234, U1 A234 or Universe 1 Channel 234. A comma is accepted between universe and address as well, eg. 1,234 as exported by some European spreadsheets
+    pub const fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    ///Sets the ASCII characters that are accepted between universe and address, eg. b"." to only accept the canonical notation 1.234
+    pub const fn separators(mut self, separators: &'static [u8]) -> Self {
+        self.separators = separators;
+        self
+    }
+
+    ///If zero_based is true, universe and address are counted from 0 as shown by Art-Net nodes, eg. 0.0 is parsed as 1.001 and 0.511 as 1.512. Absolute dmx addresses are counted from 0 as well
+    pub const fn zero_based(mut self, zero_based: bool) -> Self {
+        self.zero_based = zero_based;
+        self
+    }
+
+    ///Sets the protocol that limits the highest universe, eg. `Protocol::ArtNet` only accepts the universes 1-32768. Defaults to `Protocol::Sacn`
+    pub const fn protocol(mut self, protocol: Protocol) -> Self {
+        self.protocol = protocol;
+        self
+    }
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+///Parses a dmx address of format 'universe.address' (eg. 1.234) or an absolute dmx address (eg. 1024)
+pub(crate) fn parse(value: &str) -> Result<DMXAddress, DMXParseError> {
+    parse_bytes(value.as_bytes())
+}
+
+///Parses a dmx address of format 'universe.address' (eg. 1.234) or an absolute dmx address (eg. 1024) from ASCII bytes
+pub(crate) fn parse_bytes(value: &[u8]) -> Result<DMXAddress, DMXParseError> {
+    parse_with(value, &ParseOptions::new())
+}
+
+///Parses a dmx address of format 'universe.address' or an absolute dmx address. Which input is accepted is controlled by the options
+pub(crate) fn parse_with(
+    value: &[u8],
+    options: &ParseOptions,
+) -> Result<DMXAddress, DMXParseError> {
+    //Start is the position of value in the original input, so errors always report offsets of the original input
+    let (value, start) = if options.lenient {
+        trim(value, 0)
+    } else {
+        (value, 0)
+    };
+    if value.is_empty() {
+        return Err(DMXParseError::Empty);
+    }
+    //Zero-based numbers are shifted to the one-based numbers of DMXAddress before validating them
+    let shift = u32::from(options.zero_based);
+    //Searching the input for the separator in one pass. Only one separator allowed. The comma is only accepted in lenient mode, so it does not conflict with lists of addresses
+    let mut separator = None;
+    for (offset, byte) in value.iter().enumerate() {
+        if options.separators.contains(byte) || (options.lenient && *byte == b',') {
+            if separator.is_some() {
+                return Err(DMXParseError::TooManyDots {
+                    offset: start + offset,
+                });
+            }
+            separator = Some(offset);
+        }
+    }
+    //End of the universe and start of the address in the input
+    let split = match separator {
+        Some(separator) => Some((separator, separator + 1)),
+        //In lenient mode universe and address can also be told apart by the prefix of the address, eg. U1 A234 or Universe 1 Channel 234
+        None if options.lenient => (0..value.len())
+            .find(|&position| {
+                ADDRESS_PREFIXES
+                    .iter()
+                    .any(|prefix| starts_with_ignore_case(&value[position..], prefix))
+            })
+            .map(|prefix| (prefix, prefix)),
+        None => None,
+    };
+    //A known separator that is not accepted by the options, eg. 1.234 for the notation 1/234 of Hog
+    if split.is_none() {
+        if let Some(offset) = value
+            .iter()
+            .position(|byte| DEFAULT_SEPARATORS.contains(byte))
+        {
+            return Err(DMXParseError::InvalidSeparator {
+                offset: start + offset,
+                separator: char::from(value[offset]),
+            });
+        }
+    }
+    match split {
+        //The input is of format 1.234. Value before the separator is universe, value after the separator is address
+        Some((universe_end, address_start)) => {
+            if options.format == AddressFormat::Absolute {
+                return Err(DMXParseError::UnsupportedFormat(AddressFormat::Separated));
+            }
+            let (universe, universe_offset) =
+                component(&value[..universe_end], start, UNIVERSE_PREFIXES, options);
+            let (address, address_offset) = component(
+                &value[address_start..],
+                start + address_start,
+                ADDRESS_PREFIXES,
+                options,
+            );
+            DMXAddress::from_parts_in(
+                parse_number(universe, universe_offset)?.saturating_add(shift),
+                parse_number(address, address_offset)?.saturating_add(shift),
+                options.protocol,
+            )
+        }
+        //The input holds the absolute address
+        None => {
+            if options.format == AddressFormat::Separated {
+                return Err(DMXParseError::UnsupportedFormat(AddressFormat::Absolute));
+            }
+            let (absolute, absolute_offset) = component(value, start, &[], options);
+            DMXAddress::from_absolute_in(
+                parse_number(absolute, absolute_offset)?.saturating_add(shift),
+                options.protocol,
+            )
+        }
+    }
+}
+
+///Prefixes of the universe that are accepted in lenient mode. Longer prefixes come first so they are removed completely
+const UNIVERSE_PREFIXES: &[&[u8]] = &[b"universe", b"u"];
+
+///Prefixes of the address that are accepted in lenient mode. Longer prefixes come first so they are removed completely
+const ADDRESS_PREFIXES: &[&[u8]] = &[b"address", b"channel", b"a"];
+
+///Returns the part of the input that holds a number together with its offset in the whole input. In lenient mode whitespace and one of the optional prefixes (case-insensitive) are removed
+fn component<'a>(
+    value: &'a [u8],
+    offset: usize,
+    prefixes: &[&[u8]],
+    options: &ParseOptions,
+) -> (&'a [u8], usize) {
+    if !options.lenient {
+        return (value, offset);
+    }
+    let (value, offset) = trim(value, offset);
+    match prefixes
+        .iter()
+        .find(|prefix| starts_with_ignore_case(value, prefix))
+    {
+        Some(prefix) => trim(&value[prefix.len()..], offset + prefix.len()),
+        None => (value, offset),
+    }
+}
+
+///Returns true if the value starts with the prefix, ignoring the ASCII case
+fn starts_with_ignore_case(value: &[u8], prefix: &[u8]) -> bool {
+    value
+        .get(..prefix.len())
+        .map_or(false, |start| start.eq_ignore_ascii_case(prefix))
+}
+
+///Parses a dmx address of format 'universe.address' (eg. 1.234) or an absolute dmx address (eg. 1024) in const context. Accepts the same input as `parse` but panics instead of returning an error, so invalid input fails the build if it is evaluated at compile time
+pub(crate) const fn parse_const(value: &str) -> DMXAddress {
+    let value = value.as_bytes();
+    if value.is_empty() {
+        panic!("the dmx address is empty");
+    }
+    //Searching the input for the separator. Only one separator allowed
+    let mut separator = None;
+    let mut offset = 0;
+    while offset < value.len() {
+        if is_default_separator(value[offset]) {
+            if separator.is_some() {
+                panic!("the dmx address must not contain more than one separator between universe and address");
+            }
+            separator = Some(offset);
+        }
+        offset += 1;
+    }
+    match separator {
+        Some(separator) => from_parts_const(
+            parse_number_const(value, 0, separator),
+            parse_number_const(value, separator + 1, value.len()),
+        ),
+        None => {
+            let absolute = parse_number_const(value, 0, value.len());
+            if absolute == 0 || absolute > DMXAddress::MAX_ABSOLUTE {
+                panic!(
+                    "the absolute dmx address is out of range, it must be between 1 and DMXAddress::MAX_ABSOLUTE"
+                );
+            }
+            DMXAddress {
+                universe: ((absolute - 1) / ADDRESSES_PER_UNIVERSE + 1) as u16,
+                address: ((absolute - 1) % ADDRESSES_PER_UNIVERSE + 1) as u16,
+                absolute,
+            }
+        }
+    }
+}
+
+///Number of addresses in a universe as u32 for the calculations in const context
+const ADDRESSES_PER_UNIVERSE: u32 = DMXAddress::ADDRESSES_PER_UNIVERSE as u32;
+
+///Creates a dmx address from universe and address in const context. Panics if one of them is out of range
+pub(crate) const fn from_parts_const(universe: u32, address: u32) -> DMXAddress {
+    if universe == 0 || universe > DMXAddress::MAX_UNIVERSE as u32 {
+        panic!("the universe of the dmx address is out of range, it must be between 1 and DMXAddress::MAX_UNIVERSE");
+    }
+    if address == 0 || address > ADDRESSES_PER_UNIVERSE {
+        panic!("the address of the dmx address is out of range, it must be between 1 and DMXAddress::ADDRESSES_PER_UNIVERSE");
+    }
+    //Checked, so the absolute address can never overflow even if the limits change
+    let absolute = match (universe - 1).checked_mul(ADDRESSES_PER_UNIVERSE) {
+        Some(start) => start.checked_add(address),
+        None => None,
+    };
+    match absolute {
+        Some(absolute) => DMXAddress {
+            universe: universe as u16,
+            address: address as u16,
+            absolute,
+        },
+        None => panic!("the absolute dmx address is out of range"),
+    }
+}
+
+///Returns true if the byte is one of the `DEFAULT_SEPARATORS`
+const fn is_default_separator(byte: u8) -> bool {
+    let mut i = 0;
+    while i < DEFAULT_SEPARATORS.len() {
+        if DEFAULT_SEPARATORS[i] == byte {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+///Parses the number between start and end of the input in const context. Accepts one leading '+' like `u32::from_str`. Numbers that do not fit into u32 saturate, so they are rejected as out of range
+const fn parse_number_const(value: &[u8], start: usize, end: usize) -> u32 {
+    let start = if start < end && value[start] == b'+' {
+        start + 1
+    } else {
+        start
+    };
+    if start == end {
+        panic!("the dmx address must be a positive number or of format 'universe.address'");
+    }
+    let mut number: u32 = 0;
+    let mut offset = start;
+    while offset < end {
+        let byte = value[offset];
+        if !byte.is_ascii_digit() {
+            panic!("the dmx address must be a positive number or of format 'universe.address'");
+        }
+        number = number
+            .saturating_mul(10)
+            .saturating_add((byte - b'0') as u32);
+        offset += 1;
+    }
+    number
+}
+
+///Parses an absolute dmx address in hexadecimal notation with an optional prefix 0x, eg. 0x1FF or 1ff
+pub(crate) fn parse_hex(value: &str) -> Result<DMXAddress, DMXParseError> {
+    if value.is_empty() {
+        return Err(DMXParseError::Empty);
+    }
+    let (digits, offset) = match value.get(..2) {
+        Some("0x") | Some("0X") => (&value[2..], 2),
+        _ => (value, 0),
+    };
+    let absolute = u32::from_str_radix(digits, 16)
+        .map_err(|source| DMXParseError::NotANumber { offset, source })?;
+    DMXAddress::from_absolute(absolute)
+}
+
+///Parses a dmx address at the start of the input and returns it with the rest of the input that was not consumed. The address ends at the first character that is not part of a number or the separator
+pub(crate) fn parse_prefix(value: &str) -> Result<(DMXAddress, &str), DMXParseError> {
+    if value.is_empty() {
+        return Err(DMXParseError::Empty);
+    }
+    let bytes = value.as_bytes();
+    let universe_end = digits_end(bytes, 0);
+    //The input is only of format 1.234 if the separator is followed by a digit. Otherwise the separator belongs to the rest
+    if matches!(bytes.get(universe_end), Some(byte) if DEFAULT_SEPARATORS.contains(byte))
+        && matches!(bytes.get(universe_end + 1), Some(byte) if byte.is_ascii_digit())
+    {
+        let address_end = digits_end(bytes, universe_end + 1);
+        let address = DMXAddress::from_parts(
+            parse_number(&bytes[..universe_end], 0)?,
+            parse_number(&bytes[universe_end + 1..address_end], universe_end + 1)?,
+        )?;
+        Ok((address, &value[address_end..]))
+    } else {
+        let address = DMXAddress::from_absolute(parse_number(&bytes[..universe_end], 0)?)?;
+        Ok((address, &value[universe_end..]))
+    }
+}
+
+///Returns the position of the first byte from start on that is not an ASCII digit
+fn digits_end(value: &[u8], start: usize) -> usize {
+    value[start..]
+        .iter()
+        .position(|byte| !byte.is_ascii_digit())
+        .map_or(value.len(), |end| start + end)
+}
+
+///Parses one number of the input. The offset is the position of the number in the whole input and is returned in the error
+pub(crate) fn parse_number(value: &[u8], offset: usize) -> Result<u32, DMXParseError> {
+    //A number only consists of ASCII characters, so anything that is not valid text can be rejected right away
+    let value = core::str::from_utf8(value).map_err(|e| DMXParseError::InvalidByte {
+        offset: offset + e.valid_up_to(),
+    })?;
+    u32::from_str(value).map_err(|source| DMXParseError::NotANumber { offset, source })
+}
+
+///Removes ASCII whitespace at the start and the end of the value. Returns the trimmed value and its new offset in the whole input
+fn trim(value: &[u8], offset: usize) -> (&[u8], usize) {
+    let start = value
+        .iter()
+        .position(|byte| !byte.is_ascii_whitespace())
+        .unwrap_or(value.len());
+    let end = value
+        .iter()
+        .rposition(|byte| !byte.is_ascii_whitespace())
+        .map_or(start, |end| end + 1);
+    (&value[start..end], offset + start)
+}
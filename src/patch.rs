@@ -0,0 +1,482 @@
+//!Holds the patch that assigns footprints to fixtures and keeps them from overlapping. Only available with the feature `std`
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+
+use crate::{BoundaryPolicy, DMXAddress, DMXFootprint, DMXFootprintError};
+
+///Collection of fixtures and their footprints. The id of a fixture can be any ordered type, eg. a fixture number or a name. Footprints of different fixtures never overlap
+///
+///```rust
+/// use std::convert::TryFrom;
+///
+/// use dmx_struct::{DMXAddress, DMXFootprint, DMXPatch};
+///
+/// let mut patch = DMXPatch::new();
+/// patch.insert(101, DMXFootprint::new(DMXAddress::try_from("1.1").unwrap(), 30).unwrap()).unwrap();
+/// let collision = patch.insert(102, DMXFootprint::new(DMXAddress::try_from("1.21").unwrap(), 16).unwrap());
+/// assert_eq!(101, collision.unwrap_err().id);
+/// assert_eq!(Some(&101), patch.at(&DMXAddress::try_from("1.30").unwrap()).map(|(id, _)| id));
+/// ```
+///
+///# Serde
+///
+///With the feature `serde` the patch is (de)serialized with a stable schema. The version of the schema is 1, the policy is optional and defaults to "allow". The start is serialized like `DMXAddress`. Entries are sorted by their start and validated on deserialization
+///
+///```json
+///{
+///  "version": 1,
+///  "policy": "allow",
+///  "entries": [
+///    { "fixture": 101, "start": "1.001", "width": 30 },
+///    { "fixture": 102, "start": "1.031", "width": 16 }
+///  ]
+///}
+///```
+///
+///The policy is one of "reject", "wrap_to_next_universe" or "allow"
+#[derive(Debug, Clone, PartialEq)]
+pub struct DMXPatch<K> {
+    entries: BTreeMap<K, DMXFootprint>,
+    //Id of the fixture by the absolute start address of its footprint to look up fixtures by address
+    starts: BTreeMap<u32, K>,
+    policy: BoundaryPolicy,
+}
+
+///Returned if a footprint can not be inserted into a `DMXPatch` because it overlaps the footprint of another fixture
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatchCollision<K> {
+    ///The id of the fixture that is already patched
+    pub id: K,
+    ///The footprint of the fixture that is already patched
+    pub footprint: DMXFootprint,
+}
+
+impl<K: Ord + Clone> DMXPatch<K> {
+    ///Creates an empty patch. Footprints found by `next_free` may continue in the next universe (`BoundaryPolicy::Allow`)
+    pub fn new() -> Self {
+        Self::with_policy(BoundaryPolicy::Allow)
+    }
+
+    ///Creates an empty patch with the policy that is used by `next_free` for footprints that do not fit into the rest of a universe
+    pub fn with_policy(policy: BoundaryPolicy) -> Self {
+        DMXPatch {
+            entries: BTreeMap::new(),
+            starts: BTreeMap::new(),
+            policy,
+        }
+    }
+
+    ///Returns the policy that is used by `next_free` for footprints that do not fit into the rest of a universe
+    pub fn policy(&self) -> BoundaryPolicy {
+        self.policy
+    }
+
+    ///Returns the first address at or after start where a footprint with the width does not overlap any patched fixture. With `BoundaryPolicy::Reject` and `BoundaryPolicy::WrapToNextUniverse` the footprint never crosses the end of a universe. Returns None if there is no free block until the last valid dmx address
+    ///
+    ///```rust
+    /// use std::convert::TryFrom;
+    ///
+    /// use dmx_struct::{BoundaryPolicy, DMXAddress, DMXFootprint, DMXPatch};
+    ///
+    /// let mut patch = DMXPatch::with_policy(BoundaryPolicy::WrapToNextUniverse);
+    /// patch.insert(1, DMXFootprint::new(DMXAddress::try_from("1.1").unwrap(), 500).unwrap()).unwrap();
+    /// let free = patch.next_free(DMXAddress::try_from("1.1").unwrap(), 30).unwrap();
+    /// assert_eq!("2.001", free.to_string());
+    /// ```
+    pub fn next_free(&self, start: DMXAddress, width: u16) -> Option<DMXAddress> {
+        let mut candidate = start;
+        loop {
+            let footprint = match DMXFootprint::with_policy(candidate, width, self.policy) {
+                Ok(footprint) => footprint,
+                //With Reject the search continues in the next universe, like it does with WrapToNextUniverse
+                Err(DMXFootprintError::CrossesUniverse { .. })
+                    if width <= DMXAddress::ADDRESSES_PER_UNIVERSE =>
+                {
+                    DMXFootprint::with_policy(candidate, width, BoundaryPolicy::WrapToNextUniverse)
+                        .ok()?
+                }
+                Err(_) => return None,
+            };
+            //Continuing after the last fixture that is in the way
+            let blocking_end = self
+                .collisions(&footprint)
+                .filter_map(|(_, other)| other.end())
+                .map(|end| end.absolute)
+                .max();
+            match blocking_end {
+                None => return Some(footprint.start),
+                Some(end) => candidate = DMXAddress::from_absolute(end + 1).ok()?,
+            }
+        }
+    }
+
+    ///Inserts the footprint of a fixture. If the fixture is already patched, its footprint is replaced and the old footprint is returned. Returns the first fixture whose footprint overlaps as error and leaves the patch unchanged
+    pub fn insert(
+        &mut self,
+        id: K,
+        footprint: DMXFootprint,
+    ) -> Result<Option<DMXFootprint>, PatchCollision<K>> {
+        if let Some((other, other_footprint)) =
+            self.collisions(&footprint).find(|(other, _)| **other != id)
+        {
+            return Err(PatchCollision {
+                id: other.clone(),
+                footprint: *other_footprint,
+            });
+        }
+        let old = self.remove(&id);
+        self.starts.insert(footprint.start.absolute, id.clone());
+        self.entries.insert(id, footprint);
+        Ok(old)
+    }
+
+    ///Removes a fixture from the patch and returns its footprint
+    pub fn remove(&mut self, id: &K) -> Option<DMXFootprint> {
+        let footprint = self.entries.remove(id)?;
+        self.starts.remove(&footprint.start.absolute);
+        Some(footprint)
+    }
+
+    ///Returns the footprint of a fixture
+    pub fn get(&self, id: &K) -> Option<&DMXFootprint> {
+        self.entries.get(id)
+    }
+
+    ///Returns the fixture whose footprint contains the address
+    pub fn at(&self, address: &DMXAddress) -> Option<(&K, &DMXFootprint)> {
+        //Footprints do not overlap, so only the last footprint starting at or before the address can contain it
+        let (_, id) = self.starts.range(..=address.absolute).next_back()?;
+        let footprint = &self.entries[id];
+        if footprint.contains(address) {
+            Some((id, footprint))
+        } else {
+            None
+        }
+    }
+
+    ///Returns all fixtures whose footprints overlap the footprint in ascending order of their addresses
+    pub fn collisions<'a>(
+        &'a self,
+        footprint: &'a DMXFootprint,
+    ) -> impl Iterator<Item = (&'a K, &'a DMXFootprint)> + 'a {
+        //The footprint starting before the range might still reach into it
+        let before = self
+            .starts
+            .range(..footprint.start.absolute)
+            .next_back()
+            .map(|(_, id)| id);
+        let end = footprint.start.absolute + u32::from(footprint.width);
+        before
+            .into_iter()
+            .chain(
+                self.starts
+                    .range(footprint.start.absolute..end)
+                    .map(|(_, id)| id),
+            )
+            .map(move |id| (id, &self.entries[id]))
+            .filter(move |(_, other)| other.overlaps(footprint))
+    }
+
+    ///Returns the free blocks of one universe in ascending order. A free block never crosses the end of the universe. Returns nothing if the universe is not valid
+    pub fn gaps_in(&self, universe: u16) -> impl Iterator<Item = DMXFootprint> {
+        let mut gaps = Vec::new();
+        if let Ok(start) = DMXAddress::from_parts(universe.into(), 1) {
+            let whole = DMXFootprint {
+                start,
+                width: DMXAddress::ADDRESSES_PER_UNIVERSE,
+            };
+            let end = start.absolute + u32::from(DMXAddress::ADDRESSES_PER_UNIVERSE);
+            //Absolute address of the first slot that is not used by the fixtures before
+            let mut free = start.absolute;
+            for (_, footprint) in self.collisions(&whole) {
+                if footprint.start.absolute > free {
+                    gaps.extend(gap(&start, free, footprint.start.absolute));
+                }
+                free = free.max(footprint.start.absolute + u32::from(footprint.width));
+            }
+            if free < end {
+                gaps.extend(gap(&start, free, end));
+            }
+        }
+        gaps.into_iter()
+    }
+
+    ///Returns the free blocks of all universes from the first to the last universe that is used by a fixture in ascending order. A free block never crosses the end of a universe
+    pub fn gaps(&self) -> impl Iterator<Item = DMXFootprint> + '_ {
+        let first = self.starts.values().next().map(|id| &self.entries[id]);
+        let last = self.starts.values().next_back().map(|id| &self.entries[id]);
+        let universes = first.zip(last).map(|(first, last)| {
+            first.start.universe..=last.end().map_or(last.start.universe, |end| end.universe)
+        });
+        universes
+            .into_iter()
+            .flatten()
+            .flat_map(move |universe| self.gaps_in(universe))
+    }
+
+    ///Returns the number of slots of the largest free block in the universe. Returns 0 if the universe is full or not valid
+    pub fn largest_gap(&self, universe: u16) -> u16 {
+        self.gaps_in(universe)
+            .map(|gap| gap.width)
+            .max()
+            .unwrap_or(0)
+    }
+
+    ///Returns all fixtures and their footprints in ascending order of their addresses
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &DMXFootprint)> {
+        self.starts.values().map(move |id| (id, &self.entries[id]))
+    }
+
+    ///Returns the number of fixtures in the patch
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    ///Returns true if no fixture is patched
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+///Returns the footprint of the free slots from the absolute dmx address from to to (exclusive) in the universe of first, which is the first address of the universe. Returns None if the slots are not in the universe
+fn gap(first: &DMXAddress, from: u32, to: u32) -> Option<DMXFootprint> {
+    let offset = from.checked_sub(first.absolute)?;
+    let start = DMXAddress::from_parts(first.universe.into(), offset + 1).ok()?;
+    let width = u16::try_from(to.checked_sub(from)?).ok()?;
+    DMXFootprint::with_policy(start, width, BoundaryPolicy::Reject).ok()
+}
+
+///The default patch uses `BoundaryPolicy::Allow`
+impl<K: Ord + Clone> Default for DMXPatch<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Display> Display for PatchCollision<K> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "the footprint overlaps fixture {} at {} with {} slots",
+            self.id, self.footprint.start, self.footprint.width
+        )
+    }
+}
+
+impl<K: std::fmt::Debug + Display> std::error::Error for PatchCollision<K> {}
+
+#[cfg(test)]
+mod tests {
+    use super::gap;
+    use crate::test_helpers::{address, footprint};
+    use crate::{BoundaryPolicy, DMXFootprint, DMXPatch};
+
+    fn patch() -> DMXPatch<u32> {
+        let mut patch = DMXPatch::new();
+        patch.insert(1, footprint("1.1", 30)).unwrap();
+        patch.insert(2, footprint("1.31", 16)).unwrap();
+        patch.insert(3, footprint("1.500", 30)).unwrap();
+        patch
+    }
+
+    #[test]
+    fn test_insert() {
+        let patch = patch();
+        assert_eq!(3, patch.len());
+        assert!(!patch.is_empty());
+        assert_eq!(Some(&footprint("1.31", 16)), patch.get(&2));
+        assert_eq!(None, patch.get(&4));
+    }
+
+    #[test]
+    fn test_insert_collision() {
+        let mut patch = patch();
+        let collision = patch.insert(4, footprint("1.40", 10)).unwrap_err();
+        assert_eq!(2, collision.id);
+        assert_eq!(footprint("1.31", 16), collision.footprint);
+        let collision = patch.insert(4, footprint("2.10", 10)).unwrap_err();
+        assert_eq!(3, collision.id);
+        assert_eq!(3, patch.len());
+        assert_eq!(
+            "the footprint overlaps fixture 3 at 1.500 with 30 slots",
+            collision.to_string()
+        );
+    }
+
+    #[test]
+    fn test_insert_replace() {
+        let mut patch = patch();
+        //Moving a fixture into its own old footprint is no collision
+        assert_eq!(
+            Some(footprint("1.31", 16)),
+            patch.insert(2, footprint("1.35", 16)).unwrap()
+        );
+        assert_eq!(Some(&footprint("1.35", 16)), patch.get(&2));
+        assert_eq!(None, patch.at(&address("1.31")));
+        assert_eq!(3, patch.len());
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut patch = patch();
+        assert_eq!(Some(footprint("1.31", 16)), patch.remove(&2));
+        assert_eq!(None, patch.remove(&2));
+        assert_eq!(None, patch.at(&address("1.31")));
+        patch.insert(4, footprint("1.40", 10)).unwrap();
+        assert_eq!(
+            vec![1, 4, 3],
+            patch.iter().map(|(id, _)| *id).collect::<Vec<u32>>()
+        );
+    }
+
+    #[test]
+    fn test_at() {
+        let patch = patch();
+        assert_eq!(Some(&1), patch.at(&address("1.1")).map(|(id, _)| id));
+        assert_eq!(Some(&1), patch.at(&address("1.30")).map(|(id, _)| id));
+        assert_eq!(Some(&2), patch.at(&address("1.31")).map(|(id, _)| id));
+        assert_eq!(None, patch.at(&address("1.47")));
+        assert_eq!(Some(&3), patch.at(&address("2.17")).map(|(id, _)| id));
+        assert_eq!(None, patch.at(&address("2.18")));
+    }
+
+    #[test]
+    fn test_collisions() {
+        let patch = patch();
+        let wide = footprint("1.20", 100);
+        assert_eq!(
+            vec![1, 2],
+            patch
+                .collisions(&wide)
+                .map(|(id, _)| *id)
+                .collect::<Vec<u32>>()
+        );
+        assert_eq!(0, patch.collisions(&footprint("1.47", 453)).count());
+    }
+
+    #[test]
+    fn test_iter() {
+        let mut patch = DMXPatch::new();
+        patch.insert("b", footprint("2.1", 1)).unwrap();
+        patch.insert("a", footprint("3.1", 1)).unwrap();
+        patch.insert("c", footprint("1.1", 1)).unwrap();
+        assert_eq!(
+            vec!["c", "b", "a"],
+            patch.iter().map(|(id, _)| *id).collect::<Vec<&str>>()
+        );
+    }
+
+    #[test]
+    fn test_next_free() {
+        let patch = patch();
+        assert_eq!(Some(address("1.47")), patch.next_free(address("1.1"), 10));
+        assert_eq!(Some(address("1.47")), patch.next_free(address("1.20"), 453));
+        assert_eq!(Some(address("2.18")), patch.next_free(address("1.20"), 454));
+        assert_eq!(Some(address("1.100")), patch.next_free(address("1.100"), 1));
+        assert_eq!(
+            Some(address("1.1")),
+            DMXPatch::<u32>::new().next_free(address("1.1"), 512)
+        );
+    }
+
+    #[test]
+    fn test_next_free_allow() {
+        let mut patch = DMXPatch::with_policy(BoundaryPolicy::Allow);
+        patch.insert(1, footprint("1.1", 490)).unwrap();
+        assert_eq!(Some(address("1.491")), patch.next_free(address("1.1"), 30));
+    }
+
+    #[test]
+    fn test_next_free_wrap() {
+        for policy in &[BoundaryPolicy::Reject, BoundaryPolicy::WrapToNextUniverse] {
+            let mut patch = DMXPatch::with_policy(*policy);
+            patch.insert(1, footprint("1.1", 490)).unwrap();
+            patch.insert(2, footprint("2.1", 10)).unwrap();
+            assert_eq!(Some(address("2.11")), patch.next_free(address("1.1"), 30));
+            assert_eq!(Some(address("1.491")), patch.next_free(address("1.1"), 22));
+            assert_eq!(None, patch.next_free(address("1.1"), 513));
+        }
+    }
+
+    #[test]
+    fn test_next_free_none() {
+        let mut patch = DMXPatch::new();
+        patch.insert(1, footprint("63999.1", 512)).unwrap();
+        assert_eq!(None, patch.next_free(address("63999.1"), 1));
+        assert_eq!(None, patch.next_free(address("63998.500"), 20));
+        assert_eq!(None, patch.next_free(address("1.1"), 0));
+    }
+
+    #[test]
+    fn test_gaps_in() {
+        let patch = patch();
+        assert_eq!(
+            vec![footprint("1.47", 453)],
+            patch.gaps_in(1).collect::<Vec<DMXFootprint>>()
+        );
+        assert_eq!(
+            vec![footprint("2.18", 495)],
+            patch.gaps_in(2).collect::<Vec<DMXFootprint>>()
+        );
+        assert_eq!(
+            vec![footprint("3.1", 512)],
+            patch.gaps_in(3).collect::<Vec<DMXFootprint>>()
+        );
+        assert_eq!(0, patch.gaps_in(0).count());
+        assert_eq!(0, patch.gaps_in(64_000).count());
+    }
+
+    #[test]
+    fn test_gaps_in_2() {
+        let mut patch = DMXPatch::new();
+        patch.insert(1, footprint("1.10", 10)).unwrap();
+        patch.insert(2, footprint("1.30", 483)).unwrap();
+        assert_eq!(
+            vec![footprint("1.1", 9), footprint("1.20", 10)],
+            patch.gaps_in(1).collect::<Vec<DMXFootprint>>()
+        );
+        patch.insert(3, footprint("1.1", 9)).unwrap();
+        patch.insert(4, footprint("1.20", 10)).unwrap();
+        assert_eq!(0, patch.gaps_in(1).count());
+    }
+
+    #[test]
+    fn test_gap() {
+        let first = address("2.1");
+        assert_eq!(Some(footprint("2.10", 5)), gap(&first, 522, 527));
+        assert_eq!(Some(footprint("2.1", 512)), gap(&first, 513, 1025));
+        assert_eq!(None, gap(&first, 512, 520));
+        assert_eq!(None, gap(&first, 1000, 1030));
+        assert_eq!(None, gap(&first, 530, 520));
+    }
+
+    #[test]
+    fn test_gaps() {
+        let mut patch = patch();
+        patch.insert(4, footprint("4.1", 100)).unwrap();
+        assert_eq!(
+            vec![
+                footprint("1.47", 453),
+                footprint("2.18", 495),
+                footprint("3.1", 512),
+                footprint("4.101", 412)
+            ],
+            patch.gaps().collect::<Vec<DMXFootprint>>()
+        );
+        assert_eq!(0, DMXPatch::<u32>::new().gaps().count());
+    }
+
+    #[test]
+    fn test_largest_gap() {
+        let mut patch = DMXPatch::new();
+        patch.insert(1, footprint("1.10", 10)).unwrap();
+        patch.insert(2, footprint("1.40", 10)).unwrap();
+        patch.insert(3, footprint("1.100", 413)).unwrap();
+        assert_eq!(50, patch.largest_gap(1));
+        assert_eq!(512, patch.largest_gap(2));
+        patch.insert(4, footprint("2.1", 512)).unwrap();
+        assert_eq!(0, patch.largest_gap(2));
+        assert_eq!(0, patch.largest_gap(0));
+    }
+}
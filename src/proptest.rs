@@ -0,0 +1,117 @@
+//!Holds `proptest` strategies that generate valid dmx addresses for property tests. Only available with the feature `proptest`
+//!
+//!```rust
+//! use dmx_struct::proptest::any_dmx_address;
+//! use proptest::proptest;
+//!
+//! proptest!(|(address in any_dmx_address())| {
+//!     assert!(address.address >= 1 && address.address <= 512);
+//! });
+//! ```
+use core::ops::RangeInclusive;
+
+use ::proptest::arbitrary::Arbitrary;
+use ::proptest::strategy::{BoxedStrategy, Strategy};
+
+use crate::{DMXAddress, Protocol};
+
+///Returns a strategy that generates any valid dmx address, from 1.001 to 63999.512
+pub fn any_dmx_address() -> impl Strategy<Value = DMXAddress> {
+    any_dmx_address_for(Protocol::Sacn)
+}
+
+///Returns a strategy that generates any dmx address that is valid in the protocol, eg. from 1.001 to 32768.512 for Art-Net
+pub fn any_dmx_address_for(protocol: Protocol) -> impl Strategy<Value = DMXAddress> {
+    (1..=protocol.max_absolute())
+        .prop_map(move |absolute| DMXAddress::from_absolute_in(absolute, protocol).unwrap())
+}
+
+///Returns a strategy that generates dmx addresses with any address (1-512) in the given universes, eg. 1..=4
+///
+///Panics if the range is empty or contains universes that are not valid (1-63999)
+pub fn dmx_address_in_universes(
+    universes: RangeInclusive<u16>,
+) -> impl Strategy<Value = DMXAddress> {
+    assert!(
+        !universes.is_empty()
+            && *universes.start() >= 1
+            && *universes.end() <= DMXAddress::MAX_UNIVERSE,
+        "the universes must be a non-empty range between 1 and {}",
+        DMXAddress::MAX_UNIVERSE
+    );
+    (universes, 1..=DMXAddress::ADDRESSES_PER_UNIVERSE).prop_map(|(universe, address)| {
+        DMXAddress::from_parts(universe.into(), address.into()).unwrap()
+    })
+}
+
+///Returns a strategy that generates dmx addresses with any address (1-512) in one universe
+///
+///Panics if the universe is not valid (1-63999)
+pub fn dmx_address_in_universe(universe: u16) -> impl Strategy<Value = DMXAddress> {
+    dmx_address_in_universes(universe..=universe)
+}
+
+///Allows to use `any::<DMXAddress>()`. Generates the same addresses as `any_dmx_address`
+impl Arbitrary for DMXAddress {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<DMXAddress>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        any_dmx_address().boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use ::proptest::prelude::*;
+
+    use crate::proptest::{
+        any_dmx_address, any_dmx_address_for, dmx_address_in_universe, dmx_address_in_universes,
+    };
+    use crate::{DMXAddress, Protocol};
+
+    proptest! {
+        #[test]
+        fn test_any_dmx_address(address in any_dmx_address()) {
+            prop_assert!(address.universe >= 1 && address.universe <= 63_999);
+            prop_assert!(address.address >= 1 && address.address <= 512);
+            prop_assert_eq!(address, DMXAddress::try_from(address.to_string().as_str()).unwrap());
+        }
+
+        #[test]
+        fn test_any_dmx_address_for(address in any_dmx_address_for(Protocol::ArtNet)) {
+            prop_assert!(address.is_valid_for(Protocol::ArtNet));
+            prop_assert!(address.to_artnet().is_ok());
+        }
+
+        #[test]
+        fn test_dmx_address_in_universes(address in dmx_address_in_universes(3..=5)) {
+            prop_assert!(address.universe >= 3 && address.universe <= 5);
+            prop_assert_eq!(address, DMXAddress::try_from(address.absolute.to_string().as_str()).unwrap());
+        }
+
+        #[test]
+        fn test_dmx_address_in_universe(address in dmx_address_in_universe(63_999)) {
+            prop_assert_eq!(63_999, address.universe);
+        }
+
+        #[test]
+        fn test_arbitrary(address in any::<DMXAddress>()) {
+            prop_assert!(address.next().map_or(true, |next| next.offset_from(&address) == 1));
+        }
+
+        #[test]
+        fn test_to_string_round_trip(address in any_dmx_address()) {
+            prop_assert_eq!(address, DMXAddress::try_from(address.to_string().as_str()).unwrap());
+            prop_assert_eq!(address, DMXAddress::try_from(String::from(address).as_str()).unwrap());
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_dmx_address_in_universes_invalid() {
+        let _ = dmx_address_in_universes(0..=5);
+    }
+}
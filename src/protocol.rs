@@ -0,0 +1,190 @@
+//!Holds the protocols that limit the number of universes a dmx address can be in
+use core::fmt::{Display, Formatter};
+
+use crate::{DMXAddress, DMXParseError};
+
+///Protocol that defines the highest universe that is valid. `DMXAddress` is validated against sACN by default
+///
+///```rust
+/// use dmx_struct::{DMXAddress, ParseOptions, Protocol};
+///
+/// let options = ParseOptions::new().protocol(Protocol::ArtNet);
+/// assert!(DMXAddress::parse_with("32768.1", &options).is_ok());
+/// assert!(DMXAddress::parse_with("32769.1", &options).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Protocol {
+    ///sACN (E1.31) supports the universes 1-63999
+    #[default]
+    Sacn,
+    ///Art-Net supports 32768 port addresses, which are the universes 1-32768
+    ArtNet,
+    ///Any universe that fits into the universe of `DMXAddress` (1-65535), eg. for internal tools
+    Unlimited,
+}
+
+impl Protocol {
+    ///Returns the highest universe that is valid in the protocol
+    pub const fn max_universe(&self) -> u16 {
+        match self {
+            Protocol::Sacn => DMXAddress::MAX_UNIVERSE,
+            Protocol::ArtNet => 32_768,
+            Protocol::Unlimited => u16::MAX,
+        }
+    }
+
+    ///Returns the highest absolute dmx address that is valid in the protocol. This is address 512 in the highest universe
+    pub const fn max_absolute(&self) -> u32 {
+        self.max_universe() as u32 * DMXAddress::ADDRESSES_PER_UNIVERSE as u32
+    }
+
+    ///Returns the error for a universe that is out of range. sACN keeps returning `InvalidUniverse`
+    pub(crate) fn invalid_universe(&self, universe: u32) -> DMXParseError {
+        match self {
+            Protocol::Sacn => DMXParseError::InvalidUniverse(universe),
+            _ => DMXParseError::InvalidProtocolUniverse {
+                universe,
+                protocol: *self,
+            },
+        }
+    }
+
+    ///Returns the error for an absolute dmx address that is out of range. sACN keeps returning `InvalidAbsolute`
+    pub(crate) fn invalid_absolute(&self, absolute: u32) -> DMXParseError {
+        match self {
+            Protocol::Sacn => DMXParseError::InvalidAbsolute(absolute),
+            _ => DMXParseError::InvalidProtocolAbsolute {
+                absolute,
+                protocol: *self,
+            },
+        }
+    }
+}
+
+///Protocols can be used in format with {}. It will return the name of the protocol, eg. 'sACN'
+impl Display for Protocol {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Protocol::Sacn => write!(f, "sACN"),
+            Protocol::ArtNet => write!(f, "Art-Net"),
+            Protocol::Unlimited => write!(f, "unlimited"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use crate::{DMXAddress, DMXParseError, ParseOptions, Protocol};
+
+    #[test]
+    fn test_max() {
+        assert_eq!(63_999, Protocol::default().max_universe());
+        assert_eq!(32_767_488, Protocol::Sacn.max_absolute());
+        assert_eq!(32_768, Protocol::ArtNet.max_universe());
+        assert_eq!(16_777_216, Protocol::ArtNet.max_absolute());
+        assert_eq!(65_535, Protocol::Unlimited.max_universe());
+        assert_eq!(33_553_920, Protocol::Unlimited.max_absolute());
+    }
+
+    #[test]
+    fn test_parse_artnet() {
+        let options = ParseOptions::new().protocol(Protocol::ArtNet);
+        assert_eq!(
+            DMXAddress {
+                universe: 32_768,
+                address: 512,
+                absolute: 16_777_216
+            },
+            DMXAddress::parse_with("32768.512", &options).unwrap()
+        );
+        assert!(matches!(
+            DMXAddress::parse_with("32769.1", &options),
+            Err(DMXParseError::InvalidProtocolUniverse {
+                universe: 32_769,
+                protocol: Protocol::ArtNet
+            })
+        ));
+        assert!(matches!(
+            DMXAddress::parse_with("16777217", &options),
+            Err(DMXParseError::InvalidProtocolAbsolute {
+                absolute: 16_777_217,
+                protocol: Protocol::ArtNet
+            })
+        ));
+        assert!(matches!(
+            DMXAddress::parse_with("0.1", &options),
+            Err(DMXParseError::InvalidProtocolUniverse { universe: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_unlimited() {
+        let options = ParseOptions::new().protocol(Protocol::Unlimited);
+        assert_eq!(
+            DMXAddress {
+                universe: 65_535,
+                address: 512,
+                absolute: 33_553_920
+            },
+            DMXAddress::parse_with("65535.512", &options).unwrap()
+        );
+        assert_eq!(
+            DMXAddress {
+                universe: 64_000,
+                address: 1,
+                absolute: 32_767_489
+            },
+            DMXAddress::parse_with("32767489", &options).unwrap()
+        );
+        assert!(matches!(
+            DMXAddress::parse_with("65536.1", &options),
+            Err(DMXParseError::InvalidProtocolUniverse {
+                universe: 65_536,
+                protocol: Protocol::Unlimited
+            })
+        ));
+    }
+
+    #[test]
+    fn test_parse_sacn() {
+        let options = ParseOptions::new().protocol(Protocol::Sacn);
+        assert!(matches!(
+            DMXAddress::parse_with("64000.1", &options),
+            Err(DMXParseError::InvalidUniverse(64_000))
+        ));
+        assert!(matches!(
+            DMXAddress::parse_with("32767489", &options),
+            Err(DMXParseError::InvalidAbsolute(32_767_489))
+        ));
+    }
+
+    #[test]
+    fn test_is_valid_for() {
+        let address = DMXAddress::try_from("40000.1").unwrap();
+        assert!(address.is_valid_for(Protocol::Sacn));
+        assert!(!address.is_valid_for(Protocol::ArtNet));
+        assert!(address.is_valid_for(Protocol::Unlimited));
+        let address = DMXAddress::parse_with(
+            "65000.1",
+            &ParseOptions::new().protocol(Protocol::Unlimited),
+        )
+        .unwrap();
+        assert!(!address.is_valid_for(Protocol::Sacn));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!("sACN", Protocol::Sacn.to_string());
+        assert_eq!("Art-Net", Protocol::ArtNet.to_string());
+        assert_eq!(
+            "universe 32769 is out of range, it must be between 1 and 32768 for Art-Net",
+            DMXParseError::InvalidProtocolUniverse {
+                universe: 32_769,
+                protocol: Protocol::ArtNet
+            }
+            .to_string()
+        );
+    }
+}
@@ -0,0 +1,690 @@
+//!Holds the range of dmx addresses from a start to an end address, eg. 1.1 through 1.120
+use core::convert::TryFrom;
+use core::fmt::{Display, Formatter};
+use core::ops::RangeInclusive;
+
+use crate::parse::parse_bytes;
+use crate::{BoundaryPolicy, DMXAddress, DMXParseError};
+
+///Words between start and end of a range that are accepted when parsing (case-insensitive). Longer words come first, so through is not found as thru
+const RANGE_WORDS: [&[u8]; 2] = [b"through", b"thru"];
+
+///Inclusive range of dmx addresses from start to end. The range may span several universes
+///
+///```rust
+/// use std::convert::TryFrom;
+///
+/// use dmx_struct::{DMXAddress, DMXAddressRange};
+///
+/// let range = DMXAddressRange::try_from("1.511 thru 2.2").unwrap();
+/// assert_eq!(4, range.len());
+/// assert!(range.contains(&DMXAddress::try_from("2.1").unwrap()));
+/// assert_eq!("1.511 1.512 2.001 2.002", range.addresses().map(|a| a.to_string()).collect::<Vec<String>>().join(" "));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DMXAddressRange {
+    ///The first address of the range
+    pub start: DMXAddress,
+    ///The last address of the range
+    pub end: DMXAddress,
+}
+
+impl DMXAddressRange {
+    ///Creates a validated range. Returns an error if the end is before the start
+    pub fn new(start: DMXAddress, end: DMXAddress) -> Result<DMXAddressRange, DMXParseError> {
+        if start.absolute > end.absolute {
+            return Err(DMXParseError::InvalidRange {
+                start: start.absolute,
+                end: end.absolute,
+            });
+        }
+        Ok(DMXAddressRange { start, end })
+    }
+
+    ///Returns the number of addresses in the range. Returns 0 if the end is before the start
+    pub fn len(&self) -> u32 {
+        self.end
+            .absolute
+            .saturating_add(1)
+            .saturating_sub(self.start.absolute)
+    }
+
+    ///Returns true if the range does not hold any address, which is only the case if the end is before the start
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    ///Returns the universes the range touches in ascending order. Returns an empty range if the end is before the start
+    ///
+    ///```rust
+    /// use std::convert::TryFrom;
+    ///
+    /// use dmx_struct::DMXAddressRange;
+    ///
+    /// let range = DMXAddressRange::try_from("1.500-3.10").unwrap();
+    /// assert_eq!(1..=3, range.universes());
+    /// assert_eq!(13, range.channels_in_universe(1));
+    /// ```
+    pub fn universes(&self) -> RangeInclusive<u16> {
+        if self.is_empty() {
+            //A range that ends before it starts holds no universe
+            return RangeInclusive::new(1, 0);
+        }
+        self.start.universe..=self.end.universe
+    }
+
+    ///Returns the number of addresses of the range in the universe
+    pub fn channels_in_universe(&self, universe: u16) -> u16 {
+        if !self.universes().contains(&universe) {
+            return 0;
+        }
+        let first = if universe == self.start.universe {
+            self.start.address
+        } else {
+            1
+        };
+        let last = if universe == self.end.universe {
+            self.end.address
+        } else {
+            DMXAddress::ADDRESSES_PER_UNIVERSE
+        };
+        (last + 1).saturating_sub(first)
+    }
+
+    ///Returns true if the address is in the range
+    pub fn contains(&self, address: &DMXAddress) -> bool {
+        address.absolute >= self.start.absolute && address.absolute <= self.end.absolute
+    }
+
+    ///Returns all addresses of the range in ascending order across universe boundaries
+    pub fn addresses(&self) -> impl Iterator<Item = DMXAddress> {
+        (self.start.absolute..=self.end.absolute)
+            .map_while(|absolute| DMXAddress::from_absolute(absolute).ok())
+    }
+
+    ///Returns the start addresses of consecutive footprints with step slots each that fit completely into the range. The policy defines what happens with a footprint that does not fit into the rest of a universe like `DMXAddress::iter_step`
+    ///
+    ///```rust
+    /// use std::convert::TryFrom;
+    ///
+    /// use dmx_struct::{BoundaryPolicy, DMXAddressRange};
+    ///
+    /// let range = DMXAddressRange::try_from("1.1-1.100").unwrap();
+    /// assert_eq!(6, range.step_by_channels(16, BoundaryPolicy::Reject).count());
+    /// ```
+    pub fn step_by_channels(
+        &self,
+        step: u16,
+        policy: BoundaryPolicy,
+    ) -> impl Iterator<Item = DMXAddress> {
+        let end = self.end.absolute;
+        self.start
+            .iter_step(step, policy)
+            .take_while(move |start| start.absolute + u32::from(step) - 1 <= end)
+    }
+
+    ///Returns the parts of the range that are in one universe each in ascending order, eg. to send one packet per universe
+    ///
+    ///```rust
+    /// use std::convert::TryFrom;
+    ///
+    /// use dmx_struct::DMXAddressRange;
+    ///
+    /// let range = DMXAddressRange::try_from("1.500-3.10").unwrap();
+    /// let parts: Vec<String> = range.split_by_universe().map(|part| part.to_string()).collect();
+    /// assert_eq!(vec!["1.500-1.512", "2.001-2.512", "3.001-3.010"], parts);
+    /// ```
+    pub fn split_by_universe(&self) -> impl Iterator<Item = DMXAddressRange> {
+        let (start, end) = (self.start, self.end);
+        self.universes().map(move |universe| DMXAddressRange {
+            start: if universe == start.universe {
+                start
+            } else {
+                DMXAddress::from_parts(universe.into(), 1).unwrap_or(start)
+            },
+            end: if universe == end.universe {
+                end
+            } else {
+                DMXAddress::from_parts(universe.into(), DMXAddress::ADDRESSES_PER_UNIVERSE.into())
+                    .unwrap_or(end)
+            },
+        })
+    }
+
+    ///Returns true if the ranges share at least one address
+    pub fn overlaps(&self, other: &DMXAddressRange) -> bool {
+        !self.is_empty()
+            && !other.is_empty()
+            && self.start.absolute <= other.end.absolute
+            && other.start.absolute <= self.end.absolute
+    }
+
+    ///Returns the addresses that are in both ranges. Returns None if the ranges do not overlap
+    pub fn intersection(&self, other: &DMXAddressRange) -> Option<DMXAddressRange> {
+        if !self.overlaps(other) {
+            return None;
+        }
+        let start = if self.start.absolute >= other.start.absolute {
+            self.start
+        } else {
+            other.start
+        };
+        let end = if self.end.absolute <= other.end.absolute {
+            self.end
+        } else {
+            other.end
+        };
+        Some(DMXAddressRange { start, end })
+    }
+
+    ///Merges the ranges into a sorted list of ranges that neither overlap nor touch each other. Empty ranges are removed. Only available with the feature `std`
+    ///
+    ///```rust
+    /// use std::convert::TryFrom;
+    ///
+    /// use dmx_struct::DMXAddressRange;
+    ///
+    /// let ranges = vec![
+    ///     DMXAddressRange::try_from("1.50-1.100").unwrap(),
+    ///     DMXAddressRange::try_from("1.1-1.20").unwrap(),
+    ///     DMXAddressRange::try_from("1.21-1.60").unwrap(),
+    /// ];
+    /// assert_eq!(vec![DMXAddressRange::try_from("1.1-1.100").unwrap()], DMXAddressRange::union(ranges));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn union<I: IntoIterator<Item = DMXAddressRange>>(ranges: I) -> Vec<DMXAddressRange> {
+        let mut ranges: Vec<DMXAddressRange> = ranges
+            .into_iter()
+            .filter(|range| !range.is_empty())
+            .collect();
+        ranges.sort_by_key(|range| range.start.absolute);
+        let mut union: Vec<DMXAddressRange> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match union.last_mut() {
+                Some(last) if range.start.absolute <= last.end.absolute.saturating_add(1) => {
+                    if range.end.absolute > last.end.absolute {
+                        last.end = range.end;
+                    }
+                }
+                _ => union.push(range),
+            }
+        }
+        union
+    }
+
+    ///Returns the addresses of the ranges that are not in any of the removed ranges as sorted list of ranges that neither overlap nor touch each other. Only available with the feature `std`
+    ///
+    ///```rust
+    /// use std::convert::TryFrom;
+    ///
+    /// use dmx_struct::DMXAddressRange;
+    ///
+    /// let ranges = vec![DMXAddressRange::try_from("1.1-1.100").unwrap()];
+    /// let remove = vec![DMXAddressRange::try_from("1.21-1.30").unwrap()];
+    /// assert_eq!(
+    ///     vec![DMXAddressRange::try_from("1.1-1.20").unwrap(), DMXAddressRange::try_from("1.31-1.100").unwrap()],
+    ///     DMXAddressRange::difference(ranges, remove)
+    /// );
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn difference<I, R>(ranges: I, remove: R) -> Vec<DMXAddressRange>
+    where
+        I: IntoIterator<Item = DMXAddressRange>,
+        R: IntoIterator<Item = DMXAddressRange>,
+    {
+        let remove = Self::union(remove);
+        let mut difference = Vec::new();
+        for range in Self::union(ranges) {
+            //Start of the part of the range that was not checked against the removed ranges yet
+            let mut start = range.start.absolute;
+            for removed in remove.iter().filter(|removed| removed.overlaps(&range)) {
+                if removed.start.absolute > start {
+                    difference.extend(Self::from_absolute(start, removed.start.absolute - 1));
+                }
+                start = removed.end.absolute.saturating_add(1);
+            }
+            if start <= range.end.absolute {
+                difference.extend(Self::from_absolute(start, range.end.absolute));
+            }
+        }
+        difference
+    }
+
+    ///Creates a range from the absolute dmx addresses of start and end. Returns None if an address is not valid
+    #[cfg(feature = "std")]
+    fn from_absolute(start: u32, end: u32) -> Option<DMXAddressRange> {
+        Some(DMXAddressRange {
+            start: DMXAddress::from_absolute(start).ok()?,
+            end: DMXAddress::from_absolute(end).ok()?,
+        })
+    }
+}
+
+impl DMXAddress {
+    ///Returns the inclusive range between both addresses regardless of their order, eg. 1.1 to 1.20 for 1.20 and 1.1
+    ///
+    ///```rust
+    /// use std::convert::TryFrom;
+    ///
+    /// use dmx_struct::DMXAddress;
+    ///
+    /// let a = DMXAddress::try_from("2.10").unwrap();
+    /// let b = DMXAddress::try_from("1.500").unwrap();
+    /// assert_eq!("1.500-2.010", a.span_to(&b).to_string());
+    /// assert_eq!(a.span_to(&b), b.span_to(&a));
+    /// ```
+    pub fn span_to(&self, other: &DMXAddress) -> DMXAddressRange {
+        if self.absolute <= other.absolute {
+            DMXAddressRange {
+                start: *self,
+                end: *other,
+            }
+        } else {
+            DMXAddressRange {
+                start: *other,
+                end: *self,
+            }
+        }
+    }
+}
+
+///The range is displayed as 'start-end' (eg. 1.001-1.120)
+impl Display for DMXAddressRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+///Parses a range of format 'start-end', 'start..end', 'start thru end' or 'start through end' (eg. 1.1-1.20 or 1.1 thru 2.40). Start and end are parsed like `DMXAddress::try_from` and may be surrounded by whitespace. A single address is a range of one address
+impl TryFrom<&str> for DMXAddressRange {
+    type Error = DMXParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let value = value.as_bytes();
+        let (start, end) = match split(value) {
+            Some((start_end, end_start)) => (
+                endpoint(&value[..start_end], 0)?,
+                endpoint(&value[end_start..], end_start)?,
+            ),
+            None => {
+                let address = endpoint(value, 0)?;
+                (address, address)
+            }
+        };
+        DMXAddressRange::new(start, end)
+    }
+}
+
+///Returns the end of the start and the beginning of the end in the input. Returns None if the input has no separator between start and end
+fn split(value: &[u8]) -> Option<(usize, usize)> {
+    //.. is searched first, because a single . separates universe and address
+    if let Some(position) = value.windows(2).position(|window| window == b"..") {
+        return Some((position, position + 2));
+    }
+    for word in RANGE_WORDS.iter() {
+        if let Some(position) = value
+            .windows(word.len())
+            .position(|window| window.eq_ignore_ascii_case(word))
+        {
+            return Some((position, position + word.len()));
+        }
+    }
+    value
+        .iter()
+        .position(|byte| *byte == b'-')
+        .map(|position| (position, position + 1))
+}
+
+///Parses the start or the end of a range without the whitespace around it. The offset is the position of the endpoint in the whole input and is returned in the error
+fn endpoint(value: &[u8], offset: usize) -> Result<DMXAddress, DMXParseError> {
+    let start = value
+        .iter()
+        .position(|byte| !byte.is_ascii_whitespace())
+        .unwrap_or(value.len());
+    let end = value
+        .iter()
+        .rposition(|byte| !byte.is_ascii_whitespace())
+        .map_or(start, |end| end + 1);
+    parse_bytes(&value[start..end]).map_err(|error| error.shift(offset + start))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use crate::test_helpers::address;
+    use crate::{BoundaryPolicy, DMXAddress, DMXAddressRange, DMXParseError};
+
+    fn range(start: &str, end: &str) -> DMXAddressRange {
+        DMXAddressRange::new(address(start), address(end)).unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert_eq!(
+            DMXAddressRange {
+                start: address("1.1"),
+                end: address("1.120")
+            },
+            range("1.1", "1.120")
+        );
+        assert!(DMXAddressRange::new(address("1.1"), address("1.1")).is_ok());
+        assert!(matches!(
+            DMXAddressRange::new(address("2.1"), address("1.512")),
+            Err(DMXParseError::InvalidRange {
+                start: 513,
+                end: 512
+            })
+        ));
+    }
+
+    #[test]
+    fn test_len() {
+        assert_eq!(120, range("1.1", "1.120").len());
+        assert_eq!(1, range("1.1", "1.1").len());
+        assert_eq!(513, range("1.1", "2.1").len());
+        assert!(!range("1.1", "1.1").is_empty());
+        let invalid = DMXAddressRange {
+            start: address("2.1"),
+            end: address("1.1"),
+        };
+        assert_eq!(0, invalid.len());
+        assert!(invalid.is_empty());
+        assert_eq!(0, invalid.addresses().count());
+    }
+
+    #[test]
+    fn test_contains() {
+        let range = range("1.511", "2.2");
+        assert!(!range.contains(&address("1.510")));
+        assert!(range.contains(&address("1.511")));
+        assert!(range.contains(&address("2.2")));
+        assert!(!range.contains(&address("2.3")));
+    }
+
+    #[test]
+    fn test_addresses() {
+        assert_eq!(
+            vec![address("1.512"), address("2.1"), address("2.2")],
+            range("1.512", "2.2")
+                .addresses()
+                .collect::<Vec<DMXAddress>>()
+        );
+        assert_eq!(
+            vec![address("63999.512")],
+            range("63999.512", "63999.512")
+                .addresses()
+                .collect::<Vec<DMXAddress>>()
+        );
+        assert_eq!(120, range("1.1", "1.120").addresses().count());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!("1.001-1.120", range("1.1", "1.120").to_string());
+    }
+
+    #[test]
+    fn test_try_from() {
+        assert_eq!(
+            range("1.1", "1.20"),
+            DMXAddressRange::try_from("1.1-1.20").unwrap()
+        );
+        assert_eq!(
+            range("1.1", "1.20"),
+            DMXAddressRange::try_from("1.1..1.20").unwrap()
+        );
+        assert_eq!(
+            range("1.1", "2.40"),
+            DMXAddressRange::try_from("1.1 thru 2.40").unwrap()
+        );
+        assert_eq!(
+            range("1.1", "2.40"),
+            DMXAddressRange::try_from("1.1 THROUGH 2.40").unwrap()
+        );
+        assert_eq!(
+            range("1.1", "1.20"),
+            DMXAddressRange::try_from(" 1/1 - 20 ").unwrap()
+        );
+        assert_eq!(
+            range("1.512", "2.1"),
+            DMXAddressRange::try_from("512..513").unwrap()
+        );
+        assert_eq!(
+            range("2.5", "2.5"),
+            DMXAddressRange::try_from("2.5").unwrap()
+        );
+        assert_eq!(
+            range("1.1", "1.120"),
+            DMXAddressRange::try_from(range("1.1", "1.120").to_string().as_str()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_try_from_invalid() {
+        assert!(matches!(
+            DMXAddressRange::try_from("1.20-1.1"),
+            Err(DMXParseError::InvalidRange { start: 20, end: 1 })
+        ));
+        assert!(matches!(
+            DMXAddressRange::try_from("1.1-"),
+            Err(DMXParseError::Empty)
+        ));
+        assert!(matches!(
+            DMXAddressRange::try_from(""),
+            Err(DMXParseError::Empty)
+        ));
+        assert!(matches!(
+            DMXAddressRange::try_from("1.1 thru 1.x"),
+            Err(DMXParseError::NotANumber { offset: 11, .. })
+        ));
+        assert!(matches!(
+            DMXAddressRange::try_from("1.1-1.2.3"),
+            Err(DMXParseError::TooManyDots { offset: 7 })
+        ));
+        assert!(matches!(
+            DMXAddressRange::try_from("1.1-1.513"),
+            Err(DMXParseError::InvalidAddress(513))
+        ));
+        assert!(matches!(
+            DMXAddressRange::try_from("1.1 to 1.2"),
+            Err(DMXParseError::TooManyDots { offset: 8 })
+        ));
+    }
+
+    #[test]
+    fn test_step_by_channels() {
+        let starts: Vec<DMXAddress> = range("1.481", "2.32")
+            .step_by_channels(16, BoundaryPolicy::Allow)
+            .collect();
+        assert_eq!(
+            vec![
+                address("1.481"),
+                address("1.497"),
+                address("2.1"),
+                address("2.17")
+            ],
+            starts
+        );
+        let starts: Vec<DMXAddress> = range("1.490", "2.32")
+            .step_by_channels(16, BoundaryPolicy::WrapToNextUniverse)
+            .collect();
+        assert_eq!(
+            vec![address("1.490"), address("2.1"), address("2.17")],
+            starts
+        );
+        assert_eq!(
+            1,
+            range("1.490", "2.32")
+                .step_by_channels(16, BoundaryPolicy::Reject)
+                .count()
+        );
+        assert_eq!(
+            0,
+            range("1.1", "1.15")
+                .step_by_channels(16, BoundaryPolicy::Allow)
+                .count()
+        );
+    }
+
+    #[test]
+    fn test_overlaps() {
+        assert!(range("1.1", "1.10").overlaps(&range("1.10", "1.20")));
+        assert!(range("1.1", "2.10").overlaps(&range("1.10", "1.20")));
+        assert!(!range("1.1", "1.10").overlaps(&range("1.11", "1.20")));
+        assert!(!range("1.11", "1.20").overlaps(&range("1.1", "1.10")));
+    }
+
+    #[test]
+    fn test_intersection() {
+        assert_eq!(
+            Some(range("1.10", "1.10")),
+            range("1.1", "1.10").intersection(&range("1.10", "1.20"))
+        );
+        assert_eq!(
+            Some(range("1.10", "1.20")),
+            range("1.1", "2.10").intersection(&range("1.10", "1.20"))
+        );
+        assert_eq!(
+            Some(range("1.500", "2.10")),
+            range("1.500", "3.1").intersection(&range("1.1", "2.10"))
+        );
+        assert_eq!(
+            None,
+            range("1.1", "1.10").intersection(&range("1.11", "1.20"))
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_union() {
+        assert_eq!(
+            vec![range("1.1", "1.30"), range("1.40", "2.10")],
+            DMXAddressRange::union(vec![
+                range("1.40", "1.50"),
+                range("1.11", "1.30"),
+                range("1.1", "1.10"),
+                range("1.45", "2.10"),
+                range("1.20", "1.25"),
+            ])
+        );
+        assert_eq!(
+            Vec::<DMXAddressRange>::new(),
+            DMXAddressRange::union(vec![DMXAddressRange {
+                start: address("1.2"),
+                end: address("1.1")
+            }])
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_difference() {
+        assert_eq!(
+            vec![
+                range("1.1", "1.9"),
+                range("1.21", "1.29"),
+                range("2.1", "2.10")
+            ],
+            DMXAddressRange::difference(
+                vec![range("1.1", "1.30"), range("1.500", "2.10")],
+                vec![range("1.10", "1.20"), range("1.30", "1.512")]
+            )
+        );
+        assert_eq!(
+            Vec::<DMXAddressRange>::new(),
+            DMXAddressRange::difference(vec![range("1.5", "1.6")], vec![range("1.1", "1.10")])
+        );
+        assert_eq!(
+            vec![range("1.1", "1.10")],
+            DMXAddressRange::difference(vec![range("1.1", "1.10")], vec![])
+        );
+        assert_eq!(
+            vec![range("63999.1", "63999.511")],
+            DMXAddressRange::difference(
+                vec![range("63999.1", "63999.512")],
+                vec![range("63999.512", "63999.512")]
+            )
+        );
+    }
+
+    #[test]
+    fn test_split_by_universe() {
+        assert_eq!(
+            vec![
+                range("1.500", "1.512"),
+                range("2.1", "2.512"),
+                range("3.1", "3.10")
+            ],
+            range("1.500", "3.10")
+                .split_by_universe()
+                .collect::<Vec<DMXAddressRange>>()
+        );
+        assert_eq!(
+            vec![range("1.10", "1.20")],
+            range("1.10", "1.20")
+                .split_by_universe()
+                .collect::<Vec<DMXAddressRange>>()
+        );
+        assert_eq!(
+            vec![range("1.512", "1.512"), range("2.1", "2.1")],
+            range("1.512", "2.1")
+                .split_by_universe()
+                .collect::<Vec<DMXAddressRange>>()
+        );
+        let empty = DMXAddressRange {
+            start: address("1.2"),
+            end: address("1.1"),
+        };
+        assert_eq!(0, empty.split_by_universe().count());
+    }
+
+    #[test]
+    fn test_universes() {
+        assert_eq!(1..=3, range("1.500", "3.10").universes());
+        assert_eq!(2..=2, range("2.1", "2.512").universes());
+        assert_eq!(1..=2, range("1.512", "2.1").universes());
+        assert_eq!(
+            0,
+            DMXAddressRange {
+                start: address("2.1"),
+                end: address("1.512")
+            }
+            .universes()
+            .count()
+        );
+    }
+
+    #[test]
+    fn test_channels_in_universe() {
+        let range = range("1.500", "3.10");
+        assert_eq!(0, range.channels_in_universe(0));
+        assert_eq!(13, range.channels_in_universe(1));
+        assert_eq!(512, range.channels_in_universe(2));
+        assert_eq!(10, range.channels_in_universe(3));
+        assert_eq!(0, range.channels_in_universe(4));
+        assert_eq!(
+            u32::from(range.channels_in_universe(1))
+                + u32::from(range.channels_in_universe(2))
+                + u32::from(range.channels_in_universe(3)),
+            range.len()
+        );
+        assert_eq!(1, self::range("1.512", "1.512").channels_in_universe(1));
+        assert_eq!(512, self::range("1.1", "1.512").channels_in_universe(1));
+    }
+
+    #[test]
+    fn test_span_to() {
+        assert_eq!(
+            range("1.1", "1.20"),
+            address("1.1").span_to(&address("1.20"))
+        );
+        assert_eq!(
+            range("1.1", "1.20"),
+            address("1.20").span_to(&address("1.1"))
+        );
+        assert_eq!(range("2.5", "2.5"), address("2.5").span_to(&address("2.5")));
+        assert_eq!(1, address("2.5").span_to(&address("2.5")).len());
+        assert_eq!(514, address("3.1").span_to(&address("1.512")).len());
+    }
+}
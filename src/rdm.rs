@@ -0,0 +1,429 @@
+//!Holds the unique ids (UID) of RDM devices. A UID is 48 bit long and made of the 16 bit ESTA manufacturer id and a 32 bit device id. It is written in hex as mmmm:dddddddd, eg. 464C:0A001234
+use core::convert::TryFrom;
+use core::fmt::{Display, Formatter};
+
+///Unique id of an RDM device. UIDs are ordered by their 48 bit value, which is the order of the discovery
+///
+///```rust
+/// use std::convert::TryFrom;
+///
+/// use dmx_struct::RdmUid;
+///
+/// let uid = RdmUid::try_from("464C:0A001234").unwrap();
+/// assert_eq!(0x464C, uid.manufacturer_id());
+/// assert_eq!(0x0A00_1234, uid.device_id());
+/// assert_eq!("464C:0A001234", uid.to_string());
+/// assert!(RdmUid::BROADCAST.is_broadcast());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RdmUid(u64);
+
+///Error returned if a UID can not be parsed. Offsets are byte offsets in the input
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RdmUidError {
+    ///The input was empty
+    Empty,
+    ///The input does not contain : between manufacturer id and device id or contains it more than once
+    InvalidSeparator,
+    ///The manufacturer id is not 4 hex digits
+    InvalidManufacturer {
+        ///Byte offset of the manufacturer id
+        offset: usize,
+    },
+    ///The device id is not 8 hex digits
+    InvalidDevice {
+        ///Byte offset of the device id
+        offset: usize,
+    },
+    ///The value does not fit into 48 bits
+    OutOfRange(u64),
+    ///The end of a `RdmUidRange` is before its start
+    InvalidRange {
+        ///The value of the start
+        start: u64,
+        ///The value of the end
+        end: u64,
+    },
+}
+
+impl Display for RdmUidError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            RdmUidError::Empty => write!(f, "the uid is empty"),
+            RdmUidError::InvalidSeparator => write!(
+                f,
+                "the uid must be of format 'manufacturer:device', eg. 464C:0A001234"
+            ),
+            RdmUidError::InvalidManufacturer { offset } => write!(
+                f,
+                "the manufacturer id at position {} must be 4 hex digits",
+                offset
+            ),
+            RdmUidError::InvalidDevice { offset } => write!(
+                f,
+                "the device id at position {} must be 8 hex digits",
+                offset
+            ),
+            RdmUidError::OutOfRange(value) => {
+                write!(
+                    f,
+                    "uid {:#x} is out of range, it must fit into 48 bits",
+                    value
+                )
+            }
+            RdmUidError::InvalidRange { start, end } => write!(
+                f,
+                "range from uid {:#x} to {:#x} is not valid, the end must not be before the start",
+                start, end
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RdmUidError {}
+
+impl RdmUid {
+    ///The highest value of a UID
+    pub const MAX: u64 = 0xFFFF_FFFF_FFFF;
+    ///The device id that addresses all devices of a manufacturer
+    pub const ALL_DEVICES: u32 = 0xFFFF_FFFF;
+    ///The UID that addresses all devices of all manufacturers
+    pub const BROADCAST: RdmUid = RdmUid(Self::MAX);
+
+    ///Creates the UID from the ESTA manufacturer id and the device id
+    pub const fn new(manufacturer_id: u16, device_id: u32) -> Self {
+        RdmUid(((manufacturer_id as u64) << 32) | device_id as u64)
+    }
+
+    ///Returns the UID that addresses all devices of the manufacturer (vendorcast)
+    pub const fn vendorcast(manufacturer_id: u16) -> Self {
+        Self::new(manufacturer_id, Self::ALL_DEVICES)
+    }
+
+    ///Creates the UID from its 48 bit value. Returns an error if the value is higher than `RdmUid::MAX`
+    pub fn from_u64(value: u64) -> Result<Self, RdmUidError> {
+        if value > Self::MAX {
+            return Err(RdmUidError::OutOfRange(value));
+        }
+        Ok(RdmUid(value))
+    }
+
+    ///Creates the UID from the 6 bytes that are sent in RDM packets, most significant first
+    pub fn from_bytes(bytes: [u8; 6]) -> Self {
+        let mut value = [0; 8];
+        value[2..].copy_from_slice(&bytes);
+        RdmUid(u64::from_be_bytes(value))
+    }
+
+    ///Returns the 6 bytes that are sent in RDM packets, most significant first
+    pub fn to_bytes(&self) -> [u8; 6] {
+        let mut bytes = [0; 6];
+        bytes.copy_from_slice(&self.0.to_be_bytes()[2..]);
+        bytes
+    }
+
+    ///Returns the 48 bit value of the UID
+    pub const fn value(&self) -> u64 {
+        self.0
+    }
+
+    ///Returns the ESTA manufacturer id
+    pub const fn manufacturer_id(&self) -> u16 {
+        (self.0 >> 32) as u16
+    }
+
+    ///Returns the device id
+    pub const fn device_id(&self) -> u32 {
+        self.0 as u32
+    }
+
+    ///Returns true if the UID addresses all devices of all manufacturers
+    pub fn is_broadcast(&self) -> bool {
+        *self == Self::BROADCAST
+    }
+
+    ///Returns true if the UID addresses all devices of one or all manufacturers
+    pub fn is_vendorcast(&self) -> bool {
+        self.device_id() == Self::ALL_DEVICES
+    }
+}
+
+///Parses a UID of format 'manufacturer:device' with 4 and 8 hex digits, eg. 464C:0A001234. Lowercase digits are accepted
+impl TryFrom<&str> for RdmUid {
+    type Error = RdmUidError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        if value.is_empty() {
+            return Err(RdmUidError::Empty);
+        }
+        let mut parts = value.split(':');
+        let (manufacturer, device) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(manufacturer), Some(device), None) => (manufacturer, device),
+            _ => return Err(RdmUidError::InvalidSeparator),
+        };
+        let manufacturer =
+            parse_hex(manufacturer, 4).ok_or(RdmUidError::InvalidManufacturer { offset: 0 })?;
+        let device = parse_hex(device, 8).ok_or(RdmUidError::InvalidDevice { offset: 5 })?;
+        Ok(RdmUid::new(manufacturer as u16, device))
+    }
+}
+
+///UIDs can be used in format with {}. It will return the format 'manufacturer:device' with uppercase hex digits, eg. 464C:0A001234
+impl Display for RdmUid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:04X}:{:08X}", self.manufacturer_id(), self.device_id())
+    }
+}
+
+///Inclusive range of UIDs that is searched during RDM discovery. The discovery starts with `RdmUidRange::ALL` and splits each range that more than one device answers to
+///
+///```rust
+/// use dmx_struct::{RdmUid, RdmUidRange};
+///
+/// let (lower, upper) = RdmUidRange::ALL.split().unwrap();
+/// assert_eq!("0000:00000000-7FFF:FFFFFFFF", lower.to_string());
+/// assert_eq!("8000:00000000-FFFF:FFFFFFFE", upper.to_string());
+/// assert!(upper.contains(&RdmUid::new(0x8000, 1)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RdmUidRange {
+    ///The first UID of the range
+    pub start: RdmUid,
+    ///The last UID of the range, which is part of the range
+    pub end: RdmUid,
+}
+
+impl RdmUidRange {
+    ///All UIDs that a device can have. The broadcast UID is excluded
+    pub const ALL: RdmUidRange = RdmUidRange {
+        start: RdmUid(0),
+        end: RdmUid(RdmUid::MAX - 1),
+    };
+
+    ///Creates the range. Returns `InvalidRange` if end is before start
+    pub fn new(start: RdmUid, end: RdmUid) -> Result<Self, RdmUidError> {
+        if end < start {
+            return Err(RdmUidError::InvalidRange {
+                start: start.0,
+                end: end.0,
+            });
+        }
+        Ok(RdmUidRange { start, end })
+    }
+
+    ///Returns the number of UIDs in the range
+    pub fn len(&self) -> u64 {
+        self.end.0 - self.start.0 + 1
+    }
+
+    ///Always returns false, as a range holds at least one UID
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    ///Returns true if the UID is in the range
+    pub fn contains(&self, uid: &RdmUid) -> bool {
+        self.start <= *uid && *uid <= self.end
+    }
+
+    ///Splits the range in the middle into a lower and an upper half, as done by the binary search of the discovery. The lower half holds the middle if the number of UIDs is odd. Returns None if the range holds only one UID
+    pub fn split(&self) -> Option<(RdmUidRange, RdmUidRange)> {
+        if self.start == self.end {
+            return None;
+        }
+        let middle = self.start.0 + (self.end.0 - self.start.0) / 2;
+        Some((
+            RdmUidRange {
+                start: self.start,
+                end: RdmUid(middle),
+            },
+            RdmUidRange {
+                start: RdmUid(middle + 1),
+                end: self.end,
+            },
+        ))
+    }
+
+    ///Returns all UIDs of the range in ascending order
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = RdmUid> {
+        (self.start.0..=self.end.0).map(RdmUid)
+    }
+}
+
+impl IntoIterator for RdmUidRange {
+    type Item = RdmUid;
+    type IntoIter = core::iter::Map<core::ops::RangeInclusive<u64>, fn(u64) -> RdmUid>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        (self.start.0..=self.end.0).map(RdmUid as fn(u64) -> RdmUid)
+    }
+}
+
+///Ranges can be used in format with {}. It will return the format 'start-end', eg. 464C:00000000-464C:FFFFFFFF
+impl Display for RdmUidRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+///Parses exactly digits hex digits. Returns None if the value has another length or contains other characters
+fn parse_hex(value: &str, digits: usize) -> Option<u32> {
+    if value.len() != digits || !value.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return None;
+    }
+    u32::from_str_radix(value, 16).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use crate::{RdmUid, RdmUidError, RdmUidRange};
+
+    #[test]
+    fn test_new() {
+        let uid = RdmUid::new(0x464C, 0x0A00_1234);
+        assert_eq!(0x464C_0A00_1234, uid.value());
+        assert_eq!(0x464C, uid.manufacturer_id());
+        assert_eq!(0x0A00_1234, uid.device_id());
+        assert!(!uid.is_broadcast());
+        assert!(!uid.is_vendorcast());
+    }
+
+    #[test]
+    fn test_broadcast() {
+        assert!(RdmUid::BROADCAST.is_broadcast());
+        assert!(RdmUid::BROADCAST.is_vendorcast());
+        let vendorcast = RdmUid::vendorcast(0x464C);
+        assert!(vendorcast.is_vendorcast());
+        assert!(!vendorcast.is_broadcast());
+        assert_eq!("464C:FFFFFFFF", vendorcast.to_string());
+    }
+
+    #[test]
+    fn test_from_u64() {
+        assert_eq!(RdmUid::BROADCAST, RdmUid::from_u64(RdmUid::MAX).unwrap());
+        assert_eq!(
+            Err(RdmUidError::OutOfRange(0x1_0000_0000_0000)),
+            RdmUid::from_u64(0x1_0000_0000_0000)
+        );
+    }
+
+    #[test]
+    fn test_bytes() {
+        let uid = RdmUid::new(0x464C, 0x0A00_1234);
+        assert_eq!([0x46, 0x4C, 0x0A, 0x00, 0x12, 0x34], uid.to_bytes());
+        assert_eq!(uid, RdmUid::from_bytes(uid.to_bytes()));
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            RdmUid::new(0x464C, 0x0A00_1234),
+            RdmUid::try_from("464C:0A001234").unwrap()
+        );
+        assert_eq!(
+            RdmUid::new(0x464C, 0x0A00_1234),
+            RdmUid::try_from("464c:0a001234").unwrap()
+        );
+        assert_eq!(
+            RdmUid::BROADCAST,
+            RdmUid::try_from("FFFF:FFFFFFFF").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert_eq!(Err(RdmUidError::Empty), RdmUid::try_from(""));
+        assert_eq!(
+            Err(RdmUidError::InvalidSeparator),
+            RdmUid::try_from("464C0A001234")
+        );
+        assert_eq!(
+            Err(RdmUidError::InvalidSeparator),
+            RdmUid::try_from("464C:0A00:1234")
+        );
+        assert_eq!(
+            Err(RdmUidError::InvalidManufacturer { offset: 0 }),
+            RdmUid::try_from("46C:0A001234")
+        );
+        assert_eq!(
+            Err(RdmUidError::InvalidManufacturer { offset: 0 }),
+            RdmUid::try_from("+46C:0A001234")
+        );
+        assert_eq!(
+            Err(RdmUidError::InvalidDevice { offset: 5 }),
+            RdmUid::try_from("464C:0A00123")
+        );
+        assert_eq!(
+            Err(RdmUidError::InvalidDevice { offset: 5 }),
+            RdmUid::try_from("464C:0A00123G")
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!("0001:00000002", RdmUid::new(1, 2).to_string());
+        assert_eq!("FFFF:FFFFFFFF", RdmUid::BROADCAST.to_string());
+    }
+
+    #[test]
+    fn test_range_new() {
+        let range = RdmUidRange::new(RdmUid::new(1, 0), RdmUid::new(1, 9)).unwrap();
+        assert_eq!(10, range.len());
+        assert!(!range.is_empty());
+        assert_eq!(
+            Err(RdmUidError::InvalidRange {
+                start: 0x1_0000_0001,
+                end: 0x1_0000_0000
+            }),
+            RdmUidRange::new(RdmUid::new(1, 1), RdmUid::new(1, 0))
+        );
+        assert_eq!(RdmUid::MAX, RdmUidRange::ALL.len());
+    }
+
+    #[test]
+    fn test_range_contains() {
+        let range = RdmUidRange::new(RdmUid::new(1, 5), RdmUid::new(2, 5)).unwrap();
+        assert!(range.contains(&RdmUid::new(1, 5)));
+        assert!(range.contains(&RdmUid::new(1, 0xFFFF_FFFF)));
+        assert!(range.contains(&RdmUid::new(2, 5)));
+        assert!(!range.contains(&RdmUid::new(1, 4)));
+        assert!(!range.contains(&RdmUid::new(2, 6)));
+        assert!(!RdmUidRange::ALL.contains(&RdmUid::BROADCAST));
+    }
+
+    #[test]
+    fn test_range_split() {
+        let range =
+            RdmUidRange::new(RdmUid::from_u64(0).unwrap(), RdmUid::from_u64(4).unwrap()).unwrap();
+        let (lower, upper) = range.split().unwrap();
+        assert_eq!((0, 2), (lower.start.value(), lower.end.value()));
+        assert_eq!((3, 4), (upper.start.value(), upper.end.value()));
+        let (lower, upper) = upper.split().unwrap();
+        assert_eq!(lower.start, lower.end);
+        assert_eq!(upper.start, upper.end);
+        assert_eq!(None, lower.split());
+        let (lower, upper) = RdmUidRange::ALL.split().unwrap();
+        assert_eq!(RdmUidRange::ALL.len(), lower.len() + upper.len());
+    }
+
+    #[test]
+    fn test_range_iter() {
+        let range = RdmUidRange::new(RdmUid::new(1, 0xFFFF_FFFE), RdmUid::new(2, 1)).unwrap();
+        let uids: Vec<RdmUid> = range.into_iter().collect();
+        assert_eq!(
+            vec![
+                RdmUid::new(1, 0xFFFF_FFFE),
+                RdmUid::new(1, 0xFFFF_FFFF),
+                RdmUid::new(2, 0),
+                RdmUid::new(2, 1)
+            ],
+            uids
+        );
+        assert_eq!(Some(RdmUid::new(2, 1)), range.iter().next_back());
+        assert_eq!("0001:FFFFFFFE-0002:00000001", range.to_string());
+    }
+}
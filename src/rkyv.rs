@@ -0,0 +1,140 @@
+//!Implements zero-copy (de)serialization of `DMXAddress` with rkyv. Only available with the feature `rkyv`
+//!
+//!The archived form `ArchivedDMXAddress` only stores the absolute dmx address as `u32`, so an archived address takes 4 bytes. Archives are validated when accessed with `rkyv::access`, so invalid absolute dmx addresses are rejected
+//!
+//!```rust
+//! use std::convert::TryFrom;
+//!
+//! use dmx_struct::{ArchivedDMXAddress, DMXAddress};
+//!
+//! let address = DMXAddress::try_from("2.234").unwrap();
+//! let bytes = rkyv::to_bytes::<rkyv::rancor::Error>(&address).unwrap();
+//! let archived = rkyv::access::<ArchivedDMXAddress, rkyv::rancor::Error>(&bytes).unwrap();
+//! assert_eq!(746, archived.absolute());
+//! ```
+use ::rkyv::bytecheck::{CheckBytes, Verify};
+use ::rkyv::rancor::{Fallible, Source};
+use ::rkyv::traits::NoUndef;
+use ::rkyv::{Archive, Archived, Deserialize, Place, Portable, Serialize};
+
+use crate::{DMXAddress, DMXParseError};
+
+///Archived form of `DMXAddress` that only stores the absolute dmx address
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Portable, CheckBytes)]
+#[rkyv(crate = ::rkyv)]
+#[bytecheck(crate = ::rkyv::bytecheck, verify)]
+#[repr(transparent)]
+pub struct ArchivedDMXAddress(Archived<u32>);
+
+//SAFETY: ArchivedDMXAddress is a transparent wrapper of an archived u32 that has no padding
+unsafe impl NoUndef for ArchivedDMXAddress {}
+
+impl ArchivedDMXAddress {
+    ///Returns the absolute dmx address
+    pub fn absolute(&self) -> u32 {
+        self.0.to_native()
+    }
+
+    ///Returns the `DMXAddress` without deserializing with rkyv. Returns an error if the archive was accessed without validation and holds an invalid absolute dmx address
+    pub fn to_dmx_address(&self) -> Result<DMXAddress, DMXParseError> {
+        DMXAddress::from_absolute(self.absolute())
+    }
+}
+
+//SAFETY: verify only checks the value of the absolute dmx address, which is always initialized
+unsafe impl<C> Verify<C> for ArchivedDMXAddress
+where
+    C: Fallible + ?Sized,
+    C::Error: Source,
+{
+    fn verify(&self, _: &mut C) -> Result<(), C::Error> {
+        self.to_dmx_address().map(|_| ()).map_err(Source::new)
+    }
+}
+
+impl Archive for DMXAddress {
+    type Archived = ArchivedDMXAddress;
+    type Resolver = ();
+
+    fn resolve(&self, _: Self::Resolver, out: Place<Self::Archived>) {
+        out.write(ArchivedDMXAddress(Archived::<u32>::from_native(
+            self.absolute,
+        )));
+    }
+}
+
+impl<S: Fallible + ?Sized> Serialize<S> for DMXAddress {
+    fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+        Ok(())
+    }
+}
+
+impl<D> Deserialize<DMXAddress, D> for ArchivedDMXAddress
+where
+    D: Fallible + ?Sized,
+    D::Error: Source,
+{
+    fn deserialize(&self, _: &mut D) -> Result<DMXAddress, D::Error> {
+        self.to_dmx_address().map_err(Source::new)
+    }
+}
+
+///Archived dmx addresses can be compared with dmx addresses
+impl PartialEq<DMXAddress> for ArchivedDMXAddress {
+    fn eq(&self, other: &DMXAddress) -> bool {
+        self.absolute() == other.absolute
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use ::rkyv::rancor::Error;
+
+    use crate::{ArchivedDMXAddress, DMXAddress};
+
+    #[test]
+    fn test_size() {
+        assert_eq!(4, core::mem::size_of::<ArchivedDMXAddress>());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let address = DMXAddress::try_from("63999.512").unwrap();
+        let bytes = ::rkyv::to_bytes::<Error>(&address).unwrap();
+        assert_eq!(4, bytes.len());
+        let archived = ::rkyv::access::<ArchivedDMXAddress, Error>(&bytes).unwrap();
+        assert_eq!(32_767_488, archived.absolute());
+        assert!(*archived == address);
+        assert_eq!(
+            address,
+            ::rkyv::deserialize::<DMXAddress, Error>(archived).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_vec() {
+        let addresses: Vec<DMXAddress> = ["1.1", "2.234", "512"]
+            .iter()
+            .map(|value| DMXAddress::try_from(*value).unwrap())
+            .collect();
+        let bytes = ::rkyv::to_bytes::<Error>(&addresses).unwrap();
+        let archived = ::rkyv::access::<::rkyv::Archived<Vec<DMXAddress>>, Error>(&bytes).unwrap();
+        assert_eq!(746, archived[1].absolute());
+        assert_eq!(
+            addresses,
+            ::rkyv::deserialize::<Vec<DMXAddress>, Error>(archived).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_invalid() {
+        let address = DMXAddress::try_from("1.1").unwrap();
+        for invalid in &[0_u32, 32_767_489] {
+            let mut bytes = ::rkyv::to_bytes::<Error>(&address).unwrap();
+            bytes[..4].copy_from_slice(&invalid.to_le_bytes());
+            assert!(::rkyv::access::<ArchivedDMXAddress, Error>(&bytes).is_err());
+        }
+    }
+}
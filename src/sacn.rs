@@ -0,0 +1,250 @@
+//!Holds the universe numbers of sACN (E1.31). Universes 1-63999 carry dmx data, 64000-65535 are reserved and universe 64214 is used for universe discovery
+use core::convert::TryFrom;
+use core::fmt::{Display, Formatter};
+use core::net::{Ipv4Addr, Ipv6Addr};
+use core::str::FromStr;
+
+use crate::parse::parse_number;
+use crate::{DMXAddress, DMXParseError};
+
+///Universe number of sACN (E1.31). Holds a universe that carries dmx data (1-63999) or the discovery universe. sACN universe 1 is universe 1 of `DMXAddress`
+///
+///```rust
+/// use std::convert::TryFrom;
+///
+/// use dmx_struct::{DMXAddress, SacnUniverse};
+///
+/// let universe: SacnUniverse = "12".parse().unwrap();
+/// assert_eq!(12, universe.value());
+/// assert_eq!(universe, DMXAddress::try_from("12.1").unwrap().sacn_universe().unwrap());
+/// assert!(SacnUniverse::new(64_000).is_err());
+/// assert!(SacnUniverse::new(64_214).unwrap().is_discovery());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SacnUniverse(u16);
+
+impl SacnUniverse {
+    ///The lowest universe that carries dmx data
+    pub const MIN: u16 = 1;
+    ///The highest universe that carries dmx data
+    pub const MAX: u16 = DMXAddress::MAX_UNIVERSE;
+    ///The universe that universe discovery packets are sent to
+    pub const DISCOVERY: SacnUniverse = SacnUniverse(64_214);
+
+    ///Creates a universe that carries dmx data (1-63999) or the discovery universe (64214). Returns `InvalidUniverse` for 0 and the other reserved universes
+    pub fn new(universe: u16) -> Result<Self, DMXParseError> {
+        if universe == Self::DISCOVERY.0 {
+            return Ok(Self::DISCOVERY);
+        }
+        Self::from_dmx_universe(universe)
+    }
+
+    ///Creates the universe that carries the dmx data of the universe of `DMXAddress`. Returns `InvalidUniverse` if the universe is out of range (1-63999)
+    pub fn from_dmx_universe(universe: u16) -> Result<Self, DMXParseError> {
+        if !(Self::MIN..=Self::MAX).contains(&universe) {
+            return Err(DMXParseError::InvalidUniverse(universe.into()));
+        }
+        Ok(SacnUniverse(universe))
+    }
+
+    ///Returns the universe number that is sent in the framing layer
+    pub fn value(&self) -> u16 {
+        self.0
+    }
+
+    ///Returns true if this is the universe discovery universe, which does not carry dmx data
+    pub fn is_discovery(&self) -> bool {
+        *self == Self::DISCOVERY
+    }
+
+    ///Returns the universe of `DMXAddress` this universe carries the data of. Returns None for the discovery universe
+    pub fn dmx_universe(&self) -> Option<u16> {
+        if self.is_discovery() {
+            None
+        } else {
+            Some(self.0)
+        }
+    }
+
+    ///Returns the IPv4 multicast group the universe is sent to, which is 239.255.hi.lo with the high and low byte of the universe
+    ///
+    ///```rust
+    /// use std::net::Ipv4Addr;
+    ///
+    /// use dmx_struct::SacnUniverse;
+    ///
+    /// let universe = SacnUniverse::new(258).unwrap();
+    /// assert_eq!(Ipv4Addr::new(239, 255, 1, 2), universe.multicast_addr());
+    /// ```
+    pub fn multicast_addr(&self) -> Ipv4Addr {
+        let [hi, lo] = self.0.to_be_bytes();
+        Ipv4Addr::new(239, 255, hi, lo)
+    }
+
+    ///Returns the IPv6 multicast group the universe is sent to, which is ff18::83:00:hi:lo with the high and low byte of the universe
+    pub fn multicast_addr_v6(&self) -> Ipv6Addr {
+        Ipv6Addr::new(0xFF18, 0, 0, 0, 0, 0, 0x8300, self.0)
+    }
+}
+
+///Universes are validated like `SacnUniverse::new`, so the discovery universe is accepted
+impl TryFrom<u16> for SacnUniverse {
+    type Error = DMXParseError;
+
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        SacnUniverse::new(value)
+    }
+}
+
+impl From<SacnUniverse> for u16 {
+    fn from(value: SacnUniverse) -> Self {
+        value.0
+    }
+}
+
+///Parses the decimal universe number, eg. 12 or 64214
+impl FromStr for SacnUniverse {
+    type Err = DMXParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.is_empty() {
+            return Err(DMXParseError::Empty);
+        }
+        let universe = parse_number(value.as_bytes(), 0)?;
+        match u16::try_from(universe) {
+            Ok(universe) => SacnUniverse::new(universe),
+            Err(_) => Err(DMXParseError::InvalidUniverse(universe)),
+        }
+    }
+}
+
+///Universes can be used in format with {}. It will return the universe number
+impl Display for SacnUniverse {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl DMXAddress {
+    ///Returns the sACN universe that carries the address. Returns `InvalidUniverse` if the universe of the address is higher than 63999
+    pub fn sacn_universe(&self) -> Result<SacnUniverse, DMXParseError> {
+        SacnUniverse::from_dmx_universe(self.universe)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    use crate::{DMXAddress, DMXParseError, SacnUniverse};
+
+    #[test]
+    fn test_new() {
+        assert_eq!(1, SacnUniverse::new(1).unwrap().value());
+        assert_eq!(63_999, SacnUniverse::new(63_999).unwrap().value());
+        assert_eq!(SacnUniverse::DISCOVERY, SacnUniverse::new(64_214).unwrap());
+        assert!(matches!(
+            SacnUniverse::new(0),
+            Err(DMXParseError::InvalidUniverse(0))
+        ));
+        assert!(matches!(
+            SacnUniverse::new(64_000),
+            Err(DMXParseError::InvalidUniverse(64_000))
+        ));
+        assert!(matches!(
+            SacnUniverse::new(u16::MAX),
+            Err(DMXParseError::InvalidUniverse(65_535))
+        ));
+        assert_eq!(
+            SacnUniverse::new(5).unwrap(),
+            SacnUniverse::try_from(5).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_discovery() {
+        assert!(SacnUniverse::DISCOVERY.is_discovery());
+        assert_eq!(None, SacnUniverse::DISCOVERY.dmx_universe());
+        assert!(!SacnUniverse::new(1).unwrap().is_discovery());
+        assert_eq!(Some(1), SacnUniverse::new(1).unwrap().dmx_universe());
+        assert!(matches!(
+            SacnUniverse::from_dmx_universe(64_214),
+            Err(DMXParseError::InvalidUniverse(64_214))
+        ));
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(SacnUniverse::new(12).unwrap(), "12".parse().unwrap());
+        assert_eq!(SacnUniverse::DISCOVERY, "64214".parse().unwrap());
+        assert!(matches!(
+            "".parse::<SacnUniverse>(),
+            Err(DMXParseError::Empty)
+        ));
+        assert!(matches!(
+            "1.1".parse::<SacnUniverse>(),
+            Err(DMXParseError::NotANumber { offset: 0, .. })
+        ));
+        assert!(matches!(
+            "70000".parse::<SacnUniverse>(),
+            Err(DMXParseError::InvalidUniverse(70_000))
+        ));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!("12", SacnUniverse::new(12).unwrap().to_string());
+        assert_eq!("64214", SacnUniverse::DISCOVERY.to_string());
+    }
+
+    #[test]
+    fn test_dmx_address() {
+        let address = DMXAddress::try_from("63999.512").unwrap();
+        assert_eq!(63_999, u16::from(address.sacn_universe().unwrap()));
+        let address = DMXAddress {
+            universe: 64_000,
+            address: 1,
+            absolute: 32_768_001,
+        };
+        assert!(matches!(
+            address.sacn_universe(),
+            Err(DMXParseError::InvalidUniverse(64_000))
+        ));
+    }
+
+    #[test]
+    fn test_multicast_addr() {
+        assert_eq!(
+            Ipv4Addr::new(239, 255, 0, 1),
+            SacnUniverse::new(1).unwrap().multicast_addr()
+        );
+        assert_eq!(
+            Ipv4Addr::new(239, 255, 249, 255),
+            SacnUniverse::new(63_999).unwrap().multicast_addr()
+        );
+        assert_eq!(
+            Ipv4Addr::new(239, 255, 250, 214),
+            SacnUniverse::DISCOVERY.multicast_addr()
+        );
+        assert!(SacnUniverse::new(1)
+            .unwrap()
+            .multicast_addr()
+            .is_multicast());
+    }
+
+    #[test]
+    fn test_multicast_addr_v6() {
+        let address = SacnUniverse::new(0x0102).unwrap().multicast_addr_v6();
+        assert_eq!(
+            [0xFF, 0x18, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x83, 0x00, 0x01, 0x02],
+            address.octets()
+        );
+        assert_eq!("ff18::8300:102", address.to_string());
+        assert_eq!(
+            Ipv6Addr::new(0xFF18, 0, 0, 0, 0, 0, 0x8300, 64_214),
+            SacnUniverse::DISCOVERY.multicast_addr_v6()
+        );
+        assert!(address.is_multicast());
+    }
+}
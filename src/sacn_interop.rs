@@ -0,0 +1,112 @@
+//!Converts the levels of a universe from and to the types of the `sacn` crate, so it can be used for transport. Only available with the feature `sacn`
+//!
+//!The `sacn` crate passes universes as `u16`, which `SacnUniverse` converts from and to. The data it sends and receives are the property values of the DMP layer, which are the start code followed by the levels
+//!
+//!```rust
+//! use std::convert::TryFrom;
+//! use std::time::Instant;
+//!
+//! use dmx_struct::DMXUniverseBuffer;
+//! use sacn::packet::DataPacketDmpLayer;
+//! use sacn::receive::DMXData;
+//!
+//! let mut buffer = DMXUniverseBuffer::new(1).unwrap();
+//! buffer.as_mut_slice()[0] = 255;
+//! let layer = DataPacketDmpLayer::from(&buffer);
+//! let data = DMXData {
+//!     universe: 1,
+//!     values: layer.property_values.to_vec(),
+//!     sync_uni: 0,
+//!     priority: 100,
+//!     src_cid: None,
+//!     preview: false,
+//!     recv_timestamp: Instant::now(),
+//! };
+//! assert_eq!(buffer, DMXUniverseBuffer::try_from(&data).unwrap());
+//! ```
+use std::borrow::Cow;
+use std::convert::TryFrom;
+
+use ::sacn::packet::DataPacketDmpLayer;
+use ::sacn::receive::DMXData;
+
+use crate::{DMXUniverseBuffer, DmpPayloadError};
+
+///Creates a buffer for the universe of the received data like `DMXUniverseBuffer::from_dmp_payload`. Returns an error if the universe is out of range, the data is empty or longer than the start code and 512 slots or the start code is not 0
+impl TryFrom<&DMXData> for DMXUniverseBuffer {
+    type Error = DmpPayloadError;
+
+    fn try_from(data: &DMXData) -> Result<Self, Self::Error> {
+        DMXUniverseBuffer::from_dmp_payload(data.universe, &data.values)
+    }
+}
+
+///Creates the DMP layer with the null start code and the levels of all 512 slots, like `DMXUniverseBuffer::to_dmp_payload`
+impl From<&DMXUniverseBuffer> for DataPacketDmpLayer<'static> {
+    fn from(buffer: &DMXUniverseBuffer) -> Self {
+        DataPacketDmpLayer {
+            property_values: Cow::Owned(buffer.to_dmp_payload().to_vec()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+    use std::time::Instant;
+
+    use ::sacn::packet::DataPacketDmpLayer;
+    use ::sacn::receive::DMXData;
+
+    use crate::{DMXUniverseBuffer, DmpPayloadError};
+
+    fn data(universe: u16, values: Vec<u8>) -> DMXData {
+        DMXData {
+            universe,
+            values,
+            sync_uni: 0,
+            priority: 100,
+            src_cid: None,
+            preview: false,
+            recv_timestamp: Instant::now(),
+        }
+    }
+
+    #[test]
+    fn test_from_buffer() {
+        let mut buffer = DMXUniverseBuffer::new(3).unwrap();
+        buffer.as_mut_slice()[0] = 1;
+        buffer.as_mut_slice()[511] = 2;
+        let layer = DataPacketDmpLayer::from(&buffer);
+        assert_eq!(513, layer.property_values.len());
+        assert_eq!(&[0, 1, 0], &layer.property_values[0..3]);
+        assert_eq!(2, layer.property_values[512]);
+    }
+
+    #[test]
+    fn test_try_from_data() {
+        let buffer = DMXUniverseBuffer::try_from(&data(3, vec![0, 255, 128])).unwrap();
+        assert_eq!(3, buffer.universe());
+        assert_eq!(&[255, 128, 0], &buffer.as_slice()[0..3]);
+    }
+
+    #[test]
+    fn test_try_from_data_invalid() {
+        assert_eq!(
+            Err(DmpPayloadError::InvalidUniverse(0)),
+            DMXUniverseBuffer::try_from(&data(0, vec![0, 255]))
+        );
+        assert_eq!(
+            Err(DmpPayloadError::InvalidStartCode(0xDD)),
+            DMXUniverseBuffer::try_from(&data(1, vec![0xDD, 255]))
+        );
+        assert_eq!(
+            Err(DmpPayloadError::InvalidLength(0)),
+            DMXUniverseBuffer::try_from(&data(1, Vec::new()))
+        );
+        assert_eq!(
+            Err(DmpPayloadError::InvalidLength(514)),
+            DMXUniverseBuffer::try_from(&data(1, vec![0; 514]))
+        );
+    }
+}
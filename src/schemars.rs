@@ -0,0 +1,75 @@
+//!Implements `schemars::JsonSchema` for `DMXAddress` to document it in generated JSON schemas. Only available with the feature `schemars`
+use std::borrow::Cow;
+
+use ::schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+
+use crate::{DMXAddress, Protocol};
+
+///Describes the human readable serialization of the feature `serde`. A `DMXAddress` is either a string of format 'universe.address' (eg. '1.234', '1/234' or '1:234'), a string with an absolute dmx address (eg. '1024') or an absolute dmx address as integer
+impl JsonSchema for DMXAddress {
+    fn schema_name() -> Cow<'static, str> {
+        "DMXAddress".into()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        "dmx_struct::DMXAddress".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "description": format!(
+                "A dmx address. Either a string of format 'universe.address' with universe 1-{} and address 1-{} (eg. '1.234', '1/234' or '1:234'), or an absolute dmx address 1-{} as string or integer (eg. '1024' or 1024)",
+                DMXAddress::MAX_UNIVERSE,
+                DMXAddress::ADDRESSES_PER_UNIVERSE,
+                DMXAddress::MAX_ABSOLUTE
+            ),
+            "oneOf": [
+                {
+                    "type": "string",
+                    "pattern": "^[0-9]+([./:][0-9]+)?$",
+                    "examples": ["1.234", "1024"]
+                },
+                {
+                    "type": "integer",
+                    "minimum": 1,
+                    "maximum": Protocol::Sacn.max_absolute()
+                }
+            ]
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::schemars::schema_for;
+
+    use crate::DMXAddress;
+
+    #[test]
+    fn test_schema() {
+        let schema = schema_for!(DMXAddress);
+        let schema = schema.as_value();
+        assert_eq!("DMXAddress", schema["title"]);
+        assert_eq!("string", schema["oneOf"][0]["type"]);
+        assert_eq!("^[0-9]+([./:][0-9]+)?$", schema["oneOf"][0]["pattern"]);
+        assert_eq!("integer", schema["oneOf"][1]["type"]);
+        assert_eq!(1, schema["oneOf"][1]["minimum"]);
+        assert_eq!(32_767_488, schema["oneOf"][1]["maximum"]);
+    }
+
+    #[test]
+    fn test_schema_field() {
+        #[allow(dead_code)]
+        #[derive(::schemars::JsonSchema)]
+        struct Fixture {
+            address: DMXAddress,
+        }
+        let schema = schema_for!(Fixture);
+        let schema = schema.as_value();
+        assert_eq!(
+            "#/$defs/DMXAddress",
+            schema["properties"]["address"]["$ref"]
+        );
+        assert_eq!("integer", schema["$defs"]["DMXAddress"]["oneOf"][1]["type"]);
+    }
+}
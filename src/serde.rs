@@ -0,0 +1,650 @@
+//!Serde support for `DMXAddress`, `CompactDMXAddress` and `DMXParseError`. Only available with the feature `serde`
+//!
+//!By default a `DMXAddress` is serialized in the format 'universe.address' for human readable formats (eg. JSON, YAML) and as absolute `u32` for compact binary formats. Deserialization of human readable formats accepts the same strings as `TryFrom<&str>` and absolute dmx addresses as integers. Every value is validated so invalid addresses are rejected
+//!
+//!A `DMXFootprint` is (de)serialized as struct with the fields start and width and a `DMXPatch` with a stable schema that is documented in [`DMXPatch`](crate::DMXPatch#serde)
+//!
+//!If a specific representation is needed, one of the modules [`as_string`], [`as_absolute`] or [`as_struct`] can be used with `#[serde(with = "...")]`
+//!
+//!```rust
+//! use dmx_struct::DMXAddress;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Fixture {
+//!     #[serde(with = "dmx_struct::serde::as_absolute")]
+//!     address: DMXAddress,
+//! }
+//! ```
+use core::convert::TryFrom;
+use core::fmt::Formatter;
+
+use ::serde::de::{Error, Unexpected, Visitor};
+use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{CompactDMXAddress, DMXAddress, DMXFootprint, DMXParseError};
+
+impl Serialize for DMXAddress {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            as_string::serialize(self, serializer)
+        } else {
+            as_absolute::serialize(self, serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for DMXAddress {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(DMXAddressVisitor)
+        } else {
+            as_absolute::deserialize(deserializer)
+        }
+    }
+}
+
+///Serialized the same way as `DMXAddress`
+impl Serialize for CompactDMXAddress {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        DMXAddress::from(*self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for CompactDMXAddress {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        DMXAddress::deserialize(deserializer).map(CompactDMXAddress::from)
+    }
+}
+
+///The error is serialized with its message
+impl Serialize for DMXParseError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+///Mirror of `DMXFootprint` used for (de)serialization
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "DMXFootprint")]
+struct DMXFootprintStruct {
+    start: DMXAddress,
+    width: u16,
+}
+
+///The footprint is serialized as struct with the fields start and width
+impl Serialize for DMXFootprint {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        DMXFootprintStruct {
+            start: self.start,
+            width: self.width,
+        }
+        .serialize(serializer)
+    }
+}
+
+///The footprint is validated like `DMXFootprint::new`
+impl<'de> Deserialize<'de> for DMXFootprint {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = DMXFootprintStruct::deserialize(deserializer)?;
+        DMXFootprint::new(value.start, value.width).map_err(D::Error::custom)
+    }
+}
+
+///(De)serializes a `DMXAddress` always as string in the format 'universe.address'. Use with `#[serde(with = "dmx_struct::serde::as_string")]`
+pub mod as_string {
+    use ::serde::{Deserializer, Serializer};
+
+    use crate::DMXAddress;
+
+    use super::DMXAddressVisitor;
+
+    ///Serializes the `DMXAddress` in the format 'universe.address'
+    pub fn serialize<S>(address: &DMXAddress, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(address)
+    }
+
+    ///Deserializes a `DMXAddress` from a string with the same rules as `TryFrom<&str>`
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<DMXAddress, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(DMXAddressVisitor)
+    }
+}
+
+///(De)serializes a `DMXAddress` always as absolute dmx address (`u32`). Use with `#[serde(with = "dmx_struct::serde::as_absolute")]`
+pub mod as_absolute {
+    use ::serde::{Deserializer, Serializer};
+
+    use crate::DMXAddress;
+
+    use super::DMXAddressVisitor;
+
+    ///Serializes the absolute dmx address of the `DMXAddress`
+    pub fn serialize<S>(address: &DMXAddress, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u32(address.absolute)
+    }
+
+    ///Deserializes a `DMXAddress` from an absolute dmx address
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<DMXAddress, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_u32(DMXAddressVisitor)
+    }
+}
+
+///(De)serializes a `DMXAddress` as struct with all fields. Use with `#[serde(with = "dmx_struct::serde::as_struct")]`
+///
+///On deserialization universe, address and absolute must describe the same dmx address
+pub mod as_struct {
+    use ::serde::de::Error;
+    use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::DMXAddress;
+
+    ///Mirror of `DMXAddress` used for the struct representation
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "DMXAddress")]
+    struct DMXAddressStruct {
+        universe: u16,
+        address: u16,
+        absolute: u32,
+    }
+
+    ///Serializes the `DMXAddress` as struct with the fields universe, address and absolute
+    pub fn serialize<S>(address: &DMXAddress, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        DMXAddressStruct {
+            universe: address.universe,
+            address: address.address,
+            absolute: address.absolute,
+        }
+        .serialize(serializer)
+    }
+
+    ///Deserializes a `DMXAddress` from a struct with the fields universe, address and absolute
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<DMXAddress, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = DMXAddressStruct::deserialize(deserializer)?;
+        let address = DMXAddress::from_parts(value.universe.into(), value.address.into())
+            .map_err(|_| D::Error::custom("universe or address of dmx address out of range"))?;
+        if address.absolute != value.absolute {
+            return Err(D::Error::custom(
+                "absolute dmx address does not match universe and address",
+            ));
+        }
+        Ok(address)
+    }
+}
+
+#[cfg(feature = "std")]
+mod patch {
+    use ::serde::de::Error;
+    use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::{BoundaryPolicy, DMXAddress, DMXFootprint, DMXPatch};
+
+    ///Version of the schema of the patch. Increased on every change that is not backwards compatible
+    const VERSION: u32 = 1;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "DMXPatch")]
+    struct DMXPatchStruct<K> {
+        version: u32,
+        #[serde(default)]
+        policy: BoundaryPolicy,
+        entries: Vec<EntryStruct<K>>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "Entry")]
+    struct EntryStruct<K> {
+        fixture: K,
+        start: DMXAddress,
+        width: u16,
+    }
+
+    impl<K: Serialize + Ord + Clone> Serialize for DMXPatch<K> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            DMXPatchStruct {
+                version: VERSION,
+                policy: self.policy(),
+                entries: self
+                    .iter()
+                    .map(|(id, footprint)| EntryStruct {
+                        fixture: id.clone(),
+                        start: footprint.start,
+                        width: footprint.width,
+                    })
+                    .collect(),
+            }
+            .serialize(serializer)
+        }
+    }
+
+    ///The patch is validated on deserialization, so entries with invalid or overlapping footprints are rejected
+    impl<'de, K: Deserialize<'de> + Ord + Clone> Deserialize<'de> for DMXPatch<K> {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let value = DMXPatchStruct::<K>::deserialize(deserializer)?;
+            if value.version != VERSION {
+                return Err(D::Error::custom(format_args!(
+                    "unsupported patch version {}, expected {}",
+                    value.version, VERSION
+                )));
+            }
+            let mut patch = DMXPatch::with_policy(value.policy);
+            for (index, entry) in value.entries.into_iter().enumerate() {
+                let footprint =
+                    DMXFootprint::new(entry.start, entry.width).map_err(D::Error::custom)?;
+                if patch.get(&entry.fixture).is_some() {
+                    return Err(D::Error::custom(format_args!(
+                        "the fixture of entry {} is patched twice",
+                        index
+                    )));
+                }
+                patch.insert(entry.fixture, footprint).map_err(|_| {
+                    D::Error::custom(format_args!(
+                        "the footprint of entry {} overlaps another fixture",
+                        index
+                    ))
+                })?;
+            }
+            Ok(patch)
+        }
+    }
+}
+
+///Visitor that accepts dmx addresses as str or as absolute integer
+struct DMXAddressVisitor;
+
+impl<'de> Visitor<'de> for DMXAddressVisitor {
+    type Value = DMXAddress;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("a dmx address like '1.234' or an absolute dmx address")
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        u32::try_from(v)
+            .ok()
+            .and_then(|v| DMXAddress::from_absolute(v).ok())
+            .ok_or_else(|| E::invalid_value(Unexpected::Unsigned(v), &self))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        u64::try_from(v)
+            .map_err(|_| E::invalid_value(Unexpected::Signed(v), &self))
+            .and_then(|v| self.visit_u64(v))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        DMXAddress::try_from(v).map_err(|_| E::invalid_value(Unexpected::Str(v), &self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use serde::{Deserialize, Serialize};
+    use serde_test::{
+        assert_de_tokens, assert_de_tokens_error, assert_ser_tokens, assert_tokens, Configure,
+        Readable, Token,
+    };
+
+    #[cfg(feature = "std")]
+    use crate::{BoundaryPolicy, DMXPatch};
+    use crate::{CompactDMXAddress, DMXAddress, DMXFootprint, DMXParseError};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct AsString(#[serde(with = "crate::serde::as_string")] DMXAddress);
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct AsAbsolute(#[serde(with = "crate::serde::as_absolute")] DMXAddress);
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct AsStruct(#[serde(with = "crate::serde::as_struct")] DMXAddress);
+
+    fn as_struct_tokens(universe: u16, address: u16, absolute: u32) -> [Token; 9] {
+        [
+            Token::NewtypeStruct { name: "AsStruct" },
+            Token::Struct {
+                name: "DMXAddress",
+                len: 3,
+            },
+            Token::Str("universe"),
+            Token::U16(universe),
+            Token::Str("address"),
+            Token::U16(address),
+            Token::Str("absolute"),
+            Token::U32(absolute),
+            Token::StructEnd,
+        ]
+    }
+
+    #[test]
+    fn test_serialize() {
+        assert_tokens(
+            &DMXAddress::try_from("4.465").unwrap().readable(),
+            &[Token::Str("4.465")],
+        );
+    }
+
+    #[test]
+    fn test_serialize_2() {
+        assert_tokens(
+            &DMXAddress::try_from("1.12").unwrap().readable(),
+            &[Token::Str("1.012")],
+        );
+    }
+
+    #[test]
+    fn test_serialize_compact() {
+        assert_tokens(
+            &DMXAddress::try_from("4.465").unwrap().compact(),
+            &[Token::U32(2001)],
+        );
+    }
+
+    #[test]
+    fn test_serialize_compact_address() {
+        let compact = CompactDMXAddress::try_from("4.465").unwrap();
+        assert_tokens(&compact.readable(), &[Token::Str("4.465")]);
+        assert_tokens(&compact.compact(), &[Token::U32(2001)]);
+        assert_de_tokens_error::<Readable<CompactDMXAddress>>(
+            &[Token::Str("1.513")],
+            "invalid value: string \"1.513\", expected a dmx address like '1.234' or an absolute dmx address",
+        );
+    }
+
+    #[test]
+    fn test_deserialize_str() {
+        assert_de_tokens(
+            &DMXAddress::try_from("1.12").unwrap().readable(),
+            &[Token::Str("1.12")],
+        );
+    }
+
+    #[test]
+    fn test_deserialize_absolute_str() {
+        assert_de_tokens(
+            &DMXAddress::try_from("3.210").unwrap().readable(),
+            &[Token::Str("1234")],
+        );
+    }
+
+    #[test]
+    fn test_deserialize_absolute() {
+        assert_de_tokens(
+            &DMXAddress::try_from("3.210").unwrap().readable(),
+            &[Token::U32(1234)],
+        );
+    }
+
+    #[test]
+    fn test_deserialize_absolute_2() {
+        assert_de_tokens(
+            &DMXAddress::try_from("1.512").unwrap().readable(),
+            &[Token::I64(512)],
+        );
+    }
+
+    #[test]
+    fn test_deserialize_invalid() {
+        assert_de_tokens_error::<Readable<DMXAddress>>(
+            &[Token::Str("2.513")],
+            "invalid value: string \"2.513\", expected a dmx address like '1.234' or an absolute dmx address",
+        );
+    }
+
+    #[test]
+    fn test_deserialize_invalid_2() {
+        assert_de_tokens_error::<Readable<DMXAddress>>(
+            &[Token::U32(0)],
+            "invalid value: integer `0`, expected a dmx address like '1.234' or an absolute dmx address",
+        );
+    }
+
+    #[test]
+    fn test_deserialize_invalid_3() {
+        assert_de_tokens_error::<Readable<DMXAddress>>(
+            &[Token::I32(-3)],
+            "invalid value: integer `-3`, expected a dmx address like '1.234' or an absolute dmx address",
+        );
+    }
+
+    #[test]
+    fn test_deserialize_invalid_4() {
+        assert_de_tokens_error::<Readable<DMXAddress>>(
+            &[Token::U64(u64::MAX)],
+            "invalid value: integer `18446744073709551615`, expected a dmx address like '1.234' or an absolute dmx address",
+        );
+    }
+
+    #[test]
+    fn test_as_string() {
+        assert_tokens(
+            &AsString(DMXAddress::try_from("2.4").unwrap()).compact(),
+            &[
+                Token::NewtypeStruct { name: "AsString" },
+                Token::Str("2.004"),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_as_string_invalid() {
+        assert_de_tokens_error::<AsString>(
+            &[
+                Token::NewtypeStruct { name: "AsString" },
+                Token::Str("0.1"),
+            ],
+            "invalid value: string \"0.1\", expected a dmx address like '1.234' or an absolute dmx address",
+        );
+    }
+
+    #[test]
+    fn test_as_absolute() {
+        assert_tokens(
+            &AsAbsolute(DMXAddress::try_from("2.4").unwrap()).readable(),
+            &[Token::NewtypeStruct { name: "AsAbsolute" }, Token::U32(516)],
+        );
+    }
+
+    #[test]
+    fn test_as_absolute_invalid() {
+        assert_de_tokens_error::<AsAbsolute>(
+            &[
+                Token::NewtypeStruct { name: "AsAbsolute" },
+                Token::U32(32_767_489),
+            ],
+            "invalid value: integer `32767489`, expected a dmx address like '1.234' or an absolute dmx address",
+        );
+    }
+
+    #[test]
+    fn test_as_struct() {
+        assert_tokens(
+            &AsStruct(DMXAddress::try_from("4.465").unwrap()),
+            &as_struct_tokens(4, 465, 2001),
+        );
+    }
+
+    #[test]
+    fn test_as_struct_invalid() {
+        assert_de_tokens_error::<AsStruct>(
+            &as_struct_tokens(4, 465, 2000),
+            "absolute dmx address does not match universe and address",
+        );
+    }
+
+    #[test]
+    fn test_as_struct_invalid_2() {
+        assert_de_tokens_error::<AsStruct>(
+            &as_struct_tokens(4, 513, 2049),
+            "universe or address of dmx address out of range",
+        );
+    }
+
+    #[test]
+    fn test_serialize_error() {
+        assert_ser_tokens(
+            &DMXParseError::InvalidAddress(513),
+            &[Token::Str(
+                "address 513 is out of range, it must be between 1 and 512",
+            )],
+        );
+    }
+
+    #[test]
+    fn test_footprint() {
+        let footprint = DMXFootprint::new(DMXAddress::try_from("1.20").unwrap(), 30).unwrap();
+        assert_tokens(
+            &footprint.readable(),
+            &[
+                Token::Struct {
+                    name: "DMXFootprint",
+                    len: 2,
+                },
+                Token::Str("start"),
+                Token::Str("1.020"),
+                Token::Str("width"),
+                Token::U16(30),
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_footprint_invalid() {
+        assert_de_tokens_error::<Readable<DMXFootprint>>(
+            &[
+                Token::Struct {
+                    name: "DMXFootprint",
+                    len: 2,
+                },
+                Token::Str("start"),
+                Token::Str("1.020"),
+                Token::Str("width"),
+                Token::U16(0),
+                Token::StructEnd,
+            ],
+            "footprint width 0 is not valid, a footprint must have at least 1 slot",
+        );
+    }
+
+    #[cfg(feature = "std")]
+    fn patch() -> DMXPatch<u32> {
+        let mut patch = DMXPatch::with_policy(BoundaryPolicy::WrapToNextUniverse);
+        patch
+            .insert(
+                102,
+                DMXFootprint::new(DMXAddress::try_from("2.1").unwrap(), 16).unwrap(),
+            )
+            .unwrap();
+        patch
+            .insert(
+                101,
+                DMXFootprint::new(DMXAddress::try_from("1.1").unwrap(), 30).unwrap(),
+            )
+            .unwrap();
+        patch
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_patch_json() {
+        let json = r#"{"version":1,"policy":"wrap_to_next_universe","entries":[{"fixture":101,"start":"1.001","width":30},{"fixture":102,"start":"2.001","width":16}]}"#;
+        assert_eq!(json, serde_json::to_string(&patch()).unwrap());
+        assert_eq!(
+            patch(),
+            serde_json::from_str::<DMXPatch<u32>>(json).unwrap()
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_patch_json_default_policy() {
+        let patch: DMXPatch<String> = serde_json::from_str(
+            r#"{"version":1,"entries":[{"fixture":"spot","start":513,"width":30}]}"#,
+        )
+        .unwrap();
+        assert_eq!(BoundaryPolicy::Allow, patch.policy());
+        assert_eq!(
+            Some(&DMXFootprint::new(DMXAddress::try_from("2.1").unwrap(), 30).unwrap()),
+            patch.get(&"spot".to_string())
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_patch_json_invalid() {
+        let error = |json: &str| {
+            serde_json::from_str::<DMXPatch<u32>>(json)
+                .unwrap_err()
+                .to_string()
+        };
+        assert!(error(r#"{"version":2,"entries":[]}"#)
+            .starts_with("unsupported patch version 2, expected 1"));
+        assert!(error(
+            r#"{"version":1,"entries":[{"fixture":1,"start":"1.1","width":30},{"fixture":2,"start":"1.30","width":1}]}"#
+        )
+        .starts_with("the footprint of entry 1 overlaps another fixture"));
+        assert!(error(
+            r#"{"version":1,"entries":[{"fixture":1,"start":"1.1","width":1},{"fixture":1,"start":"1.30","width":1}]}"#
+        )
+        .starts_with("the fixture of entry 1 is patched twice"));
+        assert!(
+            error(r#"{"version":1,"entries":[{"fixture":1,"start":"1.513","width":1}]}"#)
+                .starts_with("invalid value: string \"1.513\"")
+        );
+    }
+}
@@ -0,0 +1,270 @@
+//!Holds a compact set of dmx addresses that stores one bitmap per universe. Only available with the feature `std`
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+
+use crate::DMXAddress;
+
+///Number of 64 bit words of the bitmap of one universe
+const WORDS: usize = DMXAddress::ADDRESSES_PER_UNIVERSE as usize / 64;
+
+///Set of dmx addresses, eg. all addresses used by a group of fixtures. Each universe that holds at least one address is stored as bitmap of 64 bytes. Addresses are iterated in ascending order
+///
+///```rust
+/// use std::convert::TryFrom;
+///
+/// use dmx_struct::{DMXAddress, DMXAddressSet, DMXFootprint};
+///
+/// let a: DMXAddressSet = DMXFootprint::new(DMXAddress::try_from("1.1").unwrap(), 30).unwrap().addresses().collect();
+/// let b: DMXAddressSet = DMXFootprint::new(DMXAddress::try_from("1.11").unwrap(), 30).unwrap().addresses().collect();
+/// let difference = a.difference(&b);
+/// assert_eq!(10, difference.len());
+/// assert_eq!(Some(DMXAddress::try_from("1.10").unwrap()), difference.iter().last());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DMXAddressSet {
+    //Universes without any address are never stored, so equal sets are always stored equally
+    universes: BTreeMap<u16, [u64; WORDS]>,
+}
+
+impl DMXAddressSet {
+    ///Creates an empty set
+    pub fn new() -> Self {
+        DMXAddressSet {
+            universes: BTreeMap::new(),
+        }
+    }
+
+    ///Adds the address to the set. Returns true if the address was not in the set before. Addresses outside of 1-512 are never added
+    pub fn insert(&mut self, address: DMXAddress) -> bool {
+        let (word, bit) = match position(&address) {
+            Some(position) => position,
+            None => return false,
+        };
+        let words = self.universes.entry(address.universe).or_insert([0; WORDS]);
+        let inserted = words[word] & bit == 0;
+        words[word] |= bit;
+        inserted
+    }
+
+    ///Removes the address from the set. Returns true if the address was in the set
+    pub fn remove(&mut self, address: &DMXAddress) -> bool {
+        let (word, bit) = match position(address) {
+            Some(position) => position,
+            None => return false,
+        };
+        let words = match self.universes.get_mut(&address.universe) {
+            Some(words) => words,
+            None => return false,
+        };
+        let removed = words[word] & bit != 0;
+        words[word] &= !bit;
+        if words.iter().all(|word| *word == 0) {
+            self.universes.remove(&address.universe);
+        }
+        removed
+    }
+
+    ///Returns true if the address is in the set
+    pub fn contains(&self, address: &DMXAddress) -> bool {
+        match (position(address), self.universes.get(&address.universe)) {
+            (Some((word, bit)), Some(words)) => words[word] & bit != 0,
+            _ => false,
+        }
+    }
+
+    ///Returns the number of addresses in the set
+    pub fn len(&self) -> usize {
+        self.universes
+            .values()
+            .flat_map(|words| words.iter())
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    ///Returns true if the set does not hold any address
+    pub fn is_empty(&self) -> bool {
+        self.universes.is_empty()
+    }
+
+    ///Returns a set with the addresses that are in self or in other
+    pub fn union(&self, other: &DMXAddressSet) -> DMXAddressSet {
+        let mut union = self.clone();
+        for (universe, words) in &other.universes {
+            let target = union.universes.entry(*universe).or_insert([0; WORDS]);
+            for (target, word) in target.iter_mut().zip(words) {
+                *target |= word;
+            }
+        }
+        union
+    }
+
+    ///Returns a set with the addresses that are in self and in other
+    pub fn intersection(&self, other: &DMXAddressSet) -> DMXAddressSet {
+        self.combine(other, |a, b| a & b)
+    }
+
+    ///Returns a set with the addresses that are in self but not in other
+    pub fn difference(&self, other: &DMXAddressSet) -> DMXAddressSet {
+        self.combine(other, |a, b| a & !b)
+    }
+
+    ///Returns all addresses of the set in ascending order
+    pub fn iter(&self) -> impl Iterator<Item = DMXAddress> + '_ {
+        self.universes.iter().flat_map(|(universe, words)| {
+            let universe = *universe;
+            words.iter().enumerate().flat_map(move |(index, word)| {
+                let word = *word;
+                (0..64u16)
+                    .filter(move |bit| word & (1 << bit) != 0)
+                    .map(move |bit| {
+                        let address = index as u16 * 64 + bit + 1;
+                        DMXAddress {
+                            universe,
+                            address,
+                            absolute: u32::from(address)
+                                + (u32::from(universe) - 1)
+                                    * u32::from(DMXAddress::ADDRESSES_PER_UNIVERSE),
+                        }
+                    })
+            })
+        })
+    }
+
+    ///Combines the bitmaps of the universes of self with the ones of other. Universes that are only in other are ignored
+    fn combine(&self, other: &DMXAddressSet, op: impl Fn(u64, u64) -> u64) -> DMXAddressSet {
+        let empty = [0; WORDS];
+        let universes = self
+            .universes
+            .iter()
+            .filter_map(|(universe, words)| {
+                let others = other.universes.get(universe).unwrap_or(&empty);
+                let mut combined = [0; WORDS];
+                for ((combined, a), b) in combined.iter_mut().zip(words).zip(others) {
+                    *combined = op(*a, *b);
+                }
+                if combined.iter().all(|word| *word == 0) {
+                    None
+                } else {
+                    Some((*universe, combined))
+                }
+            })
+            .collect();
+        DMXAddressSet { universes }
+    }
+}
+
+///Returns the word and the bit of the address in the bitmap of its universe. Returns None if the address is out of range
+fn position(address: &DMXAddress) -> Option<(usize, u64)> {
+    if address.universe == 0
+        || address.address == 0
+        || address.address > DMXAddress::ADDRESSES_PER_UNIVERSE
+    {
+        return None;
+    }
+    let index = usize::from(address.address - 1);
+    Some((index / 64, 1 << (index % 64)))
+}
+
+impl FromIterator<DMXAddress> for DMXAddressSet {
+    fn from_iter<I: IntoIterator<Item = DMXAddress>>(iter: I) -> Self {
+        let mut set = DMXAddressSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<DMXAddress> for DMXAddressSet {
+    fn extend<I: IntoIterator<Item = DMXAddress>>(&mut self, iter: I) {
+        for address in iter {
+            self.insert(address);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_helpers::address;
+    use crate::{DMXAddress, DMXAddressSet};
+
+    fn set(values: &[&str]) -> DMXAddressSet {
+        values.iter().map(|value| address(value)).collect()
+    }
+
+    #[test]
+    fn test_insert_remove() {
+        let mut set = DMXAddressSet::new();
+        assert!(set.is_empty());
+        assert!(set.insert(address("1.1")));
+        assert!(!set.insert(address("1.1")));
+        assert!(set.insert(address("2.512")));
+        assert_eq!(2, set.len());
+        assert!(set.contains(&address("1.1")));
+        assert!(set.contains(&address("2.512")));
+        assert!(!set.contains(&address("1.2")));
+        assert!(!set.contains(&address("3.1")));
+        assert!(set.remove(&address("2.512")));
+        assert!(!set.remove(&address("2.512")));
+        assert!(!set.remove(&address("5.1")));
+        assert_eq!(set, self::set(&["1.1"]));
+        assert!(set.remove(&address("1.1")));
+        assert!(set.is_empty());
+        assert_eq!(DMXAddressSet::default(), set);
+    }
+
+    #[test]
+    fn test_insert_invalid() {
+        let mut set = DMXAddressSet::new();
+        assert!(!set.insert(DMXAddress {
+            universe: 1,
+            address: 513,
+            absolute: 513
+        }));
+        assert!(!set.insert(DMXAddress {
+            universe: 1,
+            address: 0,
+            absolute: 0
+        }));
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_iter() {
+        let set = set(&["3.1", "1.64", "1.65", "1.512", "1.1"]);
+        let addresses: Vec<DMXAddress> = set.iter().collect();
+        assert_eq!(
+            vec![
+                address("1.1"),
+                address("1.64"),
+                address("1.65"),
+                address("1.512"),
+                address("3.1")
+            ],
+            addresses
+        );
+        assert_eq!(1025, addresses[4].absolute);
+        assert_eq!(0, DMXAddressSet::new().iter().count());
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = set(&["1.1", "1.2", "2.100"]);
+        let b = set(&["1.2", "3.1"]);
+        assert_eq!(set(&["1.1", "1.2", "2.100", "3.1"]), a.union(&b));
+        assert_eq!(set(&["1.2"]), a.intersection(&b));
+        assert_eq!(set(&["1.1", "2.100"]), a.difference(&b));
+        assert_eq!(set(&["3.1"]), b.difference(&a));
+        assert!(a.difference(&a).is_empty());
+        assert_eq!(
+            DMXAddressSet::new(),
+            set(&["1.1"]).intersection(&set(&["1.2"]))
+        );
+    }
+
+    #[test]
+    fn test_extend() {
+        let mut set = set(&["1.1"]);
+        set.extend(vec![address("1.1"), address("63999.512")]);
+        assert_eq!(2, set.len());
+        assert_eq!(Some(address("63999.512")), set.iter().last());
+    }
+}
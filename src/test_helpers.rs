@@ -0,0 +1,14 @@
+//!Holds helpers that build values from their notation for the tests of all modules
+use std::convert::TryFrom;
+
+use crate::{DMXAddress, DMXFootprint};
+
+///Parses a dmx address that is known to be valid, eg. 1.234
+pub(crate) fn address(value: &str) -> DMXAddress {
+    DMXAddress::try_from(value).unwrap()
+}
+
+///Creates a footprint of the width at a start address that is known to be valid
+pub(crate) fn footprint(start: &str, width: u16) -> DMXFootprint {
+    DMXFootprint::new(address(start), width).unwrap()
+}
@@ -0,0 +1,287 @@
+//!Implements import and export of `DMXPatch` in the USITT ASCII show file format that is still used by paperwork tools and consoles. Only available with the feature `std`
+//!
+//!The patch is stored in `Patch` records of the form `Patch page channel dimmer level`. The channel is the id of the fixture and the dimmer is parsed like `TryFrom<&str>` (eg. 746 or 2.234). The exporter writes one dimmer per record for every slot of the footprint with level FL. The importer accepts several channel, dimmer and level triples per record and joins the dimmers of a channel into one footprint, so they must be consecutive. All other records are ignored
+//!
+//!```rust
+//! use dmx_struct::DMXPatch;
+//!
+//! let ascii = "Ident 3:0\nPatch 1 101 1 FL\nPatch 1 101 2 FL\nEndData\n";
+//! let patch: DMXPatch<u32> = DMXPatch::read_usitt_ascii(ascii.as_bytes()).unwrap();
+//! assert_eq!(2, patch.get(&101).unwrap().width);
+//! let mut out = Vec::new();
+//! patch.write_usitt_ascii(&mut out).unwrap();
+//! assert_eq!(ascii, String::from_utf8(out).unwrap());
+//! ```
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::str::FromStr;
+
+use crate::{DMXAddress, DMXFootprint, DMXParseError, DMXPatch};
+
+///Error returned if a USITT ASCII file can not be read into or written from a `DMXPatch`. Errors of a record hold the line number in the file starting at 1
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum PatchAsciiError {
+    ///The file could not be read or written
+    Io(std::io::Error),
+    ///A `Patch` record does not consist of a page followed by triples of channel, dimmer and level
+    InvalidRecord {
+        ///Line of the record in the file
+        line: usize,
+    },
+    ///The channel of a record could not be parsed as fixture id
+    InvalidChannel {
+        ///Line of the record in the file
+        line: usize,
+    },
+    ///The dimmer of a record is not a valid dmx address
+    InvalidDimmer {
+        ///Line of the record in the file
+        line: usize,
+        ///The reason why the dimmer is invalid
+        source: DMXParseError,
+    },
+    ///The dimmers of a channel are not consecutive, so they do not form one footprint
+    NotConsecutive {
+        ///Line of the record with the dimmer that does not follow the previous dimmers of the channel
+        line: usize,
+    },
+    ///The footprint of a channel overlaps the footprint of another channel
+    Collision {
+        ///Line of the first record of the channel
+        line: usize,
+        ///The other channel
+        fixture: String,
+    },
+}
+
+impl<K: Ord + Clone + FromStr + Display> DMXPatch<K> {
+    ///Reads a patch from the `Patch` records of a USITT ASCII file. Reading stops at the record `EndData`. Returns an error with the line number of the first invalid record
+    pub fn read_usitt_ascii<R: Read>(reader: R) -> Result<DMXPatch<K>, PatchAsciiError> {
+        //Absolute dimmers of every channel together with the line they were patched in
+        let mut channels: BTreeMap<K, Vec<(u32, usize)>> = BTreeMap::new();
+        for (index, text) in BufReader::new(reader).lines().enumerate() {
+            let text = text?;
+            let line = index + 1;
+            //Everything after ! is a comment
+            let text = text.split('!').next().unwrap_or("");
+            let mut fields = text
+                .split(|c: char| c.is_ascii_whitespace() || c == ',')
+                .filter(|field| !field.is_empty());
+            let keyword = match fields.next() {
+                Some(keyword) => keyword,
+                None => continue,
+            };
+            if keyword.eq_ignore_ascii_case("enddata") {
+                break;
+            }
+            if !keyword.eq_ignore_ascii_case("patch") {
+                continue;
+            }
+            let fields: Vec<&str> = fields.collect();
+            if fields.len() < 4 || (fields.len() - 1) % 3 != 0 || parse_level(fields[0]).is_none() {
+                return Err(PatchAsciiError::InvalidRecord { line });
+            }
+            for triple in fields[1..].chunks(3) {
+                let channel =
+                    K::from_str(triple[0]).map_err(|_| PatchAsciiError::InvalidChannel { line })?;
+                let dimmer = DMXAddress::try_from(triple[1])
+                    .map_err(|source| PatchAsciiError::InvalidDimmer { line, source })?;
+                if parse_level(triple[2]).is_none() {
+                    return Err(PatchAsciiError::InvalidRecord { line });
+                }
+                channels
+                    .entry(channel)
+                    .or_default()
+                    .push((dimmer.absolute, line));
+            }
+        }
+        let mut patch = DMXPatch::new();
+        for (channel, mut dimmers) in channels {
+            let line = dimmers[0].1;
+            dimmers.sort_unstable();
+            for pair in dimmers.windows(2) {
+                if pair[1].0 != pair[0].0 + 1 {
+                    return Err(PatchAsciiError::NotConsecutive {
+                        line: pair[0].1.max(pair[1].1),
+                    });
+                }
+            }
+            //All dimmers are valid and consecutive, so the footprint ends at a valid dmx address
+            let start = DMXAddress::from_absolute(dimmers[0].0)
+                .map_err(|source| PatchAsciiError::InvalidDimmer { line, source })?;
+            let width = u16::try_from(dimmers.len())
+                .map_err(|_| PatchAsciiError::NotConsecutive { line })?;
+            let footprint = DMXFootprint::new(start, width)
+                .map_err(|_| PatchAsciiError::NotConsecutive { line })?;
+            patch
+                .insert(channel, footprint)
+                .map_err(|collision| PatchAsciiError::Collision {
+                    line,
+                    fixture: collision.id.to_string(),
+                })?;
+        }
+        Ok(patch)
+    }
+
+    ///Writes the patch as USITT ASCII file with one `Patch` record on page 1 for every slot of a footprint in ascending order of the addresses. Dimmers are written as absolute dmx addresses
+    pub fn write_usitt_ascii<W: Write>(&self, mut writer: W) -> Result<(), PatchAsciiError> {
+        writeln!(writer, "Ident 3:0")?;
+        for (id, footprint) in self.iter() {
+            for address in footprint.addresses() {
+                writeln!(writer, "Patch 1 {} {} FL", id, address.absolute)?;
+            }
+        }
+        writeln!(writer, "EndData")?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+///Parses a page or a level of a `Patch` record. Levels are a percentage or FL for full
+fn parse_level(value: &str) -> Option<u32> {
+    if value.eq_ignore_ascii_case("fl") {
+        return Some(100);
+    }
+    u32::from_str(value).ok()
+}
+
+impl From<std::io::Error> for PatchAsciiError {
+    fn from(error: std::io::Error) -> Self {
+        PatchAsciiError::Io(error)
+    }
+}
+
+impl Display for PatchAsciiError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PatchAsciiError::Io(error) => write!(f, "could not read or write the file: {}", error),
+            PatchAsciiError::InvalidRecord { line } => {
+                write!(f, "line {}: the patch record is not valid", line)
+            }
+            PatchAsciiError::InvalidChannel { line } => {
+                write!(f, "line {}: the channel is not valid", line)
+            }
+            PatchAsciiError::InvalidDimmer { line, source } => {
+                write!(f, "line {}: invalid dimmer: {}", line, source)
+            }
+            PatchAsciiError::NotConsecutive { line } => write!(
+                f,
+                "line {}: the dimmers of the channel are not consecutive",
+                line
+            ),
+            PatchAsciiError::Collision { line, fixture } => {
+                write!(f, "line {}: the dimmers overlap channel {}", line, fixture)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PatchAsciiError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PatchAsciiError::Io(error) => Some(error),
+            PatchAsciiError::InvalidDimmer { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_helpers::footprint;
+    use crate::{DMXParseError, DMXPatch, PatchAsciiError};
+
+    fn read(ascii: &str) -> Result<DMXPatch<u32>, PatchAsciiError> {
+        DMXPatch::read_usitt_ascii(ascii.as_bytes())
+    }
+
+    #[test]
+    fn test_read() {
+        let patch = read("Ident 3:0\n! patch\nManufacturer ETC\nPATCH 1 1 1 FL 1 2 FL, 2 513 100\npatch 1 1 3 FL\nPatch 1 3 2.2 50 ! comment\nEndData\nPatch 1 4 abc FL\n").unwrap();
+        assert_eq!(3, patch.len());
+        assert_eq!(Some(&footprint("1.1", 3)), patch.get(&1));
+        assert_eq!(Some(&footprint("2.1", 1)), patch.get(&2));
+        assert_eq!(Some(&footprint("2.2", 1)), patch.get(&3));
+    }
+
+    #[test]
+    fn test_read_unordered() {
+        let patch = read("Patch 1 1 12 FL\nPatch 1 1 10 FL\nPatch 1 1 11 FL\n").unwrap();
+        assert_eq!(Some(&footprint("1.10", 3)), patch.get(&1));
+    }
+
+    #[test]
+    fn test_read_invalid_record() {
+        assert!(matches!(
+            read("Ident 3:0\nPatch 1 1 1\n"),
+            Err(PatchAsciiError::InvalidRecord { line: 2 })
+        ));
+        assert!(matches!(
+            read("Patch\n"),
+            Err(PatchAsciiError::InvalidRecord { line: 1 })
+        ));
+        assert!(matches!(
+            read("Patch 1 1 1 XX\n"),
+            Err(PatchAsciiError::InvalidRecord { line: 1 })
+        ));
+        assert!(matches!(
+            read("Patch 1 x 1 FL\n"),
+            Err(PatchAsciiError::InvalidChannel { line: 1 })
+        ));
+        assert!(matches!(
+            read("Patch 1 1 1 FL\nPatch 1 2 1.513 FL\n"),
+            Err(PatchAsciiError::InvalidDimmer {
+                line: 2,
+                source: DMXParseError::InvalidAddress(513)
+            })
+        ));
+    }
+
+    #[test]
+    fn test_read_not_consecutive() {
+        assert!(matches!(
+            read("Patch 1 1 1 FL\nPatch 1 1 3 FL\n"),
+            Err(PatchAsciiError::NotConsecutive { line: 2 })
+        ));
+        assert!(matches!(
+            read("Patch 1 1 1 FL\nPatch 1 1 1 FL\n"),
+            Err(PatchAsciiError::NotConsecutive { line: 2 })
+        ));
+    }
+
+    #[test]
+    fn test_read_collision() {
+        let error = read("Patch 1 1 1 FL 1 2 FL\nPatch 1 2 2 FL\n").unwrap_err();
+        assert!(matches!(
+            &error,
+            PatchAsciiError::Collision { line: 2, fixture } if fixture == "1"
+        ));
+        assert_eq!("line 2: the dimmers overlap channel 1", error.to_string());
+    }
+
+    #[test]
+    fn test_write() {
+        let mut patch = DMXPatch::new();
+        patch.insert(2, footprint("2.2", 1)).unwrap();
+        patch.insert(1, footprint("1.511", 3)).unwrap();
+        let mut out = Vec::new();
+        patch.write_usitt_ascii(&mut out).unwrap();
+        assert_eq!(
+            "Ident 3:0\nPatch 1 1 511 FL\nPatch 1 1 512 FL\nPatch 1 1 513 FL\nPatch 1 2 514 FL\nEndData\n",
+            String::from_utf8(out).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut patch = DMXPatch::new();
+        patch.insert(101, footprint("1.1", 30)).unwrap();
+        patch.insert(102, footprint("3.100", 16)).unwrap();
+        let mut out = Vec::new();
+        patch.write_usitt_ascii(&mut out).unwrap();
+        assert_eq!(patch, DMXPatch::read_usitt_ascii(out.as_slice()).unwrap());
+    }
+}
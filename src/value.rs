@@ -0,0 +1,747 @@
+//!Holds the dmx value in the GDTF notation 'value/byte-count', eg. 255/1, 65535/2 or 255/1s for byte shifting
+use core::convert::TryFrom;
+use core::fmt::{Display, Formatter};
+use core::num::ParseIntError;
+use core::str::FromStr;
+
+use crate::{DMXAddress, DMXParseError};
+
+///Dmx value together with the number of bytes (channels) it is sent with. GDTF writes it as 'value/byte-count', eg. 255/1 or 65535/2
+///
+///```rust
+/// use std::convert::TryFrom;
+///
+/// use dmx_struct::DMXValue;
+///
+/// let value = DMXValue::try_from("32768/2").unwrap();
+/// assert_eq!(32768, value.value);
+/// assert_eq!(2, value.byte_count);
+/// assert_eq!("32768/2", value.to_string());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DMXValue {
+    ///The value (0-255 for 1 byte, 0-65535 for 2 bytes, 0-16777215 for 3 bytes, 0-4294967295 for 4 bytes)
+    pub value: u32,
+    ///The number of bytes the value is sent with (1-4)
+    pub byte_count: u8,
+    ///If true, the value is converted to other byte counts with byte shifting instead of byte mirroring. GDTF writes it with the suffix s, eg. 255/1s
+    pub byte_shifting: bool,
+}
+
+impl DMXValue {
+    ///The highest number of bytes a value can be sent with
+    pub const MAX_BYTE_COUNT: u8 = 4;
+
+    ///Creates a validated dmx value. Returns an error if the byte count is not supported or the value does not fit into the bytes
+    pub fn new(value: u32, byte_count: u8) -> Result<DMXValue, DMXParseError> {
+        let max = Self::max_for(byte_count)?;
+        if value > max {
+            return Err(DMXParseError::InvalidValue { value, byte_count });
+        }
+        Ok(DMXValue {
+            value,
+            byte_count,
+            byte_shifting: false,
+        })
+    }
+
+    ///Returns the highest value that can be sent with the byte count, eg. 255 for 1 byte. Returns an error if the byte count is not supported
+    pub fn max_for(byte_count: u8) -> Result<u32, DMXParseError> {
+        if byte_count == 0 || byte_count > Self::MAX_BYTE_COUNT {
+            return Err(DMXParseError::InvalidByteCount(byte_count.into()));
+        }
+        Ok(u32::MAX >> (32 - 8 * u32::from(byte_count)))
+    }
+
+    ///Returns the highest value that can be sent with the byte count of this value, eg. 255 for 1 byte
+    pub fn max_value(&self) -> u32 {
+        Self::max_for(self.byte_count).unwrap_or(0)
+    }
+
+    ///Creates a 16 bit dmx value from its coarse (most significant) and fine (least significant) byte
+    pub fn from_coarse_fine(coarse: u8, fine: u8) -> DMXValue {
+        DMXValue {
+            value: u32::from(u16::from_be_bytes([coarse, fine])),
+            byte_count: 2,
+            byte_shifting: false,
+        }
+    }
+
+    ///Splits a 16 bit dmx value into its coarse (most significant) and fine (least significant) byte. Returns None if the value is not sent with 2 bytes or does not fit into them
+    pub fn to_coarse_fine(&self) -> Option<(u8, u8)> {
+        if self.byte_count != 2 {
+            return None;
+        }
+        let [coarse, fine] = u16::try_from(self.value).ok()?.to_be_bytes();
+        Some((coarse, fine))
+    }
+
+    ///Creates a dmx value from its bytes with the most significant byte first as they are sent in consecutive slots. The byte count is the number of bytes
+    pub fn from_bytes(bytes: &[u8]) -> Result<DMXValue, DMXParseError> {
+        let byte_count = u8::try_from(bytes.len())
+            .map_err(|_| DMXParseError::InvalidByteCount(bytes.len() as u32))?;
+        Self::max_for(byte_count)?;
+        let value = bytes
+            .iter()
+            .fold(0_u32, |value, byte| (value << 8) | u32::from(*byte));
+        Ok(DMXValue {
+            value,
+            byte_count,
+            byte_shifting: false,
+        })
+    }
+
+    ///Returns the bytes of the value with the most significant byte first as they are sent in consecutive slots, eg. 0x123456/3 returns 0x12, 0x34 and 0x56
+    pub fn bytes(&self) -> impl Iterator<Item = u8> {
+        let value = self.value;
+        (0..u32::from(self.byte_count))
+            .rev()
+            .map(move |i| (value >> (8 * i)) as u8)
+    }
+
+    ///Returns the address of the last slot the value occupies if its first (most significant) byte is sent at the address. The slots roll over to the next universe. Returns None if there are not enough slots after the address
+    pub fn last_address(&self, first: &DMXAddress) -> Option<DMXAddress> {
+        first.byte_address(self.byte_count.checked_sub(1)?)
+    }
+
+    ///Converts the value to another byte count as specified by GDTF. Reducing the byte count drops the least significant bytes. Increasing the byte count mirrors the bytes by default, so 255/1 is 65535/2 and the highest value stays the highest value. With byte shifting the value is shifted instead, so 255/1s is 65280/2s
+    ///
+    ///```rust
+    /// use std::convert::TryFrom;
+    ///
+    /// use dmx_struct::DMXValue;
+    ///
+    /// assert_eq!("65535/2", DMXValue::try_from("255/1").unwrap().to_resolution(2).unwrap().to_string());
+    /// assert_eq!("65280/2s", DMXValue::try_from("255/1s").unwrap().to_resolution(2).unwrap().to_string());
+    /// ```
+    pub fn to_resolution(&self, byte_count: u8) -> Result<DMXValue, DMXParseError> {
+        Self::max_for(byte_count)?;
+        let from = u32::from(self.byte_count);
+        let to = u32::from(byte_count);
+        let value = if to <= from {
+            self.value >> (8 * (from - to))
+        } else if self.byte_shifting {
+            self.value << (8 * (to - from))
+        } else {
+            //Byte mirroring repeats the bytes of the value starting with the most significant byte until all bytes are filled, eg. 0x12/1 is 0x1212/2 and 0x1234/2 is 0x123412/3
+            let bytes = self.value.to_be_bytes();
+            let start = (4 - from) as usize;
+            (0..to as usize).fold(0_u32, |value, i| {
+                (value << 8) | u32::from(bytes[start + i % from as usize])
+            })
+        };
+        Ok(DMXValue {
+            value,
+            byte_count,
+            byte_shifting: self.byte_shifting,
+        })
+    }
+
+    ///Creates a dmx value from a normalized value between 0.0 and 1.0, eg. 0.5 is 128/1. The value is rounded to the nearest dmx value, halves are rounded up. Values outside of 0.0 and 1.0 are clamped and NaN is 0
+    pub fn from_f32(value: f32, byte_count: u8) -> Result<DMXValue, DMXParseError> {
+        let max = Self::max_for(byte_count)?;
+        //f64 is precise enough to represent every dmx value, rounding is done by adding 0.5 before truncating
+        let value = if value.is_nan() {
+            0.0
+        } else {
+            f64::from(value).clamp(0.0, 1.0)
+        };
+        Ok(DMXValue {
+            value: (value * f64::from(max) + 0.5) as u32,
+            byte_count,
+            byte_shifting: false,
+        })
+    }
+
+    ///Returns the value normalized between 0.0 and 1.0, eg. 255/1 is 1.0
+    pub fn to_f32(&self) -> f32 {
+        let max = self.max_value();
+        if max == 0 {
+            return 0.0;
+        }
+        (f64::from(self.value) / f64::from(max)) as f32
+    }
+
+    ///Creates a dmx value from percent between 0.0 and 100.0 as used in consoles, eg. 50.0 is 128/1. Rounds and clamps like `from_f32`
+    pub fn from_percent(percent: f32, byte_count: u8) -> Result<DMXValue, DMXParseError> {
+        Self::from_f32(percent / 100.0, byte_count)
+    }
+
+    ///Returns the value in percent between 0.0 and 100.0, eg. 255/1 is 100.0
+    pub fn to_percent(&self) -> f32 {
+        let max = self.max_value();
+        if max == 0 {
+            return 0.0;
+        }
+        (f64::from(self.value) * 100.0 / f64::from(max)) as f32
+    }
+}
+
+///Error returned if a dmx value of format 'value/byte-count' can not be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DMXValueParseError {
+    ///The input was empty
+    Empty,
+    ///The value or the byte count is not a positive number. A missing byte count is reported at the end of the input
+    NotANumber {
+        ///Byte offset of the part of the input that could not be parsed
+        offset: usize,
+        ///The underlying error returned when parsing the number
+        source: ParseIntError,
+    },
+    ///The input contains more than one '/'
+    TooManySeparators {
+        ///Byte offset of the second '/' in the input
+        offset: usize,
+    },
+    ///The byte count is not supported (1-4)
+    InvalidByteCount(u32),
+    ///The value does not fit into its number of bytes
+    InvalidValue {
+        ///The value that is out of range
+        value: u32,
+        ///The number of bytes the value is sent with
+        byte_count: u8,
+    },
+}
+
+impl Display for DMXValueParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DMXValueParseError::Empty => write!(f, "the dmx value is empty"),
+            DMXValueParseError::NotANumber { offset, .. } => write!(
+                f,
+                "the dmx value must be of format 'value/byte-count' (invalid number at position {})",
+                offset
+            ),
+            DMXValueParseError::TooManySeparators { offset } => write!(
+                f,
+                "the dmx value must not contain more than one '/' between value and byte count (second '/' at position {})",
+                offset
+            ),
+            DMXValueParseError::InvalidByteCount(byte_count) => write!(
+                f,
+                "byte count {} is not valid, it must be between 1 and {}",
+                byte_count,
+                DMXValue::MAX_BYTE_COUNT
+            ),
+            DMXValueParseError::InvalidValue { value, byte_count } => write!(
+                f,
+                "dmx value {} is out of range, it must be between 0 and {} for {} byte(s)",
+                value,
+                DMXValue::max_for(*byte_count).unwrap_or(0),
+                byte_count
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DMXValueParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DMXValueParseError::NotANumber { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+///Parses one number of the input. The offset is the position of the number in the whole input and is returned in the error
+fn parse_number(value: &str, offset: usize) -> Result<u32, DMXValueParseError> {
+    u32::from_str(value).map_err(|source| DMXValueParseError::NotANumber { offset, source })
+}
+
+///Parses a dmx value of format 'value/byte-count', eg. 255/1 or 65535/2. The suffix s selects byte shifting, eg. 255/1s
+impl TryFrom<&str> for DMXValue {
+    type Error = DMXValueParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        if value.is_empty() {
+            return Err(DMXValueParseError::Empty);
+        }
+        //Without separator the byte count is missing. It is parsed as empty number at the end of the input
+        let separator = value.find('/').unwrap_or(value.len());
+        let byte_count_start = (separator + 1).min(value.len());
+        let byte_count = &value[byte_count_start..];
+        if let Some(second) = byte_count.find('/') {
+            return Err(DMXValueParseError::TooManySeparators {
+                offset: byte_count_start + second,
+            });
+        }
+        let (byte_count, byte_shifting) = match byte_count.strip_suffix('s') {
+            Some(byte_count) => (byte_count, true),
+            None => (byte_count, false),
+        };
+        let number = parse_number(&value[..separator], 0)?;
+        let byte_count = parse_number(byte_count, byte_count_start)?;
+        let byte_count = u8::try_from(byte_count)
+            .ok()
+            .filter(|byte_count| (1..=Self::MAX_BYTE_COUNT).contains(byte_count))
+            .ok_or(DMXValueParseError::InvalidByteCount(byte_count))?;
+        //The byte count is supported, so only the value can be out of range
+        DMXValue::new(number, byte_count)
+            .map(|value| DMXValue {
+                byte_shifting,
+                ..value
+            })
+            .map_err(|_| DMXValueParseError::InvalidValue {
+                value: number,
+                byte_count,
+            })
+    }
+}
+
+///Dmx values can be used in format with {}. It will return the format 'value/byte-count' with the suffix s for byte shifting
+impl Display for DMXValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}/{}", self.value, self.byte_count)?;
+        if self.byte_shifting {
+            write!(f, "s")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use crate::{DMXAddress, DMXParseError, DMXValue, DMXValueParseError};
+
+    #[test]
+    fn test_new() {
+        assert_eq!(
+            DMXValue {
+                value: 255,
+                byte_count: 1,
+                byte_shifting: false
+            },
+            DMXValue::new(255, 1).unwrap()
+        );
+        assert_eq!(
+            DMXValue {
+                value: 65535,
+                byte_count: 2,
+                byte_shifting: false
+            },
+            DMXValue::new(65535, 2).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_new_invalid() {
+        assert!(matches!(
+            DMXValue::new(256, 1),
+            Err(DMXParseError::InvalidValue {
+                value: 256,
+                byte_count: 1
+            })
+        ));
+        assert!(matches!(
+            DMXValue::new(65536, 2),
+            Err(DMXParseError::InvalidValue { value: 65536, .. })
+        ));
+        assert!(matches!(
+            DMXValue::new(0, 0),
+            Err(DMXParseError::InvalidByteCount(0))
+        ));
+        assert!(matches!(
+            DMXValue::new(0, 5),
+            Err(DMXParseError::InvalidByteCount(5))
+        ));
+        assert!(matches!(
+            DMXValue::new(16_777_216, 3),
+            Err(DMXParseError::InvalidValue { .. })
+        ));
+    }
+
+    #[test]
+    fn test_max() {
+        assert_eq!(255, DMXValue::max_for(1).unwrap());
+        assert_eq!(65535, DMXValue::max_for(2).unwrap());
+        assert_eq!(16_777_215, DMXValue::max_for(3).unwrap());
+        assert_eq!(u32::MAX, DMXValue::max_for(4).unwrap());
+        assert_eq!(255, DMXValue::new(0, 1).unwrap().max_value());
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            DMXValue {
+                value: 255,
+                byte_count: 1,
+                byte_shifting: false
+            },
+            DMXValue::try_from("255/1").unwrap()
+        );
+        assert_eq!(
+            DMXValue {
+                value: 0,
+                byte_count: 2,
+                byte_shifting: false
+            },
+            DMXValue::try_from("0/2").unwrap()
+        );
+        assert_eq!(
+            DMXValue {
+                value: 65535,
+                byte_count: 2,
+                byte_shifting: false
+            },
+            DMXValue::try_from("65535/2").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(matches!(
+            DMXValue::try_from(""),
+            Err(DMXValueParseError::Empty)
+        ));
+        assert!(matches!(
+            DMXValue::try_from("255"),
+            Err(DMXValueParseError::NotANumber { offset: 3, .. })
+        ));
+        assert!(matches!(
+            DMXValue::try_from("/1"),
+            Err(DMXValueParseError::NotANumber { offset: 0, .. })
+        ));
+        assert!(matches!(
+            DMXValue::try_from("255/1/1"),
+            Err(DMXValueParseError::TooManySeparators { offset: 5 })
+        ));
+        assert!(matches!(
+            DMXValue::try_from("255/x"),
+            Err(DMXValueParseError::NotANumber { offset: 4, .. })
+        ));
+        assert!(matches!(
+            DMXValue::try_from("-1/1"),
+            Err(DMXValueParseError::NotANumber { offset: 0, .. })
+        ));
+        assert!(matches!(
+            DMXValue::try_from("256/1"),
+            Err(DMXValueParseError::InvalidValue {
+                value: 256,
+                byte_count: 1
+            })
+        ));
+        assert!(matches!(
+            DMXValue::try_from("0/300"),
+            Err(DMXValueParseError::InvalidByteCount(300))
+        ));
+        assert!(matches!(
+            DMXValue::try_from("0/0"),
+            Err(DMXValueParseError::InvalidByteCount(0))
+        ));
+        assert!(matches!(
+            DMXValue::try_from("0/\u{e4}"),
+            Err(DMXValueParseError::NotANumber { offset: 2, .. })
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_parse_error_message() {
+        assert_eq!(
+            "the dmx value is empty",
+            DMXValue::try_from("").unwrap_err().to_string()
+        );
+        assert_eq!(
+            "the dmx value must not contain more than one '/' between value and byte count (second '/' at position 5)",
+            DMXValue::try_from("255/1/1").unwrap_err().to_string()
+        );
+        assert_eq!(
+            "dmx value 256 is out of range, it must be between 0 and 255 for 1 byte(s)",
+            DMXValue::try_from("256/1").unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!("255/1", format!("{}", DMXValue::new(255, 1).unwrap()));
+        assert_eq!("0/2", DMXValue::new(0, 2).unwrap().to_string());
+    }
+
+    #[test]
+    fn test_from_f32() {
+        assert_eq!(
+            DMXValue::new(0, 1).unwrap(),
+            DMXValue::from_f32(0.0, 1).unwrap()
+        );
+        assert_eq!(
+            DMXValue::new(255, 1).unwrap(),
+            DMXValue::from_f32(1.0, 1).unwrap()
+        );
+        //127.5 is rounded up
+        assert_eq!(
+            DMXValue::new(128, 1).unwrap(),
+            DMXValue::from_f32(0.5, 1).unwrap()
+        );
+        assert_eq!(
+            DMXValue::new(32768, 2).unwrap(),
+            DMXValue::from_f32(0.5, 2).unwrap()
+        );
+        assert_eq!(
+            DMXValue::new(65535, 2).unwrap(),
+            DMXValue::from_f32(1.0, 2).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_from_f32_clamped() {
+        assert_eq!(0, DMXValue::from_f32(-0.5, 1).unwrap().value);
+        assert_eq!(255, DMXValue::from_f32(1.5, 1).unwrap().value);
+        assert_eq!(255, DMXValue::from_f32(f32::INFINITY, 1).unwrap().value);
+        assert_eq!(0, DMXValue::from_f32(f32::NAN, 1).unwrap().value);
+        assert!(matches!(
+            DMXValue::from_f32(0.5, 0),
+            Err(DMXParseError::InvalidByteCount(0))
+        ));
+    }
+
+    #[test]
+    fn test_to_f32() {
+        assert_eq!(0.0, DMXValue::new(0, 1).unwrap().to_f32());
+        assert_eq!(1.0, DMXValue::new(255, 1).unwrap().to_f32());
+        assert_eq!(1.0, DMXValue::new(65535, 2).unwrap().to_f32());
+        assert!((DMXValue::new(51, 1).unwrap().to_f32() - 0.2).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_percent() {
+        assert_eq!(
+            DMXValue::new(128, 1).unwrap(),
+            DMXValue::from_percent(50.0, 1).unwrap()
+        );
+        assert_eq!(
+            DMXValue::new(255, 1).unwrap(),
+            DMXValue::from_percent(100.0, 1).unwrap()
+        );
+        assert_eq!(
+            DMXValue::new(26, 1).unwrap(),
+            DMXValue::from_percent(10.0, 1).unwrap()
+        );
+        assert_eq!(100.0, DMXValue::new(255, 1).unwrap().to_percent());
+        assert_eq!(20.0, DMXValue::new(51, 1).unwrap().to_percent());
+        assert_eq!(0.0, DMXValue::new(0, 2).unwrap().to_percent());
+    }
+
+    #[test]
+    fn test_percent_round_trip() {
+        for byte_count in 1..=2 {
+            for value in 0..=DMXValue::max_for(byte_count).unwrap() {
+                let value = DMXValue::new(value, byte_count).unwrap();
+                assert_eq!(
+                    value,
+                    DMXValue::from_percent(value.to_percent(), byte_count).unwrap()
+                );
+                assert_eq!(
+                    value,
+                    DMXValue::from_f32(value.to_f32(), byte_count).unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_coarse_fine() {
+        assert_eq!(
+            DMXValue::new(0x1234, 2).unwrap(),
+            DMXValue::from_coarse_fine(0x12, 0x34)
+        );
+        assert_eq!(
+            DMXValue::new(65535, 2).unwrap(),
+            DMXValue::from_coarse_fine(255, 255)
+        );
+        assert_eq!(
+            Some((0x12, 0x34)),
+            DMXValue::new(0x1234, 2).unwrap().to_coarse_fine()
+        );
+        assert_eq!(Some((0, 0)), DMXValue::new(0, 2).unwrap().to_coarse_fine());
+        assert_eq!(None, DMXValue::new(255, 1).unwrap().to_coarse_fine());
+        assert_eq!(
+            None,
+            DMXValue {
+                value: 65536,
+                byte_count: 2,
+                byte_shifting: false
+            }
+            .to_coarse_fine()
+        );
+    }
+
+    #[test]
+    fn test_parse_4_bytes() {
+        assert_eq!(
+            DMXValue {
+                value: 16_777_215,
+                byte_count: 3,
+                byte_shifting: false
+            },
+            DMXValue::try_from("16777215/3").unwrap()
+        );
+        assert_eq!(
+            DMXValue {
+                value: u32::MAX,
+                byte_count: 4,
+                byte_shifting: false
+            },
+            DMXValue::try_from("4294967295/4").unwrap()
+        );
+        assert!(matches!(
+            DMXValue::try_from("16777216/3"),
+            Err(DMXValueParseError::InvalidValue { .. })
+        ));
+    }
+
+    #[test]
+    fn test_bytes() {
+        let value = DMXValue::new(0x12_3456, 3).unwrap();
+        assert_eq!(vec![0x12, 0x34, 0x56], value.bytes().collect::<Vec<u8>>());
+        assert_eq!(value, DMXValue::from_bytes(&[0x12, 0x34, 0x56]).unwrap());
+        let value = DMXValue::new(0x1234_5678, 4).unwrap();
+        assert_eq!(
+            vec![0x12, 0x34, 0x56, 0x78],
+            value.bytes().collect::<Vec<u8>>()
+        );
+        assert_eq!(
+            value,
+            DMXValue::from_bytes(&[0x12, 0x34, 0x56, 0x78]).unwrap()
+        );
+        assert_eq!(
+            vec![0xFF],
+            DMXValue::new(255, 1).unwrap().bytes().collect::<Vec<u8>>()
+        );
+    }
+
+    #[test]
+    fn test_from_bytes_invalid() {
+        assert!(matches!(
+            DMXValue::from_bytes(&[]),
+            Err(DMXParseError::InvalidByteCount(0))
+        ));
+        assert!(matches!(
+            DMXValue::from_bytes(&[0; 5]),
+            Err(DMXParseError::InvalidByteCount(5))
+        ));
+        assert!(matches!(
+            DMXValue::from_bytes(&[0; 300]),
+            Err(DMXParseError::InvalidByteCount(300))
+        ));
+    }
+
+    #[test]
+    fn test_last_address() {
+        let first = DMXAddress::try_from("1.510").unwrap();
+        assert_eq!(
+            first,
+            DMXValue::new(0, 1).unwrap().last_address(&first).unwrap()
+        );
+        assert_eq!(
+            DMXAddress::try_from("1.512").unwrap(),
+            DMXValue::new(0, 3).unwrap().last_address(&first).unwrap()
+        );
+        assert_eq!(
+            DMXAddress::try_from("2.001").unwrap(),
+            DMXValue::new(0, 4).unwrap().last_address(&first).unwrap()
+        );
+        assert!(DMXValue::new(0, 2)
+            .unwrap()
+            .last_address(&DMXAddress::try_from("63999.512").unwrap())
+            .is_none());
+    }
+
+    #[test]
+    fn test_f32_4_bytes() {
+        assert_eq!(u32::MAX, DMXValue::from_f32(1.0, 4).unwrap().value);
+        assert_eq!(0x8000_0000, DMXValue::from_f32(0.5, 4).unwrap().value);
+        assert_eq!(1.0, DMXValue::new(u32::MAX, 4).unwrap().to_f32());
+        assert_eq!(16_777_215, DMXValue::from_percent(100.0, 3).unwrap().value);
+    }
+
+    #[test]
+    fn test_parse_byte_shifting() {
+        assert_eq!(
+            DMXValue {
+                value: 255,
+                byte_count: 1,
+                byte_shifting: true
+            },
+            DMXValue::try_from("255/1s").unwrap()
+        );
+        assert_eq!("255/1s", DMXValue::try_from("255/1s").unwrap().to_string());
+        assert!(matches!(
+            DMXValue::try_from("255/s"),
+            Err(DMXValueParseError::NotANumber { offset: 4, .. })
+        ));
+        assert!(matches!(
+            DMXValue::try_from("255/1ss"),
+            Err(DMXValueParseError::NotANumber { offset: 4, .. })
+        ));
+    }
+
+    fn resolution(value: &str, byte_count: u8) -> u32 {
+        DMXValue::try_from(value)
+            .unwrap()
+            .to_resolution(byte_count)
+            .unwrap()
+            .value
+    }
+
+    #[test]
+    fn test_to_resolution_mirroring() {
+        //Examples of the GDTF specification: 255/1 in a 16 bit channel is 65535
+        assert_eq!(65535, resolution("255/1", 2));
+        assert_eq!(16_777_215, resolution("255/1", 3));
+        assert_eq!(u32::MAX, resolution("255/1", 4));
+        assert_eq!(0, resolution("0/1", 2));
+        assert_eq!(0x8080, resolution("128/1", 2));
+        assert_eq!(0x12_3412, resolution("4660/2", 3));
+        assert_eq!(0x1234_1234, resolution("4660/2", 4));
+        assert_eq!(0x1234_5612, resolution("1193046/3", 4));
+    }
+
+    #[test]
+    fn test_to_resolution_shifting() {
+        //Examples of the GDTF specification: 255/1s in a 16 bit channel is 65280
+        assert_eq!(65280, resolution("255/1s", 2));
+        assert_eq!(0xFF_0000, resolution("255/1s", 3));
+        assert_eq!(0x8000, resolution("128/1s", 2));
+        assert_eq!(0x1234_0000, resolution("4660/2s", 4));
+    }
+
+    #[test]
+    fn test_to_resolution_reduce() {
+        assert_eq!(255, resolution("65535/2", 1));
+        assert_eq!(255, resolution("65280/2s", 1));
+        assert_eq!(0x12, resolution("4660/2", 1));
+        assert_eq!(0x1234, resolution("305419896/4", 2));
+        assert_eq!(200, resolution("200/1", 1));
+    }
+
+    #[test]
+    fn test_to_resolution_round_trip() {
+        for value in 0..=255 {
+            for byte_count in 1..=4 {
+                let value = DMXValue::new(value, 1).unwrap();
+                assert_eq!(
+                    value,
+                    value
+                        .to_resolution(byte_count)
+                        .unwrap()
+                        .to_resolution(1)
+                        .unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_to_resolution_invalid() {
+        assert!(matches!(
+            DMXValue::new(0, 1).unwrap().to_resolution(5),
+            Err(DMXParseError::InvalidByteCount(5))
+        ));
+    }
+}